@@ -1,10 +1,11 @@
 use crate::db::Database;
-use rusqlite::Result;
+use rusqlite::{OptionalExtension, Result};
 use rust_xlsxwriter::{Color, Format, FormatAlign, FormatBorder, Formula, Workbook};
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
     collections::{HashMap, HashSet},
+    path::Path,
 };
 use tauri::State;
 use uuid::Uuid;
@@ -20,6 +21,48 @@ pub struct Auction {
     pub status: String,
     pub total_lots: i32,
     pub created_at: String,
+    /// Prepended to lot numbers in HiBid exports (e.g. "W34" → "W34-042")
+    pub lot_prefix: Option<String>,
+    /// `current_user` setting at the time the auction was created
+    pub created_by: String,
+    /// Most lots the auction may hold; `None` is unlimited
+    pub max_lots: Option<i64>,
+    pub auction_type: AuctionType,
+}
+
+/// Kind of sale, which picks the commission rate charged at reconciliation
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum AuctionType {
+    /// Charged at `default_commission_rate`
+    #[default]
+    Standard,
+    /// Charged at `cash_sale_commission_rate`
+    Cash,
+}
+
+impl AuctionType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuctionType::Standard => "Standard",
+            AuctionType::Cash => "Cash",
+        }
+    }
+
+    /// Parse the stored column; anything but "Cash" is a standard auction
+    pub fn from_db(value: Option<&str>) -> Self {
+        match value {
+            Some(value) if value.trim().eq_ignore_ascii_case("cash") => AuctionType::Cash,
+            _ => AuctionType::Standard,
+        }
+    }
+
+    /// Settings key holding this auction type's commission rate
+    pub fn commission_rate_setting(self) -> &'static str {
+        match self {
+            AuctionType::Standard => "default_commission_rate",
+            AuctionType::Cash => "cash_sale_commission_rate",
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -33,12 +76,22 @@ pub struct CreateAuctionRequest {
     pub start_date: Option<String>,
     #[serde(default)]
     pub end_date: Option<String>,
+    #[serde(default)]
+    pub lot_prefix: Option<String>,
+    #[serde(default)]
+    pub max_lots: Option<i64>,
+    #[serde(default)]
+    pub auction_type: AuctionType,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateVendorRequest {
     pub cost_coefficient: f64,
     pub min_price_margin: f64,
+    #[serde(default)]
+    pub aliases: Option<Vec<String>>,
+    #[serde(default)]
+    pub commission_exempt: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -64,6 +117,65 @@ pub struct AuctionResultBid {
     pub high_bid: f64,
 }
 
+#[derive(Debug, Serialize)]
+pub struct MissingImageItem {
+    pub item_id: String,
+    pub lot_number: String,
+    pub title: String,
+}
+
+/// One page of `get_auctions_filtered`
+#[derive(Debug, Serialize)]
+pub struct AuctionPage {
+    pub auctions: Vec<Auction>,
+    /// Auctions matching the filters across all pages
+    pub total_count: i64,
+}
+
+/// Page size for `get_auctions_filtered` when no limit is given, and the largest allowed
+const DEFAULT_AUCTION_PAGE_SIZE: i64 = 50;
+const MAX_AUCTION_PAGE_SIZE: i64 = 500;
+
+/// Columns read by `map_auction_row`
+const AUCTION_COLUMNS: &str = "id, hibid_auction_id, name, vendor_id, start_date, end_date, status, total_lots, created_at, lot_prefix,
+                    COALESCE(created_by, 'system'), max_lots, auction_type";
+
+fn map_auction_row(row: &rusqlite::Row) -> Result<Auction> {
+    Ok(Auction {
+        id: row.get(0)?,
+        hibid_auction_id: row.get(1)?,
+        name: row.get(2)?,
+        vendor_id: row.get(3)?,
+        start_date: row.get(4)?,
+        end_date: row.get(5)?,
+        status: row.get(6)?,
+        total_lots: row.get(7)?,
+        created_at: row.get(8)?,
+        lot_prefix: row.get(9)?,
+        created_by: row.get(10)?,
+        max_lots: row.get(11)?,
+        auction_type: AuctionType::from_db(row.get::<_, Option<String>>(12)?.as_deref()),
+    })
+}
+
+/// Pre-export image check for one auction: Listed lots with no `{lot}-N.jpg` photo
+#[derive(Debug, Serialize)]
+pub struct ImageCheckSummary {
+    pub listed_count: usize,
+    pub with_images: usize,
+    pub missing_count: usize,
+    pub missing: Vec<MissingImageItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuctionItemOutcome {
+    #[serde(flatten)]
+    pub item: crate::db::InventoryItemRow,
+    pub high_bid: Option<f64>,
+    pub winning_bidder: Option<String>,
+    pub is_buyback: Option<bool>,
+}
+
 const REPORT_BONUS_RATE: f64 = 0.11;
 const REPORT_HISTORY_WINDOW: u32 = 8;
 
@@ -234,7 +346,7 @@ fn split_natural_tokens(value: &str) -> Vec<String> {
     tokens
 }
 
-fn natural_lot_cmp(a: &str, b: &str) -> Ordering {
+pub(crate) fn natural_lot_cmp(a: &str, b: &str) -> Ordering {
     let a_parts = split_natural_tokens(a);
     let b_parts = split_natural_tokens(b);
     let max_len = a_parts.len().max(b_parts.len());
@@ -285,15 +397,19 @@ impl AuctionManager {
         })?;
 
         db.conn.execute(
-            "INSERT INTO auctions (id, hibid_auction_id, name, vendor_id, start_date, end_date, status, total_lots)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'Active', 0)",
+            "INSERT INTO auctions (id, hibid_auction_id, name, vendor_id, start_date, end_date, status, total_lots, lot_prefix, created_by, max_lots, auction_type)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'Active', 0, NULLIF(TRIM(?7), ''), ?8, ?9, ?10)",
             rusqlite::params![
                 id,
                 req.hibid_auction_id,
                 normalized_name,
                 req.vendor_id,
                 req.start_date,
-                req.end_date
+                req.end_date,
+                req.lot_prefix,
+                db.current_user(),
+                req.max_lots.filter(|max| *max > 0),
+                req.auction_type.as_str()
             ],
         )?;
 
@@ -301,48 +417,103 @@ impl AuctionManager {
     }
 
     pub fn list_auctions(db: &Database) -> Result<Vec<Auction>> {
-        let mut stmt = db.conn.prepare(
-            "SELECT id, hibid_auction_id, name, vendor_id, start_date, end_date, status, total_lots, created_at
-             FROM auctions ORDER BY created_at DESC",
-        )?;
+        let mut stmt = db.conn.prepare(&format!(
+            "SELECT {} FROM auctions ORDER BY created_at DESC",
+            AUCTION_COLUMNS
+        ))?;
 
         let auctions = stmt
-            .query_map([], |row| {
-                Ok(Auction {
-                    id: row.get(0)?,
-                    hibid_auction_id: row.get(1)?,
-                    name: row.get(2)?,
-                    vendor_id: row.get(3)?,
-                    start_date: row.get(4)?,
-                    end_date: row.get(5)?,
-                    status: row.get(6)?,
-                    total_lots: row.get(7)?,
-                    created_at: row.get(8)?,
+            .query_map([], map_auction_row)?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(auctions)
+    }
+
+    /// Items of an auction in natural lot order, with their result (if any).
+    /// Result columns are read by name so they don't shift when inventory
+    /// columns are added.
+    pub fn auction_items(db: &Database, auction_id: &str) -> Result<Vec<AuctionItemOutcome>> {
+        let query = format!(
+            "SELECT i.*, ar.high_bid AS result_high_bid,
+                    ar.winning_bidder AS result_winning_bidder,
+                    ar.is_buyback AS result_is_buyback
+             FROM (SELECT {} FROM inventory_items WHERE auction_id = ?1) i
+             LEFT JOIN auction_results ar
+               ON ar.item_id = i.id
+              AND ar.auction_id = ?1",
+            crate::db::INVENTORY_ITEM_COLUMNS
+        );
+        let mut stmt = db.conn.prepare(&query)?;
+        let mut items = stmt
+            .query_map(rusqlite::params![auction_id], |row| {
+                Ok(AuctionItemOutcome {
+                    item: crate::db::map_inventory_item_row(row)?,
+                    high_bid: row.get("result_high_bid")?,
+                    winning_bidder: row.get("result_winning_bidder")?,
+                    is_buyback: row.get("result_is_buyback")?,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
 
-        Ok(auctions)
+        items.sort_by(|a, b| {
+            natural_lot_cmp(
+                a.item.lot_number.as_deref().unwrap_or(""),
+                b.item.lot_number.as_deref().unwrap_or(""),
+            )
+        });
+        Ok(items)
+    }
+
+    /// One page of auctions, newest first, filtered by status and by start date
+    /// (YYYY-MM-DD, inclusive). Blank filters are ignored; auctions without a
+    /// start date are left out once a date bound is given.
+    pub fn list_auctions_filtered(
+        db: &Database,
+        status: Option<&str>,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<AuctionPage> {
+        fn non_blank(value: Option<&str>) -> Option<&str> {
+            value.map(str::trim).filter(|v| !v.is_empty())
+        }
+        let (status, start_date, end_date) = (
+            non_blank(status),
+            non_blank(start_date),
+            non_blank(end_date),
+        );
+        let filter = "WHERE (?1 IS NULL OR status = ?1)
+               AND (?2 IS NULL OR datetime(start_date) >= datetime(?2))
+               AND (?3 IS NULL OR datetime(start_date) < datetime(?3, '+1 day'))";
+
+        let total_count: i64 = db.conn.query_row(
+            &format!("SELECT COUNT(*) FROM auctions {}", filter),
+            rusqlite::params![status, start_date, end_date],
+            |row| row.get(0),
+        )?;
+        let mut stmt = db.conn.prepare(&format!(
+            "SELECT {} FROM auctions {} ORDER BY created_at DESC LIMIT ?4 OFFSET ?5",
+            AUCTION_COLUMNS, filter
+        ))?;
+        let auctions = stmt
+            .query_map(
+                rusqlite::params![status, start_date, end_date, limit, offset],
+                map_auction_row,
+            )?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(AuctionPage {
+            auctions,
+            total_count,
+        })
     }
 
     pub fn get_auction_by_id(db: &Database, auction_id: &str) -> Result<Auction> {
         db.conn.query_row(
-            "SELECT id, hibid_auction_id, name, vendor_id, start_date, end_date, status, total_lots, created_at
-             FROM auctions WHERE id = ?1",
+            &format!("SELECT {} FROM auctions WHERE id = ?1", AUCTION_COLUMNS),
             rusqlite::params![auction_id],
-            |row| {
-                Ok(Auction {
-                    id: row.get(0)?,
-                    hibid_auction_id: row.get(1)?,
-                    name: row.get(2)?,
-                    vendor_id: row.get(3)?,
-                    start_date: row.get(4)?,
-                    end_date: row.get(5)?,
-                    status: row.get(6)?,
-                    total_lots: row.get(7)?,
-                    created_at: row.get(8)?,
-                })
-            }
+            map_auction_row,
         )
     }
 
@@ -359,6 +530,19 @@ impl AuctionManager {
             "UPDATE vendors SET cost_coefficient = ?1, min_price_margin = ?2 WHERE id = ?3",
             rusqlite::params![data.cost_coefficient, data.min_price_margin, vendor_id],
         )?;
+        if let Some(aliases) = &data.aliases {
+            let joined = crate::pricing::parse_vendor_aliases(Some(&aliases.join(","))).join(",");
+            db.conn.execute(
+                "UPDATE vendors SET aliases = NULLIF(?1, '') WHERE id = ?2",
+                rusqlite::params![joined, vendor_id],
+            )?;
+        }
+        if let Some(exempt) = data.commission_exempt {
+            db.conn.execute(
+                "UPDATE vendors SET commission_exempt = ?1 WHERE id = ?2",
+                rusqlite::params![exempt, vendor_id],
+            )?;
+        }
         Ok(())
     }
 
@@ -463,7 +647,8 @@ impl AuctionManager {
         // Build a map of lot_number -> CSV row for matching
         let mut csv_by_lot: HashMap<String, &csv_parser::HiBidResultRow> = HashMap::new();
         for row in &csv_results {
-            let lot = row.lot_number.trim().to_string();
+            let lot =
+                crate::hibid::strip_lot_prefix(auction.lot_prefix.as_deref(), &row.lot_number);
             if !lot.is_empty() {
                 csv_by_lot.insert(lot, row);
             }
@@ -545,8 +730,8 @@ impl AuctionManager {
                 .unwrap_or_default();
             let bidder_phone = row
                 .phone
-                .as_ref()
-                .map(|v| v.trim().to_string())
+                .as_deref()
+                .map(csv_parser::normalize_phone)
                 .unwrap_or_default();
 
             let is_floor = buyer_name.eq_ignore_ascii_case("floor");
@@ -587,6 +772,13 @@ impl AuctionManager {
             }
         };
 
+        let commission_terms = crate::reconciliation::CommissionTerms::load(db, auction_id);
+        let pricing_engine =
+            crate::pricing::PricingEngine::new(&db.conn).map_err(|e| e.to_string())?;
+        let created_by = db.current_user();
+        let buyers_premium_rate = crate::reconciliation::load_buyers_premium_rate(db);
+        let seasons = db.load_season_map();
+
         // First, clean up any existing auction_results for this auction
         db.conn
             .execute(
@@ -648,8 +840,10 @@ impl AuctionManager {
 
             // Insert auction result
             let result_id = Uuid::new_v4().to_string();
-            let commission = if new_status == "Sold" {
-                selling_price * 0.15
+            let commission_exempt =
+                new_status == "Sold" && pricing_engine.is_commission_exempt(&item.source);
+            let commission = if new_status == "Sold" && !commission_exempt {
+                commission_terms.amount(selling_price)
             } else {
                 0.0
             };
@@ -663,9 +857,10 @@ impl AuctionManager {
                     "INSERT INTO auction_results (
                     id, auction_id, item_id, winning_bidder, bidder_id, high_bid, max_bid,
                     bidder_email, bidder_phone, is_buyback, commission_rate, commission_amount,
-                    net_profit, item_status, min_price_snapshot
+                    net_profit, item_status, min_price_snapshot, bidder_phone_raw, commission_exempt,
+                    created_by, buyers_premium
                 )
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)",
                     rusqlite::params![
                         result_id,
                         auction_id,
@@ -677,14 +872,20 @@ impl AuctionManager {
                         bidder_email,
                         bidder_phone,
                         is_buyback,
-                        0.15_f64,
+                        commission_terms.rate,
                         commission,
                         net_profit,
                         new_status,
                         item.min_price,
+                        csv_row.and_then(|row| row.phone.as_deref()).map(str::trim),
+                        commission_exempt,
+                        created_by,
+                        round2(selling_price * buyers_premium_rate),
                     ],
                 )
                 .map_err(|e| e.to_string())?;
+            db.record_historical_sale(&result_id, &seasons)
+                .map_err(|e| e.to_string())?;
 
             let cost_coefficient = if item.retail_price > 0.0 {
                 round2(item.cost_price / item.retail_price).max(0.0)
@@ -728,7 +929,8 @@ impl AuctionManager {
         let mut unmatched_csv_rows = 0;
         let mut unmatched_difference_total = 0.0;
         for row in &csv_results {
-            let lot_number = row.lot_number.trim().to_string();
+            let lot_number =
+                crate::hibid::strip_lot_prefix(auction.lot_prefix.as_deref(), &row.lot_number);
             if lot_number.is_empty() || matched_csv_lots.contains(&lot_number) {
                 continue;
             }
@@ -1840,6 +2042,47 @@ pub fn get_auctions(state: State<crate::AppState>) -> std::result::Result<Vec<Au
     AuctionManager::list_auctions(&db).map_err(|e| e.to_string())
 }
 
+/// Filtered, paged auction list for the auctions screen. `limit` defaults to
+/// 50 (at most 500); `get_auctions` still returns everything.
+#[tauri::command]
+pub fn get_auctions_filtered(
+    status: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+    state: State<crate::AppState>,
+) -> std::result::Result<AuctionPage, String> {
+    for date in [&start_date, &end_date].into_iter().flatten() {
+        let date = date.trim();
+        if !date.is_empty() && chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+            return Err(format!("Invalid date '{}', expected YYYY-MM-DD", date));
+        }
+    }
+    let limit = limit.unwrap_or(DEFAULT_AUCTION_PAGE_SIZE);
+    if !(1..=MAX_AUCTION_PAGE_SIZE).contains(&limit) {
+        return Err(format!(
+            "limit must be between 1 and {}, got {}",
+            MAX_AUCTION_PAGE_SIZE, limit
+        ));
+    }
+    let offset = offset.unwrap_or(0);
+    if offset < 0 {
+        return Err(format!("offset must not be negative, got {}", offset));
+    }
+
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    AuctionManager::list_auctions_filtered(
+        &db,
+        status.as_deref(),
+        start_date.as_deref(),
+        end_date.as_deref(),
+        limit,
+        offset,
+    )
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_auction_by_id(
     auction_id: String,
@@ -1907,6 +2150,95 @@ pub fn unassign_item(
     Ok(())
 }
 
+#[tauri::command]
+pub fn reassign_item(
+    item_id: String,
+    target_auction_id: String,
+    state: State<crate::AppState>,
+) -> std::result::Result<(), String> {
+    let mut db = state.db.lock().map_err(|e| e.to_string())?;
+    move_listed_item(&mut db, &item_id, &target_auction_id)
+}
+
+/// Move a Listed item into another open auction, refusing when the target is
+/// already at its `max_lots`
+fn move_listed_item(
+    db: &mut Database,
+    item_id: &str,
+    target_auction_id: &str,
+) -> std::result::Result<(), String> {
+    let (target_status, max_lots): (String, Option<i64>) = db
+        .conn
+        .query_row(
+            "SELECT status, max_lots FROM auctions WHERE id = ?1",
+            rusqlite::params![target_auction_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| format!("Auction not found: {}", e))?;
+    if matches!(target_status.as_str(), "Completed" | "Cancelled") {
+        return Err(format!(
+            "Items cannot be moved into a {} auction",
+            target_status.to_lowercase()
+        ));
+    }
+
+    let (status, source_auction_id): (String, Option<String>) = db
+        .conn
+        .query_row(
+            "SELECT current_status, auction_id FROM inventory_items WHERE id = ?1",
+            rusqlite::params![item_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| format!("Item not found: {}", e))?;
+    if status != "Listed" {
+        return Err("Only listed items can be moved between auctions".to_string());
+    }
+    if source_auction_id.as_deref() == Some(target_auction_id) {
+        return Ok(());
+    }
+
+    // Move directly so the item stays Listed and keeps its listed_at
+    let tx = db.conn.transaction().map_err(|e| e.to_string())?;
+    if let Some(max) = max_lots {
+        // Counted the same way as auctions.total_lots
+        let lot_count: i64 = tx
+            .query_row(
+                "SELECT COUNT(*) FROM inventory_items WHERE auction_id = ?1",
+                rusqlite::params![target_auction_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        if lot_count >= max {
+            return Err(format!(
+                "Auction is full ({} of {} lots); raise max_lots or pick another auction",
+                lot_count, max
+            ));
+        }
+    }
+    tx.execute(
+        "UPDATE inventory_items SET auction_id = ?1 WHERE id = ?2",
+        rusqlite::params![target_auction_id, item_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    for auc_id in source_auction_id
+        .as_deref()
+        .into_iter()
+        .chain([target_auction_id])
+    {
+        tx.execute(
+            "UPDATE auctions SET total_lots = (
+                SELECT COUNT(*) FROM inventory_items WHERE auction_id = ?1
+             ) WHERE id = ?1",
+            rusqlite::params![auc_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
 #[tauri::command]
 pub fn finish_auction(
     auction_id: String,
@@ -1945,6 +2277,73 @@ pub fn get_all_auction_reports(
     AuctionManager::get_all_auction_reports(&db)
 }
 
+/// Set or clear (blank) the lot number prefix used in this auction's HiBid exports
+#[tauri::command]
+pub fn set_auction_lot_prefix(
+    auction_id: String,
+    lot_prefix: Option<String>,
+    state: State<crate::AppState>,
+) -> std::result::Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let updated = db
+        .conn
+        .execute(
+            "UPDATE auctions SET lot_prefix = NULLIF(TRIM(?1), '') WHERE id = ?2",
+            rusqlite::params![lot_prefix, auction_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err(format!("Auction {} not found", auction_id));
+    }
+    Ok(())
+}
+
+/// Set or clear (`None` / 0) the most lots an auction may hold
+#[tauri::command]
+pub fn set_auction_max_lots(
+    auction_id: String,
+    max_lots: Option<i64>,
+    state: State<crate::AppState>,
+) -> std::result::Result<(), String> {
+    if max_lots.is_some_and(|max| max < 0) {
+        return Err("max_lots cannot be negative".to_string());
+    }
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let updated = db
+        .conn
+        .execute(
+            "UPDATE auctions SET max_lots = NULLIF(?1, 0) WHERE id = ?2",
+            rusqlite::params![max_lots, auction_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err(format!("Auction {} not found", auction_id));
+    }
+    Ok(())
+}
+
+/// Switch an auction between Standard and Cash commission; applies to results
+/// reconciled afterwards
+#[tauri::command]
+pub fn set_auction_type(
+    auction_id: String,
+    auction_type: AuctionType,
+    state: State<crate::AppState>,
+) -> std::result::Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let updated = db
+        .conn
+        .execute(
+            "UPDATE auctions SET auction_type = ?1 WHERE id = ?2",
+            rusqlite::params![auction_type.as_str(), auction_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err(format!("Auction {} not found", auction_id));
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub fn rename_auction(
     auction_id: String,
@@ -1991,13 +2390,24 @@ pub fn get_auction_result_bids(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn get_auction_items(
+    auction_id: String,
+    state: State<crate::AppState>,
+) -> std::result::Result<Vec<AuctionItemOutcome>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    AuctionManager::auction_items(&db, &auction_id).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_relistable_inventory_items(
     auction_id: String,
     state: State<crate::AppState>,
 ) -> std::result::Result<Vec<crate::db::InventoryItemRow>, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    let items = db.get_inventory_items(None).map_err(|e| e.to_string())?;
+    let items = db
+        .get_inventory_items(None, None)
+        .map_err(|e| e.to_string())?;
     let filtered = items
         .into_iter()
         .filter(|item| {
@@ -2010,36 +2420,128 @@ pub fn get_relistable_inventory_items(
     Ok(filtered)
 }
 
+/// Item refused by `assign_items_to_auction` because its source maps to another vendor
+#[derive(Debug, Serialize)]
+pub struct VendorMismatch {
+    pub item_id: String,
+    pub lot_number: Option<String>,
+    pub source: Option<String>,
+    pub matched_vendor: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AssignItemsResult {
+    pub assigned_count: i32,
+    pub vendor_mismatches: Vec<VendorMismatch>,
+    /// Eligible items left out because the auction reached `max_lots`
+    pub capacity_rejected: i32,
+}
+
 #[tauri::command]
 pub fn assign_items_to_auction(
     auction_id: String,
     item_ids: Vec<String>,
+    strict_vendor: Option<bool>,
     state: State<crate::AppState>,
-) -> std::result::Result<i32, String> {
+) -> std::result::Result<AssignItemsResult, String> {
+    let mut db = state.db.lock().map_err(|e| e.to_string())?;
+    assign_items(
+        &mut db,
+        &auction_id,
+        item_ids,
+        strict_vendor.unwrap_or(false),
+    )
+}
+
+fn assign_items(
+    db: &mut Database,
+    auction_id: &str,
+    item_ids: Vec<String>,
+    strict_vendor: bool,
+) -> std::result::Result<AssignItemsResult, String> {
+    let mut result = AssignItemsResult {
+        assigned_count: 0,
+        vendor_mismatches: Vec::new(),
+        capacity_rejected: 0,
+    };
     if item_ids.is_empty() {
-        return Ok(0);
+        return Ok(result);
     }
 
-    let mut db = state.db.lock().map_err(|e| e.to_string())?;
-    let auction_status: String = db
+    let (auction_status, auction_vendor_id, max_lots): (String, Option<String>, Option<i64>) = db
         .conn
         .query_row(
-            "SELECT status FROM auctions WHERE id = ?1",
+            "SELECT status, vendor_id, max_lots FROM auctions WHERE id = ?1",
             rusqlite::params![&auction_id],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
         )
         .map_err(|e| format!("Auction not found: {}", e))?;
     if auction_status != "Active" {
         return Err("Items can only be added to an active auction".to_string());
     }
 
+    // Strict mode only applies when the auction is tied to a vendor
+    let pricing_engine = match (strict_vendor, &auction_vendor_id) {
+        (true, Some(_)) => {
+            Some(crate::pricing::PricingEngine::new(&db.conn).map_err(|e| e.to_string())?)
+        }
+        _ => None,
+    };
+
     let tx = db.conn.transaction().map_err(|e| e.to_string())?;
-    let mut affected_count: i32 = 0;
+    // Counted the same way as auctions.total_lots
+    let mut lot_count: i64 = tx
+        .query_row(
+            "SELECT COUNT(*) FROM inventory_items WHERE auction_id = ?1",
+            rusqlite::params![&auction_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
     let mut touched_ids: HashSet<String> = HashSet::new();
     for item_id in item_ids {
         if !touched_ids.insert(item_id.clone()) {
             continue;
         }
+        if let (Some(engine), Some(vendor_id)) = (&pricing_engine, &auction_vendor_id) {
+            let item: Option<(Option<String>, Option<String>)> = tx
+                .query_row(
+                    "SELECT lot_number, source FROM inventory_items WHERE id = ?1",
+                    rusqlite::params![&item_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?;
+            let Some((lot_number, source)) = item else {
+                continue;
+            };
+            let matched = engine.match_vendor(source.as_deref().unwrap_or(""));
+            if matched.map(|v| &v.id) != Some(vendor_id) {
+                result.vendor_mismatches.push(VendorMismatch {
+                    item_id,
+                    lot_number,
+                    source,
+                    matched_vendor: matched.map(|v| v.name.clone()),
+                });
+                continue;
+            }
+        }
+        if max_lots.is_some_and(|max| lot_count >= max) {
+            let eligible: bool = tx
+                .query_row(
+                    "SELECT EXISTS(
+                         SELECT 1 FROM inventory_items
+                         WHERE id = ?1
+                           AND current_status IN ('InStock', 'Unsold', 'Buyback', 'FloorSale')
+                     )",
+                    rusqlite::params![&item_id],
+                    |row| row.get(0),
+                )
+                .map_err(|e| e.to_string())?;
+            if eligible {
+                result.capacity_rejected += 1;
+            }
+            continue;
+        }
         let affected = tx
             .execute(
                 "UPDATE inventory_items
@@ -2054,7 +2556,8 @@ pub fn assign_items_to_auction(
                 rusqlite::params![&auction_id, &item_id],
             )
             .map_err(|e| e.to_string())?;
-        affected_count += affected as i32;
+        result.assigned_count += affected as i32;
+        lot_count += affected as i64;
     }
 
     tx.execute(
@@ -2070,7 +2573,7 @@ pub fn assign_items_to_auction(
     .map_err(|e| e.to_string())?;
     tx.commit().map_err(|e| e.to_string())?;
 
-    Ok(affected_count)
+    Ok(result)
 }
 
 #[tauri::command]
@@ -2289,6 +2792,81 @@ pub fn delete_auction(
     Ok(())
 }
 
+#[tauri::command]
+pub fn get_items_missing_images(
+    auction_id: String,
+    image_dir: String,
+    state: State<crate::AppState>,
+) -> std::result::Result<Vec<MissingImageItem>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let (_, missing) = listed_items_missing_images(&db, &auction_id, Path::new(&image_dir))?;
+    Ok(missing)
+}
+
+/// Read-only checklist step before a HiBid export: counts Listed lots that would be
+/// exported without photos, using the same image lookup as the export itself.
+#[tauri::command]
+pub fn check_auction_images(
+    auction_id: String,
+    image_dir: String,
+    state: State<crate::AppState>,
+) -> std::result::Result<ImageCheckSummary, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let (listed_count, missing) =
+        listed_items_missing_images(&db, &auction_id, Path::new(&image_dir))?;
+
+    Ok(ImageCheckSummary {
+        listed_count,
+        with_images: listed_count - missing.len(),
+        missing_count: missing.len(),
+        missing,
+    })
+}
+
+/// Listed lots of an auction with no photo `collect_lot_images` would export,
+/// sorted by lot, together with the number of Listed lots checked
+fn listed_items_missing_images(
+    db: &Database,
+    auction_id: &str,
+    image_dir: &Path,
+) -> std::result::Result<(usize, Vec<MissingImageItem>), String> {
+    // collect_lot_images treats an unreadable directory as "no photos"; fail loudly here instead
+    std::fs::read_dir(image_dir).map_err(|e| format!("Failed to read image directory: {}", e))?;
+
+    let mut stmt = db
+        .conn
+        .prepare(
+            "SELECT id, COALESCE(lot_number, ''), raw_title
+             FROM inventory_items
+             WHERE auction_id = ?1
+               AND current_status = 'Listed'",
+        )
+        .map_err(|e| e.to_string())?;
+    let items = stmt
+        .query_map(rusqlite::params![auction_id], |row| {
+            Ok(MissingImageItem {
+                item_id: row.get(0)?,
+                lot_number: row.get(1)?,
+                title: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+
+    let listed_count = items.len();
+    let mut missing: Vec<MissingImageItem> = items
+        .into_iter()
+        .filter(|item| {
+            item.lot_number.trim().is_empty()
+                || crate::hibid::collect_lot_images(image_dir, &item.lot_number).is_empty()
+        })
+        .collect();
+    missing.sort_by(|a, b| natural_lot_cmp(&a.lot_number, &b.lot_number));
+
+    Ok((listed_count, missing))
+}
+
 #[tauri::command]
 pub fn open_report_file(file_path: String) -> std::result::Result<(), String> {
     // Open file with default system application
@@ -3146,4 +3724,193 @@ mod tests {
         assert_eq!(report_rows[0].0, "detail");
         assert_eq!(report_rows[1].0, "summary");
     }
+
+    /// Fresh database in its own temp dir; remove the dir when done
+    fn test_db(label: &str) -> (PathBuf, Database) {
+        let base_dir: PathBuf =
+            std::env::temp_dir().join(format!("sugarland_{}_{}", label, Uuid::new_v4()));
+        fs::create_dir_all(&base_dir).expect("Failed to create temp dir");
+        let db =
+            Database::new(path_str(&base_dir.join("test.db"))).expect("Failed to create test db");
+        db.conn
+            .execute(
+                "INSERT INTO manifests (id, source_filename) VALUES ('m-test', 'manifest.csv')",
+                [],
+            )
+            .expect("Failed to insert manifest");
+        (base_dir, db)
+    }
+
+    fn insert_item(db: &Database, item_id: &str, lot_number: &str, auction_id: Option<&str>) {
+        db.conn
+            .execute(
+                "INSERT INTO inventory_items (
+                    id, manifest_id, lot_number, raw_title, source, retail_price, cost_price,
+                    min_price, current_status, auction_id, notes, gross_margin_pct, is_flagged
+                 ) VALUES (?1, 'm-test', ?2, 'Test Item', 'Best Buy', 100.0, 14.0, 24.0,
+                           CASE WHEN ?3 IS NULL THEN 'InStock' ELSE 'Listed' END, ?3,
+                           'missing remote', 41.67, 1)",
+                params![item_id, lot_number, auction_id],
+            )
+            .expect("Failed to insert item");
+    }
+
+    #[test]
+    fn auction_items_read_result_columns_by_name() {
+        let (base_dir, db) = test_db("auction_items");
+        db.conn
+            .execute(
+                "INSERT INTO auctions (id, name, status) VALUES ('a1', 'Sugarland 1', 'Active')",
+                [],
+            )
+            .expect("Failed to insert auction");
+        insert_item(&db, "item-2", "2", Some("a1"));
+        insert_item(&db, "item-10", "10", Some("a1"));
+        db.conn
+            .execute(
+                "INSERT INTO auction_results (id, auction_id, item_id, winning_bidder, high_bid, is_buyback)
+                 VALUES ('r1', 'a1', 'item-10', 'Buyer One', 55.5, 0)",
+                [],
+            )
+            .expect("Failed to insert result");
+
+        let items = AuctionManager::auction_items(&db, "a1").expect("auction items");
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].item.id, "item-2");
+        assert_eq!(items[0].high_bid, None);
+        let sold = &items[1];
+        assert_eq!(sold.item.notes.as_deref(), Some("missing remote"));
+        assert_eq!(sold.high_bid, Some(55.5));
+        assert_eq!(sold.winning_bidder.as_deref(), Some("Buyer One"));
+        assert_eq!(sold.is_buyback, Some(false));
+        let _ = fs::remove_dir_all(&base_dir);
+    }
+
+    #[test]
+    fn assign_items_stops_at_max_lots() {
+        let (base_dir, mut db) = test_db("assign_capacity");
+        db.conn
+            .execute(
+                "INSERT INTO auctions (id, name, status, max_lots) VALUES ('a1', 'Sugarland 1', 'Active', 2)",
+                [],
+            )
+            .expect("Failed to insert auction");
+        insert_item(&db, "item-1", "1", Some("a1"));
+        insert_item(&db, "item-2", "2", None);
+        insert_item(&db, "item-3", "3", None);
+
+        let result = assign_items(
+            &mut db,
+            "a1",
+            vec!["item-2".to_string(), "item-3".to_string()],
+            false,
+        )
+        .expect("assign");
+        assert_eq!(result.assigned_count, 1);
+        assert_eq!(result.capacity_rejected, 1);
+        let total_lots: i64 = db
+            .conn
+            .query_row(
+                "SELECT total_lots FROM auctions WHERE id = 'a1'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("total lots");
+        assert_eq!(total_lots, 2);
+        let _ = fs::remove_dir_all(&base_dir);
+    }
+
+    #[test]
+    fn reassign_item_respects_target_max_lots() {
+        let (base_dir, mut db) = test_db("reassign_capacity");
+        db.conn
+            .execute_batch(
+                "INSERT INTO auctions (id, name, status) VALUES ('a1', 'Sugarland 1', 'Active');
+                 INSERT INTO auctions (id, name, status, max_lots) VALUES ('a2', 'Sugarland 2', 'Active', 1);",
+            )
+            .expect("Failed to insert auctions");
+        insert_item(&db, "item-1", "1", Some("a1"));
+        insert_item(&db, "item-2", "2", Some("a1"));
+
+        move_listed_item(&mut db, "item-1", "a2").expect("first move fits");
+        let err = move_listed_item(&mut db, "item-2", "a2").unwrap_err();
+        assert!(err.contains("Auction is full"), "{}", err);
+        let auction_id: String = db
+            .conn
+            .query_row(
+                "SELECT auction_id FROM inventory_items WHERE id = 'item-2'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("item auction");
+        assert_eq!(auction_id, "a1");
+        let _ = fs::remove_dir_all(&base_dir);
+    }
+
+    #[test]
+    fn missing_images_use_the_export_photo_lookup() {
+        let (base_dir, db) = test_db("missing_images");
+        db.conn
+            .execute(
+                "INSERT INTO auctions (id, name, status) VALUES ('a1', 'Sugarland 1', 'Active')",
+                [],
+            )
+            .expect("Failed to insert auction");
+        insert_item(&db, "item-7", "7", Some("a1"));
+        insert_item(&db, "item-42", "42", Some("a1"));
+        let image_dir = base_dir.join("images");
+        fs::create_dir_all(&image_dir).expect("Failed to create image dir");
+        fs::write(image_dir.join("7-1.JPG"), b"jpg").expect("Failed to write image");
+        // Not a numbered photo slot, so the export would skip it
+        fs::write(image_dir.join("42-x.jpg"), b"jpg").expect("Failed to write image");
+
+        let (listed_count, missing) =
+            listed_items_missing_images(&db, "a1", &image_dir).expect("missing images");
+        assert_eq!(listed_count, 2);
+        let lots: Vec<_> = missing.iter().map(|m| m.lot_number.as_str()).collect();
+        assert_eq!(lots, ["42"]);
+        let _ = fs::remove_dir_all(&base_dir);
+    }
+
+    #[test]
+    fn list_auctions_filtered_pages_by_status_and_start_date() {
+        let (base_dir, db) = test_db("auction_filter");
+
+        for (id, status, start_date) in [
+            ("a1", "Active", Some("2026-03-01 10:00:00")),
+            ("a2", "Completed", Some("2026-03-15")),
+            ("a3", "Completed", Some("2026-04-02")),
+            ("a4", "Completed", None),
+        ] {
+            db.conn
+                .execute(
+                    "INSERT INTO auctions (id, name, status, start_date) VALUES (?1, ?1, ?2, ?3)",
+                    params![id, status, start_date],
+                )
+                .expect("Failed to insert auction");
+        }
+
+        let page = AuctionManager::list_auctions_filtered(&db, Some("Completed"), None, None, 2, 0)
+            .expect("page");
+        assert_eq!(page.total_count, 3);
+        assert_eq!(page.auctions.len(), 2);
+        let rest = AuctionManager::list_auctions_filtered(&db, Some("Completed"), None, None, 2, 2)
+            .expect("page");
+        assert_eq!(rest.auctions.len(), 1);
+
+        let march = AuctionManager::list_auctions_filtered(
+            &db,
+            Some(" "),
+            Some("2026-03-01"),
+            Some("2026-03-31"),
+            50,
+            0,
+        )
+        .expect("page");
+        assert_eq!(march.total_count, 2);
+        let mut ids: Vec<_> = march.auctions.iter().map(|a| a.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["a1", "a2"]);
+        let _ = fs::remove_dir_all(&base_dir);
+    }
 }
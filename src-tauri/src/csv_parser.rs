@@ -1,8 +1,12 @@
 // CSV Parser module — Manyfastscan manifest parsing and data cleaning
 
+use crate::error::AppError;
+use lazy_static::lazy_static;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fs::File;
+use std::io::Read;
 
 #[derive(Debug, Deserialize)]
 pub struct BStockManifestRow {
@@ -57,6 +61,19 @@ pub fn parse_bstock_csv(file_path: &str) -> Result<Vec<BStockManifestRow>, Box<d
     Ok(rows)
 }
 
+/// Settings key for the shortest title (in characters) an imported row may have
+pub const MIN_TITLE_LENGTH_SETTING: &str = "min_title_length";
+
+/// `min_title_length` when the setting is missing or invalid
+pub const DEFAULT_MIN_TITLE_LENGTH: usize = 3;
+
+/// Whether a manifest title is too short or has no letters at all ("", "X",
+/// "12345") and needs a human to write a real one before listing
+pub fn is_garbage_title(title: &str, min_length: usize) -> bool {
+    let title = title.trim();
+    title.chars().count() < min_length || !title.chars().any(char::is_alphabetic)
+}
+
 /// Clean a price string by removing $, commas, and whitespace
 pub fn clean_price(price_str: &str) -> f64 {
     price_str
@@ -72,6 +89,36 @@ pub fn clean_hibid_cents_price(price_str: &str) -> f64 {
     clean_price(price_str) / 100.0
 }
 
+/// Canonical US phone format "(503) 555-1234", with any extension kept as
+/// " x123". A leading country code 1 is dropped. Values that aren't a
+/// 10-digit US number are returned trimmed but otherwise unchanged.
+pub fn normalize_phone(raw: &str) -> String {
+    let trimmed = raw.trim();
+    let lower = trimmed.to_lowercase();
+    let (number, extension) = match lower.find("ext").or_else(|| lower.find(['x', '#'])) {
+        Some(pos) => (
+            &lower[..pos],
+            lower[pos..].trim_start_matches(|c: char| !c.is_ascii_digit()),
+        ),
+        None => (lower.as_str(), ""),
+    };
+
+    let mut digits: String = number.chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.len() == 11 && digits.starts_with('1') {
+        digits.remove(0);
+    }
+    if digits.len() != 10 || number.chars().any(|c| c.is_alphabetic()) {
+        return trimmed.to_string();
+    }
+
+    let formatted = format!("({}) {}-{}", &digits[..3], &digits[3..6], &digits[6..]);
+    if extension.is_empty() {
+        formatted
+    } else {
+        format!("{} x{}", formatted, extension)
+    }
+}
+
 /// Normalize a source string to a canonical vendor name
 pub fn normalize_source(source: &Option<String>) -> String {
     match source {
@@ -183,7 +230,8 @@ pub struct HiBidResultRow {
     #[serde(rename = "Title", default)]
     pub title: Option<String>,
 
-    #[serde(rename = "Winning Bidder", default)]
+    /// Bidder number; holds "Name (id)" in the combined layout until split
+    #[serde(rename = "Winning Bidder", alias = "Bidder", default)]
     pub bidder_id: String,
 
     #[serde(rename = "Name", default)]
@@ -200,9 +248,30 @@ pub struct HiBidResultRow {
 
     #[serde(rename = "Phone", default)]
     pub phone: Option<String>,
+
+    /// Auction the row belongs to, only present in combined multi-auction files
+    #[serde(rename = "Auction", default)]
+    pub auction: Option<String>,
+}
+
+lazy_static! {
+    // "Smith, John (5046)" — bidder name followed by a parenthesized bidder id
+    static ref COMBINED_BIDDER: Regex = Regex::new(r"^(.*?)\s*\(\s*([^()]+?)\s*\)\s*$").unwrap();
+}
+
+/// Split a combined "Name (id)" bidder value into (name, id).
+/// Returns `None` when the value has no trailing parenthesized id.
+pub fn split_combined_bidder(value: &str) -> Option<(String, String)> {
+    COMBINED_BIDDER
+        .captures(value.trim())
+        .map(|cap| (cap[1].trim().to_string(), cap[2].to_string()))
 }
 
 /// Parse a HiBid auction results CSV
+///
+/// Supports both the two-column layout (`Winning Bidder` id + `Name`) and the
+/// combined layout where a single bidder column holds "Name (id)" and there is
+/// no `Name` column.
 pub fn parse_hibid_results(file_path: &str) -> Result<Vec<HiBidResultRow>, Box<dyn Error>> {
     let file = File::open(file_path)?;
     let mut reader = csv::ReaderBuilder::new()
@@ -211,7 +280,9 @@ pub fn parse_hibid_results(file_path: &str) -> Result<Vec<HiBidResultRow>, Box<d
         .trim(csv::Trim::All)
         .from_reader(file);
 
-    let mut rows = Vec::new();
+    let combined_bidder_layout = !reader.headers()?.iter().any(|h| h == "Name");
+
+    let mut rows: Vec<HiBidResultRow> = Vec::new();
     for result in reader.deserialize() {
         match result {
             Ok(row) => rows.push(row),
@@ -222,9 +293,93 @@ pub fn parse_hibid_results(file_path: &str) -> Result<Vec<HiBidResultRow>, Box<d
         }
     }
 
+    if combined_bidder_layout {
+        for row in rows.iter_mut() {
+            if let Some((name, id)) = split_combined_bidder(&row.bidder_id) {
+                row.winning_bidder = name;
+                row.bidder_id = id;
+            }
+        }
+    }
+
     Ok(rows)
 }
 
+/// Result rows grouped by HiBid auction name, in first-seen order
+pub type AuctionResultGroups = Vec<(String, Vec<HiBidResultRow>)>;
+
+/// Parse a combined HiBid results CSV covering several auctions and group its
+/// rows by the `Auction` column, in first-seen order. Rows with a blank auction
+/// are grouped under an empty key. Fails if the file has no `Auction` column.
+pub fn parse_hibid_results_by_auction(
+    file_path: &str,
+) -> Result<AuctionResultGroups, Box<dyn Error>> {
+    let file = File::open(file_path)?;
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(file);
+    if !reader.headers()?.iter().any(|h| h == "Auction") {
+        return Err("Results file has no Auction column; use single-auction reconciliation".into());
+    }
+
+    let mut groups: AuctionResultGroups = Vec::new();
+    for row in parse_hibid_results(file_path)? {
+        let key = row.auction.as_deref().unwrap_or("").trim().to_string();
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, rows)) => rows.push(row),
+            None => groups.push((key, vec![row])),
+        }
+    }
+    Ok(groups)
+}
+
+/// One row of a vendor import CSV. Numeric columns are kept as text so bad
+/// values can be reported per row instead of failing the whole file.
+#[derive(Debug, Deserialize)]
+pub struct VendorCsvRow {
+    #[serde(default)]
+    pub id: String,
+
+    #[serde(default)]
+    pub name: String,
+
+    #[serde(default)]
+    pub cost_coefficient: String,
+
+    #[serde(default)]
+    pub min_price_margin: String,
+
+    #[serde(default)]
+    pub active: Option<String>,
+}
+
+/// Parse a vendor CSV (id, name, cost_coefficient, min_price_margin, active).
+/// Each entry is the parsed row or the reason it could not be read, in file order.
+pub fn parse_vendor_csv(
+    file_path: &str,
+) -> Result<Vec<Result<VendorCsvRow, String>>, Box<dyn Error>> {
+    let file = File::open(file_path)?;
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(file);
+
+    let headers = reader.headers()?.clone();
+    if !headers.iter().any(|h| h.eq_ignore_ascii_case("name")) {
+        return Err("Vendor file has no name column".into());
+    }
+    let lowered: csv::StringRecord = headers.iter().map(|h| h.to_ascii_lowercase()).collect();
+    reader.set_headers(lowered);
+
+    Ok(reader
+        .deserialize()
+        .map(|row| row.map_err(|e: csv::Error| e.to_string()))
+        .collect())
+}
+
 // ============================================================
 // CSV Validation
 // ============================================================
@@ -244,7 +399,14 @@ pub fn validate_bstock_csv(path: &str) -> Result<ValidationResult, String> {
         .from_reader(file);
 
     // 1. Check required headers
-    let headers = rdr.headers().map_err(|e| e.to_string())?;
+    let headers = rdr.headers().map_err(|e| e.to_string())?.clone();
+    if headers.iter().all(|h| h.trim().is_empty()) {
+        return Ok(ValidationResult {
+            valid: false,
+            message: "No data rows: the file is empty".to_string(),
+            warnings: vec![],
+        });
+    }
     let required = vec!["LotNumber", "Title", "Retail Price"];
     let mut missing = Vec::new();
 
@@ -290,6 +452,14 @@ pub fn validate_bstock_csv(path: &str) -> Result<ValidationResult, String> {
         }
     }
 
+    if row_count == 0 {
+        return Ok(ValidationResult {
+            valid: false,
+            message: "No data rows: the file only contains a header row".to_string(),
+            warnings,
+        });
+    }
+
     Ok(ValidationResult {
         valid: true,
         message: format!("CSV is valid. Checked {} rows.", row_count),
@@ -303,6 +473,292 @@ pub fn validate_csv(file_path: String) -> Result<ValidationResult, String> {
     validate_bstock_csv(&file_path)
 }
 
+/// Whether a HiBid money field is blank or a number (after removing "$" and ",")
+fn is_parseable_bid(value: &str) -> bool {
+    let cleaned = value.replace(['$', ','], "");
+    let cleaned = cleaned.trim();
+    cleaned.is_empty() || cleaned.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+/// Validate a HiBid results CSV before reconciliation: required headers, blank
+/// lot numbers and unparseable bids. Every row is checked.
+pub fn validate_hibid_results_file(path: &str) -> Result<ValidationResult, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(file);
+
+    // 1. Check required headers (the same names parse_hibid_results reads)
+    let headers = rdr.headers().map_err(|e| e.to_string())?.clone();
+    if headers.iter().all(|h| h.trim().is_empty()) {
+        return Ok(ValidationResult {
+            valid: false,
+            message: "No data rows: the file is empty".to_string(),
+            warnings: vec![],
+        });
+    }
+    let position = |names: &[&str]| headers.iter().position(|h| names.contains(&h));
+    let lot_col = position(&["Lot"]);
+    let bidder_col = position(&["Winning Bidder", "Bidder"]);
+    let high_bid_col = position(&["High Bid"]);
+    let max_bid_col = position(&["Max Bid"]);
+
+    let missing: Vec<&str> = [
+        (lot_col, "Lot"),
+        (bidder_col, "Winning Bidder"),
+        (high_bid_col, "High Bid"),
+    ]
+    .iter()
+    .filter(|(col, _)| col.is_none())
+    .map(|(_, name)| *name)
+    .collect();
+    if !missing.is_empty() {
+        return Ok(ValidationResult {
+            valid: false,
+            message: format!("Missing required columns: {}", missing.join(", ")),
+            warnings: vec![],
+        });
+    }
+
+    // 2. Check every row
+    let mut warnings = Vec::new();
+    let mut row_count = 0;
+    for (i, result) in rdr.records().enumerate() {
+        let row = i + 2;
+        let record = match result {
+            Ok(record) => record,
+            Err(e) => {
+                return Ok(ValidationResult {
+                    valid: false,
+                    message: format!("Invalid data at row {}: {}", row, e),
+                    warnings,
+                });
+            }
+        };
+        row_count += 1;
+
+        let field = |col: Option<usize>| col.and_then(|c| record.get(c)).unwrap_or("");
+        if field(lot_col).is_empty() {
+            warnings.push(format!("Row {}: Blank lot number", row));
+        }
+        for (col, name) in [(high_bid_col, "High Bid"), (max_bid_col, "Max Bid")] {
+            let value = field(col);
+            if !is_parseable_bid(value) {
+                warnings.push(format!("Row {}: Unparseable {} {:?}", row, name, value));
+            }
+        }
+    }
+
+    if row_count == 0 {
+        return Ok(ValidationResult {
+            valid: false,
+            message: "No data rows: the file only contains a header row".to_string(),
+            warnings,
+        });
+    }
+
+    Ok(ValidationResult {
+        valid: true,
+        message: format!(
+            "Results file is valid. Checked {} rows ({} warnings).",
+            row_count,
+            warnings.len()
+        ),
+        warnings,
+    })
+}
+
+#[tauri::command]
+pub fn validate_hibid_results(file_path: String) -> Result<ValidationResult, String> {
+    log::info!("Validating HiBid results: {}", file_path);
+    validate_hibid_results_file(&file_path)
+}
+
+// ============================================================
+// Pre-import probe
+// ============================================================
+
+/// Bytes read from the start of a file by `probe_csv`
+const PROBE_SAMPLE_BYTES: u64 = 64 * 1024;
+
+/// Delimiters `detect_delimiter` chooses between, with their display names
+const DELIMITER_CANDIDATES: [(u8, &str); 4] =
+    [(b',', "comma"), (b'\t', "tab"), (b';', "semicolon"), (b'|', "pipe")];
+
+/// Text encoding of a CSV file as detected from its first bytes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum CsvEncoding {
+    #[serde(rename = "UTF-8")]
+    Utf8,
+    #[serde(rename = "UTF-8 (BOM)")]
+    Utf8Bom,
+    #[serde(rename = "UTF-16LE")]
+    Utf16Le,
+    #[serde(rename = "UTF-16BE")]
+    Utf16Be,
+    /// Anything that isn't valid UTF-8 is read as Latin-1 (Windows-1252 exports)
+    #[serde(rename = "Latin-1")]
+    Latin1,
+}
+
+/// What `probe_csv` found in a file, for the pre-import confirmation dialog
+#[derive(Debug, Serialize)]
+pub struct CsvProbe {
+    pub delimiter: String,
+    /// "comma", "tab", "semicolon" or "pipe"
+    pub delimiter_name: String,
+    pub encoding: CsvEncoding,
+    pub headers: Vec<String>,
+    /// Data rows, excluding the header. Extrapolated from the sample when
+    /// `row_count_exact` is false.
+    pub row_count: usize,
+    pub row_count_exact: bool,
+    pub file_size: u64,
+}
+
+/// Detect the encoding of a sample from its byte order mark, falling back to
+/// Latin-1 when the bytes aren't valid UTF-8. A multi-byte character cut off at
+/// the end of the sample doesn't count against UTF-8.
+pub fn detect_encoding(sample: &[u8]) -> CsvEncoding {
+    if sample.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return CsvEncoding::Utf8Bom;
+    }
+    if sample.starts_with(&[0xFF, 0xFE]) {
+        return CsvEncoding::Utf16Le;
+    }
+    if sample.starts_with(&[0xFE, 0xFF]) {
+        return CsvEncoding::Utf16Be;
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => CsvEncoding::Utf8,
+        Err(e) if e.error_len().is_none() => CsvEncoding::Utf8,
+        Err(_) => CsvEncoding::Latin1,
+    }
+}
+
+/// Decode a sample as `encoding`, dropping any byte order mark
+fn decode_sample(sample: &[u8], encoding: CsvEncoding) -> String {
+    match encoding {
+        CsvEncoding::Utf8 => String::from_utf8_lossy(sample).into_owned(),
+        CsvEncoding::Utf8Bom => String::from_utf8_lossy(&sample[3..]).into_owned(),
+        CsvEncoding::Utf16Le | CsvEncoding::Utf16Be => {
+            let units = sample[2..].chunks_exact(2).map(|pair| match encoding {
+                CsvEncoding::Utf16Le => u16::from_le_bytes([pair[0], pair[1]]),
+                _ => u16::from_be_bytes([pair[0], pair[1]]),
+            });
+            char::decode_utf16(units)
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect()
+        }
+        CsvEncoding::Latin1 => sample.iter().map(|&b| b as char).collect(),
+    }
+}
+
+/// Count `delimiter` in a line, ignoring anything inside double quotes
+fn count_unquoted(line: &str, delimiter: u8) -> usize {
+    let mut in_quotes = false;
+    line.bytes()
+        .filter(|&b| {
+            if b == b'"' {
+                in_quotes = !in_quotes;
+            }
+            b == delimiter && !in_quotes
+        })
+        .count()
+}
+
+/// Pick the delimiter that appears in the header line and splits the most of
+/// the following lines into the same number of fields; ties go to the one
+/// producing more columns. Defaults to a comma.
+pub fn detect_delimiter(text: &str) -> u8 {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(10)
+        .collect();
+    let Some(header) = lines.first() else {
+        return b',';
+    };
+
+    DELIMITER_CANDIDATES
+        .iter()
+        .map(|&(delimiter, _)| {
+            let header_count = count_unquoted(header, delimiter);
+            let consistent = lines
+                .iter()
+                .filter(|line| count_unquoted(line, delimiter) == header_count)
+                .count();
+            (delimiter, header_count, consistent)
+        })
+        .filter(|&(_, header_count, _)| header_count > 0)
+        .max_by_key(|&(_, header_count, consistent)| (consistent, header_count))
+        .map(|(delimiter, _, _)| delimiter)
+        .unwrap_or(b',')
+}
+
+/// Detect the delimiter and encoding of a CSV file and read its headers and an
+/// estimated row count, without importing anything. Only the first 64 KB are
+/// read; larger files get a row count extrapolated from the file size.
+pub fn probe_csv_file(path: &str) -> Result<CsvProbe, AppError> {
+    let file = File::open(path)?;
+    let file_size = file.metadata()?.len();
+    let mut sample = Vec::new();
+    file.take(PROBE_SAMPLE_BYTES).read_to_end(&mut sample)?;
+
+    let encoding = detect_encoding(&sample);
+    if matches!(encoding, CsvEncoding::Utf16Le | CsvEncoding::Utf16Be) && sample.len() % 2 == 1 {
+        sample.pop();
+    }
+    let text = decode_sample(&sample, encoding);
+    let delimiter = detect_delimiter(&text);
+
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .delimiter(delimiter)
+        .from_reader(text.as_bytes());
+    let headers: Vec<String> = rdr.headers()?.iter().map(|h| h.trim().to_string()).collect();
+    let mut sampled_rows = 0;
+    for record in rdr.records() {
+        match record {
+            Ok(record) if record.iter().all(|field| field.trim().is_empty()) => {}
+            Ok(_) => sampled_rows += 1,
+            // A quoted field cut off at the end of the sample
+            Err(_) => break,
+        }
+    }
+
+    let row_count_exact = (sample.len() as u64) >= file_size;
+    let row_count = if row_count_exact || sample.is_empty() {
+        sampled_rows
+    } else {
+        (sampled_rows as f64 * file_size as f64 / sample.len() as f64).round() as usize
+    };
+    let delimiter_name = DELIMITER_CANDIDATES
+        .iter()
+        .find(|&&(d, _)| d == delimiter)
+        .map(|&(_, name)| name)
+        .unwrap_or("comma");
+
+    Ok(CsvProbe {
+        delimiter: (delimiter as char).to_string(),
+        delimiter_name: delimiter_name.to_string(),
+        encoding,
+        headers: headers.into_iter().filter(|h| !h.is_empty()).collect(),
+        row_count,
+        row_count_exact,
+        file_size,
+    })
+}
+
+#[tauri::command]
+pub fn probe_csv(file_path: String) -> Result<CsvProbe, AppError> {
+    log::info!("Probing CSV: {}", file_path);
+    probe_csv_file(&file_path)
+}
+
 // ============================================================
 // Tests
 // ============================================================
@@ -310,6 +766,99 @@ pub fn validate_csv(file_path: String) -> Result<ValidationResult, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::{Path, PathBuf};
+
+    /// Per-test temp directory, removed when dropped
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(label: &str) -> Self {
+            let dir =
+                std::env::temp_dir().join(format!("sugarland_{}_{}", label, uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(&dir).unwrap();
+            TestDir(dir)
+        }
+    }
+
+    impl std::ops::Deref for TestDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_validate_hibid_results_flags_bad_rows() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("hibid_results_{}.csv", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid\n1,TV,1001,Buyer,30500,31000\n,Drill,1002,Buyer,abc,\n3,Lamp,,,,\n",
+        )
+        .unwrap();
+        let result = validate_hibid_results_file(path.to_str().unwrap()).unwrap();
+        assert!(result.valid);
+        assert_eq!(
+            result.warnings,
+            [
+                "Row 3: Blank lot number",
+                "Row 3: Unparseable High Bid \"abc\""
+            ]
+        );
+        assert!(result.message.contains("Checked 3 rows"));
+
+        std::fs::write(&path, "Lot,Title,High Bid\n1,TV,100\n").unwrap();
+        let result = validate_hibid_results_file(path.to_str().unwrap()).unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.message, "Missing required columns: Winning Bidder");
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_probe_csv_detects_delimiter_and_encoding() {
+        let path = std::env::temp_dir().join(format!("probe_{}.csv", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            b"LotNumber\tTitle\tRetail Price\n1\tCaf\xe9 table\t$10\n2\t\"Lamp, brass\"\t$5\n",
+        )
+        .unwrap();
+        let probe = probe_csv_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(probe.delimiter, "\t");
+        assert_eq!(probe.delimiter_name, "tab");
+        assert_eq!(probe.encoding, CsvEncoding::Latin1);
+        assert_eq!(probe.headers, ["LotNumber", "Title", "Retail Price"]);
+        assert_eq!(probe.row_count, 2);
+        assert!(probe.row_count_exact);
+
+        std::fs::write(
+            &path,
+            "\u{feff}Lot;Title;High Bid\n1;\"TV; 55 in\";100\n2;Drill;50\n3;Lamp;\n",
+        )
+        .unwrap();
+        let probe = probe_csv_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(probe.delimiter_name, "semicolon");
+        assert_eq!(probe.encoding, CsvEncoding::Utf8Bom);
+        assert_eq!(probe.headers, ["Lot", "Title", "High Bid"]);
+        assert_eq!(probe.row_count, 3);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_is_garbage_title() {
+        assert!(is_garbage_title("", 3));
+        assert!(is_garbage_title(" X ", 3));
+        assert!(is_garbage_title("12345", 3));
+        assert!(is_garbage_title("#42 - 7", 3));
+        assert!(!is_garbage_title("TV", 2));
+        assert!(!is_garbage_title("Dewalt 20V Drill", 3));
+    }
 
     #[test]
     fn test_clean_price() {
@@ -322,6 +871,22 @@ mod tests {
         assert_eq!(clean_hibid_cents_price("30500"), 305.0);
     }
 
+    #[test]
+    fn test_normalize_phone() {
+        assert_eq!(normalize_phone("(503) 555-1234"), "(503) 555-1234");
+        assert_eq!(normalize_phone("5035551234"), "(503) 555-1234");
+        assert_eq!(normalize_phone("503.555.1234"), "(503) 555-1234");
+        assert_eq!(normalize_phone("503-555-1234"), "(503) 555-1234");
+        assert_eq!(normalize_phone("+1 503 555 1234"), "(503) 555-1234");
+        assert_eq!(normalize_phone("1-503-555-1234"), "(503) 555-1234");
+        assert_eq!(
+            normalize_phone("503-555-1234 ext. 12"),
+            "(503) 555-1234 x12"
+        );
+        assert_eq!(normalize_phone(" 555-1234 "), "555-1234");
+        assert_eq!(normalize_phone(""), "");
+    }
+
     #[test]
     fn test_normalize_source() {
         assert_eq!(normalize_source(&Some("Best Buy".to_string())), "Best Buy");
@@ -372,4 +937,59 @@ mod tests {
         assert!(!parse_read_description_flag(&Some("0".to_string())));
         assert!(!parse_read_description_flag(&None));
     }
+
+    #[test]
+    fn test_validate_reports_no_data_rows() {
+        let dir = TestDir::new("validate");
+
+        let header_only = dir.join("header_only.csv");
+        std::fs::write(&header_only, "LotNumber,Title,Retail Price\n").unwrap();
+        let result = validate_bstock_csv(header_only.to_str().unwrap()).unwrap();
+        assert!(!result.valid);
+        assert!(result.message.starts_with("No data rows"));
+
+        let empty = dir.join("empty.csv");
+        std::fs::write(&empty, "").unwrap();
+        let result = validate_bstock_csv(empty.to_str().unwrap()).unwrap();
+        assert!(!result.valid);
+        assert!(result.message.starts_with("No data rows"));
+
+        let missing = dir.join("missing.csv");
+        std::fs::write(&missing, "LotNumber,Title\n1,Item\n").unwrap();
+        let result = validate_bstock_csv(missing.to_str().unwrap()).unwrap();
+        assert!(result.message.starts_with("Missing required columns"));
+    }
+
+    #[test]
+    fn test_parse_hibid_results_combined_bidder_column() {
+        assert_eq!(
+            split_combined_bidder("Smith, John (5046)"),
+            Some(("Smith, John".to_string(), "5046".to_string()))
+        );
+        assert_eq!(split_combined_bidder("5046"), None);
+
+        let dir = TestDir::new("results");
+
+        let combined = dir.join("combined.csv");
+        std::fs::write(
+            &combined,
+            "Lot,Title,Winning Bidder,High Bid\n1,TV,\"Smith, John (5046)\",30500\n2,Drill,,\n",
+        )
+        .unwrap();
+        let rows = parse_hibid_results(combined.to_str().unwrap()).unwrap();
+        assert_eq!(rows[0].winning_bidder, "Smith, John");
+        assert_eq!(rows[0].bidder_id, "5046");
+        assert_eq!(rows[1].winning_bidder, "");
+        assert_eq!(rows[1].bidder_id, "");
+
+        let separate = dir.join("separate.csv");
+        std::fs::write(
+            &separate,
+            "Lot,Title,Winning Bidder,Name,High Bid\n1,TV,5046,Smith (John),30500\n",
+        )
+        .unwrap();
+        let rows = parse_hibid_results(separate.to_str().unwrap()).unwrap();
+        assert_eq!(rows[0].winning_bidder, "Smith (John)");
+        assert_eq!(rows[0].bidder_id, "5046");
+    }
 }
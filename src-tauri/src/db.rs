@@ -1,7 +1,160 @@
 // Database module — SQLite management with migrations and queries
 
+use chrono::Datelike;
 use rusqlite::{Connection, Result};
 use serde::Serialize;
+use std::collections::HashMap;
+
+/// Settings key holding the month (1-12) → season label map (JSON object)
+pub const SEASON_MAP_SETTING: &str = "season_map";
+
+/// Settings key naming the staff member stamped into `created_by` columns
+pub const CURRENT_USER_SETTING: &str = "current_user";
+
+/// `created_by` value when no current user is set
+pub const DEFAULT_CURRENT_USER: &str = "system";
+
+/// Sales analytics by brand/category/season
+const V_SALES_ANALYTICS_SQL: &str = "
+    CREATE VIEW IF NOT EXISTS v_sales_analytics AS
+    SELECT
+        hs.extracted_brand,
+        hs.category,
+        hs.season,
+        COUNT(*) as sales_count,
+        AVG(hs.sale_price) as avg_sale_price,
+        AVG(hs.retail_price) as avg_retail_price,
+        MIN(hs.sale_date) as first_sale,
+        MAX(hs.sale_date) as last_sale
+    FROM historical_sales hs
+    GROUP BY hs.extracted_brand, hs.category, hs.season;
+";
+
+/// P&L per auction, classified by the persisted auction_results item_status
+const V_AUCTION_PNL_SQL: &str = "
+    CREATE VIEW IF NOT EXISTS v_auction_pnl AS
+    SELECT
+        a.id as auction_id,
+        a.name as auction_name,
+        a.start_date,
+        a.end_date,
+        COUNT(DISTINCT i.id) as total_items,
+        SUM(
+            CASE
+                WHEN COALESCE(
+                    ar.item_status,
+                    CASE
+                        WHEN ar.is_buyback = TRUE THEN 'Buyback'
+                        WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
+                        ELSE 'Unsold'
+                    END
+                ) = 'Sold' THEN 1
+                ELSE 0
+            END
+        ) as sold_items,
+        SUM(
+            CASE
+                WHEN COALESCE(
+                    ar.item_status,
+                    CASE
+                        WHEN ar.is_buyback = TRUE THEN 'Buyback'
+                        WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
+                        ELSE 'Unsold'
+                    END
+                ) = 'Buyback' THEN 1
+                ELSE 0
+            END
+        ) as buyback_items,
+        SUM(
+            CASE
+                WHEN COALESCE(
+                    ar.item_status,
+                    CASE
+                        WHEN ar.is_buyback = TRUE THEN 'Buyback'
+                        WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
+                        ELSE 'Unsold'
+                    END
+                ) = 'Sold' THEN ar.high_bid
+                ELSE 0
+            END
+        ) as total_revenue,
+        SUM(
+            CASE
+                WHEN COALESCE(
+                    ar.item_status,
+                    CASE
+                        WHEN ar.is_buyback = TRUE THEN 'Buyback'
+                        WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
+                        ELSE 'Unsold'
+                    END
+                ) = 'Sold' THEN i.cost_price
+                ELSE 0
+            END
+        ) as total_cost,
+        SUM(
+            CASE
+                WHEN COALESCE(
+                    ar.item_status,
+                    CASE
+                        WHEN ar.is_buyback = TRUE THEN 'Buyback'
+                        WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
+                        ELSE 'Unsold'
+                    END
+                ) = 'Sold' THEN ar.commission_amount
+                ELSE 0
+            END
+        ) as total_commission,
+        SUM(
+            CASE
+                WHEN COALESCE(
+                    ar.item_status,
+                    CASE
+                        WHEN ar.is_buyback = TRUE THEN 'Buyback'
+                        WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
+                        ELSE 'Unsold'
+                    END
+                ) = 'Sold' THEN COALESCE(ar.high_bid, 0) - COALESCE(i.cost_price, 0)
+                ELSE 0
+            END
+        ) as net_profit
+    FROM auctions a
+    LEFT JOIN inventory_items i ON i.auction_id = a.id
+    LEFT JOIN auction_results ar ON ar.auction_id = a.id AND ar.item_id = i.id
+    GROUP BY a.id;
+";
+
+/// Conservative P&L that also charges buyback item costs (tied-up capital)
+const V_AUCTION_PNL_FULL_SQL: &str = "
+    CREATE VIEW IF NOT EXISTS v_auction_pnl_full AS
+    SELECT
+        p.auction_id,
+        p.auction_name,
+        p.start_date,
+        p.end_date,
+        p.total_items,
+        p.sold_items,
+        p.buyback_items,
+        p.total_revenue,
+        p.total_cost,
+        p.total_commission,
+        COALESCE(b.buyback_cost, 0) as buyback_cost,
+        COALESCE(p.net_profit, 0) - COALESCE(b.buyback_cost, 0) as net_profit
+    FROM v_auction_pnl p
+    LEFT JOIN (
+        SELECT ar.auction_id, SUM(COALESCE(i.cost_price, 0)) as buyback_cost
+        FROM auction_results ar
+        JOIN inventory_items i ON i.id = ar.item_id
+        WHERE COALESCE(
+            ar.item_status,
+            CASE
+                WHEN ar.is_buyback = TRUE THEN 'Buyback'
+                WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
+                ELSE 'Unsold'
+            END
+        ) = 'Buyback'
+        GROUP BY ar.auction_id
+    ) b ON b.auction_id = p.auction_id;
+";
 
 pub struct Database {
     pub conn: Connection,
@@ -34,11 +187,63 @@ pub struct InventoryItemRow {
     pub buybacker_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// Free-text staff note ("missing remote"); appended to the HiBid description
+    pub notes: Option<String>,
+    /// (min_price − cost) / min_price in percent, stored when prices are set
+    pub gross_margin_pct: Option<f64>,
+    /// Set by staff to send the item to the manager review queue
+    pub is_flagged: bool,
+    pub flag_reason: Option<String>,
+    /// Title was empty, too short or had no letters on import; cleared when fixed
+    pub needs_title: bool,
+    /// Warehouse bin/shelf where the item is stored ("B-12"), for picklists
+    pub location: Option<String>,
+}
+
+/// Filters for `get_inventory_filtered`; blank values are ignored
+#[derive(Debug, Default)]
+pub struct InventoryFilter<'a> {
+    /// Exact status; `None` means everything not Sold
+    pub status: Option<&'a str>,
+    /// Case-insensitive substring of the condition, e.g. "open box"
+    pub condition: Option<&'a str>,
+    pub manifest_id: Option<&'a str>,
+    /// Case-insensitive source (vendor) name, e.g. "Best Buy"
+    pub source: Option<&'a str>,
+}
+
+/// One imported manifest (B-Stock shipment) with its annotations
+#[derive(Debug, Serialize)]
+pub struct ManifestRow {
+    pub id: String,
+    pub import_date: String,
+    pub source_filename: String,
+    pub total_retail_value: f64,
+    pub total_cost: f64,
+    pub items_count: i64,
+    pub status: String,
+    /// Free-text note ("damaged in transit")
+    pub notes: Option<String>,
+    /// Labels such as "store #42 closeout", stored comma-separated
+    pub tags: Vec<String>,
+    pub created_by: String,
+}
+
+/// Split a comma-separated tags column into trimmed, non-empty tags
+pub fn parse_tags(raw: Option<&str>) -> Vec<String> {
+    raw.unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
 }
 
 #[derive(Debug, Serialize)]
 pub struct DashboardStats {
     pub total_items: i64,
+    /// Sum of `quantity` across all items (units, where `total_items` counts rows)
+    pub total_quantity: i64,
     pub in_stock: i64,
     pub listed: i64,
     pub sold: i64,
@@ -48,6 +253,123 @@ pub struct DashboardStats {
     pub active_auctions: i64,
 }
 
+#[derive(Debug, Serialize)]
+pub struct ValuationBucket {
+    pub key: String,
+    pub item_count: i64,
+    pub total_cost: f64,
+    pub total_retail: f64,
+    pub total_min_price: f64,
+}
+
+/// Point-in-time value of on-hand inventory (everything not Sold or Scrap)
+#[derive(Debug, Serialize)]
+pub struct InventoryValuation {
+    pub item_count: i64,
+    pub total_cost: f64,
+    pub total_retail: f64,
+    pub total_min_price: f64,
+    pub by_source: Vec<ValuationBucket>,
+    pub by_category: Vec<ValuationBucket>,
+}
+
+/// A distinct value present in inventory with the number of items carrying it
+#[derive(Debug, Serialize)]
+pub struct FacetValue {
+    pub value: String,
+    pub count: i64,
+}
+
+/// Distinct filter values actually present in inventory
+#[derive(Debug, Serialize)]
+pub struct InventoryFacets {
+    pub sources: Vec<FacetValue>,
+    pub categories: Vec<FacetValue>,
+    pub brands: Vec<FacetValue>,
+    pub statuses: Vec<FacetValue>,
+}
+
+/// Imported volume per source over a date range
+#[derive(Debug, Serialize)]
+pub struct SourceStats {
+    pub source: String,
+    pub item_count: i64,
+    pub total_quantity: i64,
+    pub total_cost: f64,
+    pub total_retail: f64,
+    /// Cost paid per dollar of retail (total cost / total retail)
+    pub avg_recovery_coefficient: f64,
+}
+
+/// Result of `Database::rebuild_derived`
+#[derive(Debug, Serialize)]
+pub struct DerivedRebuild {
+    pub views: Vec<String>,
+    /// Indexes present after the rebuild
+    pub index_count: i64,
+}
+
+/// Column list matching `map_inventory_item_row`. Add columns at the end and
+/// update the mapper in the same change; queries that select extra columns
+/// after this list must read them by name, not position (see
+/// `AuctionManager::auction_items`).
+pub const INVENTORY_ITEM_COLUMNS: &str = "id, manifest_id, lot_number, quantity,
+    raw_title, vendor_code, source, condition, read_description_flag,
+    normalized_title, extracted_brand, extracted_model, sku_extracted, category,
+    retail_price, cost_price, min_price,
+    current_status, auction_id, listed_at, sold_at,
+    sale_order, buybacker_id,
+    created_at, updated_at, notes, gross_margin_pct, is_flagged, flag_reason, needs_title,
+    location";
+
+/// Map a row selected with `INVENTORY_ITEM_COLUMNS` into an `InventoryItemRow`
+pub fn map_inventory_item_row(row: &rusqlite::Row<'_>) -> Result<InventoryItemRow> {
+    Ok(InventoryItemRow {
+        id: row.get(0)?,
+        manifest_id: row.get(1)?,
+        lot_number: row.get(2)?,
+        quantity: row.get(3)?,
+        raw_title: row.get(4)?,
+        vendor_code: row.get(5)?,
+        source: row.get(6)?,
+        condition: row.get(7)?,
+        read_description_flag: row.get::<_, Option<bool>>(8)?.unwrap_or(false),
+        normalized_title: row.get(9)?,
+        extracted_brand: row.get(10)?,
+        extracted_model: row.get(11)?,
+        sku_extracted: row.get(12)?,
+        category: row.get(13)?,
+        retail_price: row.get(14)?,
+        cost_price: row.get(15)?,
+        min_price: row.get(16)?,
+        current_status: row.get(17)?,
+        auction_id: row.get(18)?,
+        listed_at: row.get(19)?,
+        sold_at: row.get(20)?,
+        sale_order: row.get(21)?,
+        buybacker_id: row.get(22)?,
+        created_at: row.get(23)?,
+        updated_at: row.get(24)?,
+        notes: row.get(25)?,
+        gross_margin_pct: row.get(26)?,
+        is_flagged: row.get::<_, Option<bool>>(27)?.unwrap_or(false),
+        flag_reason: row.get(28)?,
+        needs_title: row.get::<_, Option<bool>>(29)?.unwrap_or(false),
+        location: row.get(30)?,
+    })
+}
+
+/// Default calendar-quarter season for a month (1-12)
+pub fn default_season_for_month(month: u32) -> String {
+    match month {
+        1..=3 => "Q1",
+        4..=6 => "Q2",
+        7..=9 => "Q3",
+        _ => "Q4",
+    }
+    .to_string()
+}
+
 impl Database {
     pub fn new(path: &str) -> Result<Self> {
         let conn = Connection::open(path)?;
@@ -211,39 +533,6 @@ impl Database {
             CREATE INDEX IF NOT EXISTS idx_historical_brand ON historical_sales(extracted_brand);
             CREATE INDEX IF NOT EXISTS idx_historical_date ON historical_sales(sale_date);
 
-            -- Trigger: auto-populate historical_sales on auction result insertion
-            CREATE TRIGGER IF NOT EXISTS after_auction_result_insert
-            AFTER INSERT ON auction_results
-            FOR EACH ROW
-            WHEN NEW.is_buyback = FALSE
-            BEGIN
-                INSERT OR IGNORE INTO historical_sales (
-                    id, normalized_title, extracted_brand, extracted_sku,
-                    category, condition, retail_price, cost_price,
-                    sale_price, sale_date, platform, season
-                )
-                SELECT
-                    NEW.id,
-                    i.normalized_title,
-                    i.extracted_brand,
-                    i.sku_extracted,
-                    i.category,
-                    i.condition,
-                    i.retail_price,
-                    i.cost_price,
-                    NEW.high_bid,
-                    DATE('now'),
-                    'HiBid',
-                    CASE
-                        WHEN CAST(strftime('%m', 'now') AS INTEGER) BETWEEN 1 AND 3 THEN 'Q1'
-                        WHEN CAST(strftime('%m', 'now') AS INTEGER) BETWEEN 4 AND 6 THEN 'Q2'
-                        WHEN CAST(strftime('%m', 'now') AS INTEGER) BETWEEN 7 AND 9 THEN 'Q3'
-                        ELSE 'Q4'
-                    END
-                FROM inventory_items i
-                WHERE i.id = NEW.item_id;
-            END;
-
             -- Settings table for runtime configuration
             CREATE TABLE IF NOT EXISTS settings (
                 key TEXT PRIMARY KEY,
@@ -392,6 +681,24 @@ impl Database {
             "ALTER TABLE inventory_items ADD COLUMN read_description_flag BOOLEAN NOT NULL DEFAULT FALSE",
             [],
         );
+        // Migration: link items bundled by merge_items back to the lot they were merged into
+        let _ = self.conn.execute(
+            "ALTER TABLE inventory_items ADD COLUMN merged_into_id TEXT",
+            [],
+        );
+        let _ = self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_inventory_merged_into ON inventory_items(merged_into_id)",
+            [],
+        );
+        // Migration: comma-separated alternative source names per vendor
+        let _ = self
+            .conn
+            .execute("ALTER TABLE vendors ADD COLUMN aliases TEXT", []);
+        // Migration: overall NLP extraction confidence for the review queue
+        let _ = self.conn.execute(
+            "ALTER TABLE inventory_items ADD COLUMN nlp_confidence REAL",
+            [],
+        );
         // Migration: keep per-attempt snapshot data for reliable repeater analytics
         let _ = self.conn.execute(
             "ALTER TABLE auction_results ADD COLUMN item_status TEXT",
@@ -421,138 +728,190 @@ impl Database {
              WHERE min_price_snapshot IS NULL",
             [],
         );
-        // Migration: keep historical_sales focused on true sold attempts only
+        // Migration: per-auction commission terms (flat fee per lot, minimum per lot)
+        let _ = self
+            .conn
+            .execute("ALTER TABLE auctions ADD COLUMN commission_flat REAL", []);
+        let _ = self
+            .conn
+            .execute("ALTER TABLE auctions ADD COLUMN commission_min REAL", []);
+        // Migration: phone exactly as it appeared in the results file (bidder_phone is normalized)
+        let _ = self.conn.execute(
+            "ALTER TABLE auction_results ADD COLUMN bidder_phone_raw TEXT",
+            [],
+        );
+        // Migration: optional per-auction prefix for exported HiBid lot numbers
+        let _ = self
+            .conn
+            .execute("ALTER TABLE auctions ADD COLUMN lot_prefix TEXT", []);
+        // Migration: days between listing and sale, for velocity stats
+        let _ = self.conn.execute(
+            "ALTER TABLE auction_results ADD COLUMN days_listed REAL",
+            [],
+        );
+        let _ = self.conn.execute(
+            "UPDATE auction_results
+             SET days_listed = (
+                 SELECT julianday(i.sold_at) - julianday(i.listed_at)
+                 FROM inventory_items i
+                 WHERE i.id = auction_results.item_id
+                   AND i.current_status = 'Sold'
+             )
+             WHERE days_listed IS NULL AND item_status = 'Sold'",
+            [],
+        );
+        // Migration: vendors whose lots carry no auction house commission (own consignments)
+        let _ = self.conn.execute(
+            "ALTER TABLE vendors ADD COLUMN commission_exempt BOOLEAN NOT NULL DEFAULT 0",
+            [],
+        );
+        let _ = self.conn.execute(
+            "ALTER TABLE auction_results ADD COLUMN commission_exempt BOOLEAN NOT NULL DEFAULT 0",
+            [],
+        );
+        // Migration: who created the record (`current_user` setting), for basic accountability
+        for table in ["manifests", "auctions", "auction_results"] {
+            let _ = self.conn.execute(
+                &format!(
+                    "ALTER TABLE {} ADD COLUMN created_by TEXT NOT NULL DEFAULT 'system'",
+                    table
+                ),
+                [],
+            );
+        }
+        // Migration: free-text staff notes per item
+        let _ = self
+            .conn
+            .execute("ALTER TABLE inventory_items ADD COLUMN notes TEXT", []);
+        // Migration: stored gross margin percent for margin filtering (see pricing::gross_margin_pct)
+        let _ = self.conn.execute(
+            "ALTER TABLE inventory_items ADD COLUMN gross_margin_pct REAL",
+            [],
+        );
+        let _ = self.conn.execute(
+            "UPDATE inventory_items
+             SET gross_margin_pct = ROUND((min_price - cost_price) * 100.0 / min_price, 2)
+             WHERE gross_margin_pct IS NULL AND min_price > 0",
+            [],
+        );
+        let _ = self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_items_gross_margin ON inventory_items(gross_margin_pct)",
+            [],
+        );
+        // Migration: optional lot capacity per auction (NULL = unlimited)
+        let _ = self
+            .conn
+            .execute("ALTER TABLE auctions ADD COLUMN max_lots INTEGER", []);
+        // Migration: Standard or Cash auction, selecting the commission rate setting
+        let _ = self.conn.execute(
+            "ALTER TABLE auctions ADD COLUMN auction_type TEXT NOT NULL DEFAULT 'Standard'",
+            [],
+        );
+        // Migration: buyer's premium per result (paid by the buyer, informational)
+        let _ = self.conn.execute(
+            "ALTER TABLE auction_results ADD COLUMN buyers_premium REAL NOT NULL DEFAULT 0",
+            [],
+        );
+        // Migration: manager review flag ("wrong price", "check condition")
+        let _ = self.conn.execute(
+            "ALTER TABLE inventory_items ADD COLUMN is_flagged BOOLEAN NOT NULL DEFAULT 0",
+            [],
+        );
+        let _ = self.conn.execute(
+            "ALTER TABLE inventory_items ADD COLUMN flag_reason TEXT",
+            [],
+        );
+        // Migration: warehouse bin/location per item, printed on picklists
+        let _ = self
+            .conn
+            .execute("ALTER TABLE inventory_items ADD COLUMN location TEXT", []);
+        // Migration: imported rows whose title is missing or garbage ("X", "12345")
+        let _ = self.conn.execute(
+            "ALTER TABLE inventory_items ADD COLUMN needs_title BOOLEAN NOT NULL DEFAULT 0",
+            [],
+        );
+        // Migration: salvage recovered on scrapped items, netted against the P&L write-off
+        let _ = self.conn.execute(
+            "ALTER TABLE inventory_items ADD COLUMN scrap_value REAL NOT NULL DEFAULT 0",
+            [],
+        );
+        // Migration: link split pieces to their original so its Scrap row is not written off
+        let _ = self.conn.execute(
+            "ALTER TABLE inventory_items ADD COLUMN split_from_id TEXT",
+            [],
+        );
+        let _ = self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_inventory_split_from ON inventory_items(split_from_id)",
+            [],
+        );
+        // Migration: comma-separated labels per manifest ("store #42 closeout")
+        let _ = self
+            .conn
+            .execute("ALTER TABLE manifests ADD COLUMN tags TEXT", []);
+        // Migration: item status history, written by triggers so every status
+        // change is recorded no matter which command makes it. Items created
+        // before this migration have no history until their next change.
         let _ = self.conn.execute_batch(
-            "
-            DROP TRIGGER IF EXISTS after_auction_result_insert;
-            CREATE TRIGGER IF NOT EXISTS after_auction_result_insert
-            AFTER INSERT ON auction_results
+            "CREATE TABLE IF NOT EXISTS item_status_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                item_id TEXT NOT NULL,
+                from_status TEXT,
+                to_status TEXT NOT NULL,
+                changed_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE INDEX IF NOT EXISTS idx_status_history_item
+                ON item_status_history(item_id, id);
+            CREATE TRIGGER IF NOT EXISTS record_item_status_insert
+            AFTER INSERT ON inventory_items
             FOR EACH ROW
-            WHEN NEW.is_buyback = FALSE AND COALESCE(NEW.item_status, 'Sold') = 'Sold'
             BEGIN
-                INSERT OR IGNORE INTO historical_sales (
-                    id, normalized_title, extracted_brand, extracted_sku,
-                    category, condition, retail_price, cost_price,
-                    sale_price, sale_date, platform, season
-                )
-                SELECT
-                    NEW.id,
-                    i.normalized_title,
-                    i.extracted_brand,
-                    i.sku_extracted,
-                    i.category,
-                    i.condition,
-                    i.retail_price,
-                    i.cost_price,
-                    NEW.high_bid,
-                    DATE('now'),
-                    'HiBid',
-                    CASE
-                        WHEN CAST(strftime('%m', 'now') AS INTEGER) BETWEEN 1 AND 3 THEN 'Q1'
-                        WHEN CAST(strftime('%m', 'now') AS INTEGER) BETWEEN 4 AND 6 THEN 'Q2'
-                        WHEN CAST(strftime('%m', 'now') AS INTEGER) BETWEEN 7 AND 9 THEN 'Q3'
-                        ELSE 'Q4'
-                    END
-                FROM inventory_items i
-                WHERE i.id = NEW.item_id;
+                INSERT INTO item_status_history (item_id, from_status, to_status, changed_at)
+                VALUES (NEW.id, NULL, COALESCE(NEW.current_status, 'InStock'),
+                        COALESCE(NEW.created_at, CURRENT_TIMESTAMP));
             END;
-            ",
+            CREATE TRIGGER IF NOT EXISTS record_item_status_change
+            AFTER UPDATE OF current_status ON inventory_items
+            FOR EACH ROW
+            WHEN OLD.current_status IS NOT NEW.current_status
+            BEGIN
+                INSERT INTO item_status_history (item_id, from_status, to_status)
+                VALUES (NEW.id, OLD.current_status, NEW.current_status);
+            END;
+            CREATE TRIGGER IF NOT EXISTS delete_item_status_history
+            AFTER DELETE ON inventory_items
+            FOR EACH ROW
+            BEGIN
+                DELETE FROM item_status_history WHERE item_id = OLD.id;
+            END;",
         );
-        // Migration: refresh v_auction_pnl to use persisted auction_results item_status
-        let _ = self.conn.execute_batch(
-            "
-            DROP VIEW IF EXISTS v_auction_pnl;
-            CREATE VIEW IF NOT EXISTS v_auction_pnl AS
-            SELECT
-                a.id as auction_id,
-                a.name as auction_name,
-                a.start_date,
-                a.end_date,
-                COUNT(DISTINCT i.id) as total_items,
-                SUM(
-                    CASE
-                        WHEN COALESCE(
-                            ar.item_status,
-                            CASE
-                                WHEN ar.is_buyback = TRUE THEN 'Buyback'
-                                WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
-                                ELSE 'Unsold'
-                            END
-                        ) = 'Sold' THEN 1
-                        ELSE 0
-                    END
-                ) as sold_items,
-                SUM(
-                    CASE
-                        WHEN COALESCE(
-                            ar.item_status,
-                            CASE
-                                WHEN ar.is_buyback = TRUE THEN 'Buyback'
-                                WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
-                                ELSE 'Unsold'
-                            END
-                        ) = 'Buyback' THEN 1
-                        ELSE 0
-                    END
-                ) as buyback_items,
-                SUM(
-                    CASE
-                        WHEN COALESCE(
-                            ar.item_status,
-                            CASE
-                                WHEN ar.is_buyback = TRUE THEN 'Buyback'
-                                WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
-                                ELSE 'Unsold'
-                            END
-                        ) = 'Sold' THEN ar.high_bid
-                        ELSE 0
-                    END
-                ) as total_revenue,
-                SUM(
-                    CASE
-                        WHEN COALESCE(
-                            ar.item_status,
-                            CASE
-                                WHEN ar.is_buyback = TRUE THEN 'Buyback'
-                                WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
-                                ELSE 'Unsold'
-                            END
-                        ) = 'Sold' THEN i.cost_price
-                        ELSE 0
-                    END
-                ) as total_cost,
-                SUM(
-                    CASE
-                        WHEN COALESCE(
-                            ar.item_status,
-                            CASE
-                                WHEN ar.is_buyback = TRUE THEN 'Buyback'
-                                WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
-                                ELSE 'Unsold'
-                            END
-                        ) = 'Sold' THEN ar.commission_amount
-                        ELSE 0
-                    END
-                ) as total_commission,
-                SUM(
-                    CASE
-                        WHEN COALESCE(
-                            ar.item_status,
-                            CASE
-                                WHEN ar.is_buyback = TRUE THEN 'Buyback'
-                                WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
-                                ELSE 'Unsold'
-                            END
-                        ) = 'Sold' THEN COALESCE(ar.high_bid, 0) - COALESCE(i.cost_price, 0)
-                        ELSE 0
-                    END
-                ) as net_profit
-            FROM auctions a
-            LEFT JOIN inventory_items i ON i.auction_id = a.id
-            LEFT JOIN auction_results ar ON ar.auction_id = a.id AND ar.item_id = i.id
-            GROUP BY a.id;
-            ",
+        // Migration: items scrapped before the history table get a Scrap entry
+        // dated by their last update, so the write-off date stops moving with edits
+        let _ = self.conn.execute(
+            "INSERT INTO item_status_history (item_id, from_status, to_status, changed_at)
+             SELECT i.id, NULL, 'Scrap', COALESCE(i.updated_at, i.created_at, CURRENT_TIMESTAMP)
+             FROM inventory_items i
+             WHERE i.current_status = 'Scrap'
+               AND NOT EXISTS (
+                   SELECT 1 FROM item_status_history h
+                   WHERE h.item_id = i.id AND h.to_status = 'Scrap'
+               )",
+            [],
         );
+        // Migration: historical_sales rows are written from Rust (record_historical_sale)
+        // so seasons follow the configurable season map instead of a hardcoded trigger
+        let _ = self
+            .conn
+            .execute("DROP TRIGGER IF EXISTS after_auction_result_insert", []);
+        // Migration: refresh v_auction_pnl to use persisted auction_results item_status
+        let _ = self.conn.execute_batch(&format!(
+            "DROP VIEW IF EXISTS v_auction_pnl;{}",
+            V_AUCTION_PNL_SQL
+        ));
+        // Migration: conservative P&L that also charges buyback item costs (tied-up capital)
+        let _ = self.conn.execute_batch(&format!(
+            "DROP VIEW IF EXISTS v_auction_pnl_full;{}",
+            V_AUCTION_PNL_FULL_SQL
+        ));
 
         // ============================================================
         // New tables for ТЗ: condition_types, source_types, pricing_rules, buybackers
@@ -590,6 +949,31 @@ impl Database {
                 created_at DATETIME DEFAULT CURRENT_TIMESTAMP
             );
 
+            -- Profit adjustments (vendor credits, returns) so cost_price stays non-negative
+            CREATE TABLE IF NOT EXISTS adjustments (
+                id TEXT PRIMARY KEY,
+                item_id TEXT NOT NULL REFERENCES inventory_items(id),
+                amount REAL NOT NULL,
+                reason TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_adjustments_item ON adjustments(item_id);
+
+            -- Sales made outside HiBid (cash sales at the lot)
+            CREATE TABLE IF NOT EXISTS cash_sales (
+                id TEXT PRIMARY KEY,
+                item_id TEXT NOT NULL REFERENCES inventory_items(id),
+                buyer_name TEXT,
+                sale_price REAL NOT NULL,
+                commission_rate REAL NOT NULL,
+                commission_amount REAL NOT NULL,
+                net_profit REAL NOT NULL,
+                platform TEXT NOT NULL DEFAULT 'Cash',
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_cash_sales_item ON cash_sales(item_id);
             CREATE INDEX IF NOT EXISTS idx_inventory_sale_order ON inventory_items(sale_order);
             CREATE INDEX IF NOT EXISTS idx_inventory_buybacker ON inventory_items(buybacker_id);
         ")?;
@@ -676,11 +1060,69 @@ impl Database {
         self.conn.execute_batch(
             "
             INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
-                ('ron_larsson_bidder_id', '5046', 'Internal buyback bidder ID (Ron Larsson)', 'reconciliation');
+                ('ron_larsson_bidder_id', '5046', 'Internal buyback bidder IDs, comma-separated (Ron Larsson)', 'reconciliation');
             INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
                 ('default_commission_rate', '0.15', 'Default auction commission rate (15%)', 'financial');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('fallback_commission_rate', '0.15', 'Commission rate used (with a warning) when default_commission_rate is missing or invalid', 'financial');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('commission_flat', '0', 'Flat commission fee added per sold lot', 'financial');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('commission_min', '0', 'Minimum commission per sold lot (0 = no minimum)', 'financial');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('reconcile_allow_zero_high_bid', '0', 'Accept results rows with a winning bidder but no high bid (1 = on)', 'reconciliation');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('reconcile_unreported_status', 'Listed', 'Status for listed items missing from a results file: Listed (keep), InStock (roll over) or Buyback', 'reconciliation');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('buyers_premium_rate', '0', 'HiBid buyer''s premium rate charged to buyers on top of the hammer price (informational)', 'financial');
             INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
                 ('cash_sale_commission_rate', '0.10', 'Commission rate for cash sales (10%)', 'financial');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('default_pricing_vendor', 'Amazon Bstock', 'Vendor used to price items whose source matches no vendor', 'financial');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('condition_price_factors', '{\"New\": 1.0, \"Used\": 1.0, \"Renewed\": 1.0, \"Broken\": 1.0}', 'Min price factor per condition label or category (JSON)', 'financial');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('season_map', '{\"1\": \"Q1\", \"2\": \"Q1\", \"3\": \"Q1\", \"4\": \"Q2\", \"5\": \"Q2\", \"6\": \"Q2\", \"7\": \"Q3\", \"8\": \"Q3\", \"9\": \"Q3\", \"10\": \"Q4\", \"11\": \"Q4\", \"12\": \"Q4\"}', 'Season label per calendar month for historical sales (JSON)', 'analytics');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('default_category', 'General Merchandise', 'Category used for items without a detected category', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('min_listable_retail', '0', 'Import rows with retail below this as Scrap (0 = keep all)', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('min_title_length', '3', 'Imported titles shorter than this, or without letters, are marked as needing a title', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('nlp_normalize_steps', '[\"StopWords\", \"SpecialChars\", \"Whitespace\"]', 'Title normalization steps in order (JSON); leave a step out to turn it off', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('source_category_hints', '{}', 'Category per source for items whose title has no category keyword (JSON)', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('apply_default_category_on_import', '0', 'Write the default category to uncategorized items on import (1 = on)', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('hibid_export_min_bid', '0', 'Export a MinBid reserve column and open StartBid below min price (1 = on)', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('hibid_start_bid_fraction', '0.5', 'StartBid as a fraction of min price when MinBid is exported', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('hibid_start_bid_strategy', 'min_price', 'StartBid basis: min_price, or cost for no-reserve style openings', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('hibid_start_bid_cost_fraction', '0.3', 'StartBid as a fraction of cost with the cost strategy', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('hibid_min_start_bid', '1', 'Lowest StartBid allowed with the cost strategy', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('max_cost_retail_ratio', '0.5', 'Warn on import when a row costs more than this fraction of retail (likely a mis-mapped source)', 'financial');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('hibid_lead_strategy', 'raw', 'Lead composition: raw (first 50 characters of the title) or structured (brand + model + size/capacity + category)', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('hibid_export_columns', '', 'Export column layout as JSON [{\"field\": \"LotNum\", \"header\": \"Lot #\"}] (blank = standard HiBid columns)', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('hibid_bid_increment_ladders', '', 'BidIncrement ladders as JSON {\"default\": [...], \"categories\": {\"Jewelry\": [{\"up_to\": 100, \"increment\": 2}, {\"increment\": 10}]}} (blank = flat 5)', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('export_number_locale', 'dot', 'Decimal separator for numbers in CSV exports: dot (1234.50) or comma (1234,50)', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('image_url_base', '', 'Base URL of hosted lot photos; Images then lists {base}/{lot}-N.jpg (blank = file names)', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('scrap_min_buybacks', '2', 'Suggest scrapping items bought back at least this many times', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('scrap_max_age_days', '120', 'Suggest scrapping unsold items older than this many days', 'general');
+            INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
+                ('current_user', 'system', 'Name stamped as created_by on new manifests, auctions and results', 'general');
             INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
                 ('app_version', '0.3.0', 'Current application version', 'system');
             INSERT OR IGNORE INTO settings (key, value, description, category) VALUES
@@ -794,67 +1236,499 @@ impl Database {
         Ok(())
     }
 
-    pub fn get_inventory_items(&self, status: Option<&str>) -> Result<Vec<InventoryItemRow>> {
-        let mut query = String::from(
-            "SELECT id, manifest_id, lot_number, quantity,
-                    raw_title, vendor_code, source, condition, read_description_flag,
-                    normalized_title, extracted_brand, extracted_model, sku_extracted, category,
-                    retail_price, cost_price, min_price,
-                    current_status, auction_id, listed_at, sold_at,
-                    sale_order, buybacker_id,
-                    created_at, updated_at
-             FROM inventory_items WHERE 1=1",
-        );
-
-        if let Some(s) = status {
-            query.push_str(&format!(" AND current_status = '{}'", s));
-        } else {
-            query.push_str(" AND current_status != 'Sold'");
-        }
+    /// Inventory items by status (default: everything not Sold). `condition`
+    /// matches case-insensitively anywhere in the condition, e.g. "open box".
+    pub fn get_inventory_items(
+        &self,
+        status: Option<&str>,
+        condition: Option<&str>,
+    ) -> Result<Vec<InventoryItemRow>> {
+        self.get_inventory_filtered(&InventoryFilter {
+            status,
+            condition,
+            ..Default::default()
+        })
+    }
 
-        query.push_str(" ORDER BY created_at DESC LIMIT 1000");
+    /// Inventory items matching every filter given, newest first (at most 1000).
+    /// Shared by the inventory grid and the inventory CSV export.
+    pub fn get_inventory_filtered(
+        &self,
+        filter: &InventoryFilter,
+    ) -> Result<Vec<InventoryItemRow>> {
+        let non_blank = |value: Option<&str>| {
+            value
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let query = format!(
+            "SELECT {} FROM inventory_items
+             WHERE (current_status = ?1 OR (?1 IS NULL AND current_status != 'Sold'))
+               AND (?2 IS NULL OR LOWER(COALESCE(condition, '')) LIKE '%' || LOWER(?2) || '%')
+               AND (?3 IS NULL OR manifest_id = ?3)
+               AND (?4 IS NULL OR LOWER(TRIM(COALESCE(source, ''))) = LOWER(?4))
+             ORDER BY created_at DESC LIMIT 1000",
+            INVENTORY_ITEM_COLUMNS
+        );
 
         let mut stmt = self.conn.prepare(&query)?;
         let items = stmt
+            .query_map(
+                rusqlite::params![
+                    non_blank(filter.status),
+                    non_blank(filter.condition),
+                    non_blank(filter.manifest_id),
+                    non_blank(filter.source),
+                ],
+                map_inventory_item_row,
+            )?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(items)
+    }
+
+    pub fn get_facets(&self) -> Result<InventoryFacets> {
+        let facet_query = |column: &str| -> Result<Vec<FacetValue>> {
+            let query = format!(
+                "SELECT TRIM({column}) AS value, COUNT(*)
+                 FROM inventory_items
+                 WHERE {column} IS NOT NULL AND TRIM({column}) != ''
+                 GROUP BY value
+                 ORDER BY 2 DESC, value"
+            );
+            let mut stmt = self.conn.prepare(&query)?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok(FacetValue {
+                        value: row.get(0)?,
+                        count: row.get(1)?,
+                    })
+                })?
+                .collect::<Result<Vec<_>>>()?;
+            Ok(rows)
+        };
+
+        Ok(InventoryFacets {
+            sources: facet_query("source")?,
+            categories: facet_query("category")?,
+            brands: facet_query("extracted_brand")?,
+            statuses: facet_query("current_status")?,
+        })
+    }
+
+    /// Aggregate imported items by source, optionally limited to items created
+    /// between `start_date` and `end_date` (inclusive). Scrap rows (split/merge
+    /// originals) are left out, matching the manifest totals.
+    pub fn get_source_stats(
+        &self,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+    ) -> Result<Vec<SourceStats>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT COALESCE(NULLIF(TRIM(source), ''), 'Unknown') AS src,
+                    COUNT(*),
+                    COALESCE(SUM(quantity), 0),
+                    COALESCE(SUM(cost_price), 0),
+                    COALESCE(SUM(retail_price), 0)
+             FROM inventory_items
+             WHERE current_status != 'Scrap'
+               AND (?1 IS NULL OR datetime(created_at) >= datetime(?1))
+               AND (?2 IS NULL OR datetime(created_at) < datetime(?2, '+1 day'))
+             GROUP BY src
+             ORDER BY 4 DESC",
+        )?;
+        let rows = stmt
+            .query_map(rusqlite::params![start_date, end_date], |row| {
+                let total_cost: f64 = row.get(3)?;
+                let total_retail: f64 = row.get(4)?;
+                let avg_recovery_coefficient = if total_retail > 0.0 {
+                    (total_cost / total_retail * 10000.0).round() / 10000.0
+                } else {
+                    0.0
+                };
+                Ok(SourceStats {
+                    source: row.get(0)?,
+                    item_count: row.get(1)?,
+                    total_quantity: row.get(2)?,
+                    total_cost,
+                    total_retail,
+                    avg_recovery_coefficient,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    /// All manifests, newest import first
+    pub fn get_manifests(&self) -> Result<Vec<ManifestRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, import_date, source_filename,
+                    COALESCE(total_retail_value, 0), COALESCE(total_cost, 0),
+                    COALESCE(items_count, 0), COALESCE(status, 'Imported'),
+                    notes, tags, created_by
+             FROM manifests
+             ORDER BY import_date DESC, source_filename",
+        )?;
+        let manifests = stmt
             .query_map([], |row| {
-                Ok(InventoryItemRow {
+                Ok(ManifestRow {
                     id: row.get(0)?,
-                    manifest_id: row.get(1)?,
-                    lot_number: row.get(2)?,
-                    quantity: row.get(3)?,
-                    raw_title: row.get(4)?,
-                    vendor_code: row.get(5)?,
-                    source: row.get(6)?,
-                    condition: row.get(7)?,
-                    read_description_flag: row.get::<_, Option<bool>>(8)?.unwrap_or(false),
-                    normalized_title: row.get(9)?,
-                    extracted_brand: row.get(10)?,
-                    extracted_model: row.get(11)?,
-                    sku_extracted: row.get(12)?,
-                    category: row.get(13)?,
-                    retail_price: row.get(14)?,
-                    cost_price: row.get(15)?,
-                    min_price: row.get(16)?,
-                    current_status: row.get(17)?,
-                    auction_id: row.get(18)?,
-                    listed_at: row.get(19)?,
-                    sold_at: row.get(20)?,
-                    sale_order: row.get(21)?,
-                    buybacker_id: row.get(22)?,
-                    created_at: row.get(23)?,
-                    updated_at: row.get(24)?,
+                    import_date: row.get(1)?,
+                    source_filename: row.get(2)?,
+                    total_retail_value: row.get(3)?,
+                    total_cost: row.get(4)?,
+                    items_count: row.get(5)?,
+                    status: row.get(6)?,
+                    notes: row.get(7)?,
+                    tags: parse_tags(row.get::<_, Option<String>>(8)?.as_deref()),
+                    created_by: row.get(9)?,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
 
+        Ok(manifests)
+    }
+
+    /// Listed items of one auction, newest first. Unlike `get_inventory_items`
+    /// there is no row cap, so large catalogs export in full.
+    pub fn get_listed_items_for_auction(
+        &self,
+        auction_id: &str,
+        condition: Option<&str>,
+    ) -> Result<Vec<InventoryItemRow>> {
+        let query = format!(
+            "SELECT {} FROM inventory_items
+             WHERE current_status = 'Listed' AND auction_id = ?1
+               AND (?2 IS NULL OR LOWER(COALESCE(condition, '')) LIKE '%' || LOWER(?2) || '%')
+             ORDER BY created_at DESC",
+            INVENTORY_ITEM_COLUMNS
+        );
+
+        let condition = condition.map(str::trim).filter(|c| !c.is_empty());
+        let mut stmt = self.conn.prepare(&query)?;
+        let items = stmt
+            .query_map(
+                rusqlite::params![auction_id, condition],
+                map_inventory_item_row,
+            )?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(items)
+    }
+
+    /// Items whose row changed after `since` (maintained by the updated_at trigger), newest first
+    pub fn get_recent_activity(&self, since: &str, limit: i64) -> Result<Vec<InventoryItemRow>> {
+        let query = format!(
+            "SELECT {} FROM inventory_items
+             WHERE datetime(updated_at) > datetime(?1)
+             ORDER BY datetime(updated_at) DESC
+             LIMIT ?2",
+            INVENTORY_ITEM_COLUMNS
+        );
+
+        let mut stmt = self.conn.prepare(&query)?;
+        let items = stmt
+            .query_map(rusqlite::params![since, limit], map_inventory_item_row)?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(items)
+    }
+
+    /// Valuation of on-hand inventory, excluding Sold and Scrap items
+    pub fn get_valuation(&self) -> Result<InventoryValuation> {
+        let bucket_query = |group_expr: &str| -> Result<Vec<ValuationBucket>> {
+            let query = format!(
+                "SELECT {group_expr} AS bucket, COUNT(*),
+                        COALESCE(SUM(cost_price), 0),
+                        COALESCE(SUM(retail_price), 0),
+                        COALESCE(SUM(min_price), 0)
+                 FROM inventory_items
+                 WHERE current_status NOT IN ('Sold', 'Scrap')
+                 GROUP BY bucket
+                 ORDER BY 3 DESC"
+            );
+            let mut stmt = self.conn.prepare(&query)?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok(ValuationBucket {
+                        key: row.get(0)?,
+                        item_count: row.get(1)?,
+                        total_cost: row.get(2)?,
+                        total_retail: row.get(3)?,
+                        total_min_price: row.get(4)?,
+                    })
+                })?
+                .collect::<Result<Vec<_>>>()?;
+            Ok(rows)
+        };
+
+        let by_source = bucket_query("COALESCE(NULLIF(TRIM(source), ''), 'Unknown')")?;
+        let by_category = bucket_query("COALESCE(NULLIF(TRIM(category), ''), 'Uncategorized')")?;
+
+        Ok(InventoryValuation {
+            item_count: by_source.iter().map(|b| b.item_count).sum(),
+            total_cost: by_source.iter().map(|b| b.total_cost).sum(),
+            total_retail: by_source.iter().map(|b| b.total_retail).sum(),
+            total_min_price: by_source.iter().map(|b| b.total_min_price).sum(),
+            by_source,
+            by_category,
+        })
+    }
+
+    /// Items whose stored gross margin percent lies within the given bounds
+    /// (inclusive), lowest margin first. Items without a min price are excluded.
+    pub fn get_inventory_by_margin(
+        &self,
+        min_pct: Option<f64>,
+        max_pct: Option<f64>,
+    ) -> Result<Vec<InventoryItemRow>> {
+        let query = format!(
+            "SELECT {} FROM inventory_items
+             WHERE gross_margin_pct IS NOT NULL
+               AND (?1 IS NULL OR gross_margin_pct >= ?1)
+               AND (?2 IS NULL OR gross_margin_pct <= ?2)
+             ORDER BY gross_margin_pct ASC, lot_number",
+            INVENTORY_ITEM_COLUMNS
+        );
+
+        let mut stmt = self.conn.prepare(&query)?;
+        let items = stmt
+            .query_map(rusqlite::params![min_pct, max_pct], map_inventory_item_row)?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(items)
+    }
+
+    /// Every item in one status, oldest first, without the grid's row cap
+    pub fn get_items_by_status(&self, status: &str) -> Result<Vec<InventoryItemRow>> {
+        let query = format!(
+            "SELECT {} FROM inventory_items WHERE current_status = ?1 ORDER BY created_at, id",
+            INVENTORY_ITEM_COLUMNS
+        );
+
+        let mut stmt = self.conn.prepare(&query)?;
+        let items = stmt
+            .query_map(rusqlite::params![status], map_inventory_item_row)?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(items)
+    }
+
+    /// Items whose title must be rewritten before listing, in lot order
+    pub fn get_items_needing_title(&self) -> Result<Vec<InventoryItemRow>> {
+        let query = format!(
+            "SELECT {} FROM inventory_items
+             WHERE needs_title = 1
+             ORDER BY manifest_id, lot_number",
+            INVENTORY_ITEM_COLUMNS
+        );
+
+        let mut stmt = self.conn.prepare(&query)?;
+        let items = stmt
+            .query_map([], map_inventory_item_row)?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(items)
+    }
+
+    /// Items flagged for manager review, longest-waiting first
+    pub fn get_flagged_items(&self) -> Result<Vec<InventoryItemRow>> {
+        let query = format!(
+            "SELECT {} FROM inventory_items
+             WHERE is_flagged = 1
+             ORDER BY updated_at ASC, lot_number",
+            INVENTORY_ITEM_COLUMNS
+        );
+
+        let mut stmt = self.conn.prepare(&query)?;
+        let items = stmt
+            .query_map([], map_inventory_item_row)?
+            .collect::<Result<Vec<_>>>()?;
+
         Ok(items)
     }
 
+    /// Drop and recreate the derived views, rebuild indexes and refresh the
+    /// query planner statistics, all in one transaction. Safe to run repeatedly.
+    pub fn rebuild_derived(&self) -> Result<DerivedRebuild> {
+        let tx = self.conn.unchecked_transaction()?;
+        // v_auction_pnl_full reads v_auction_pnl, so it is dropped first and created last
+        tx.execute_batch(&format!(
+            "DROP VIEW IF EXISTS v_auction_pnl_full;
+             DROP VIEW IF EXISTS v_auction_pnl;
+             DROP VIEW IF EXISTS v_sales_analytics;
+             {}{}{}
+             REINDEX;
+             ANALYZE;",
+            V_SALES_ANALYTICS_SQL, V_AUCTION_PNL_SQL, V_AUCTION_PNL_FULL_SQL
+        ))?;
+        let index_count: i64 = tx.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index'",
+            [],
+            |row| row.get(0),
+        )?;
+        tx.commit()?;
+
+        Ok(DerivedRebuild {
+            views: vec![
+                "v_sales_analytics".to_string(),
+                "v_auction_pnl".to_string(),
+                "v_auction_pnl_full".to_string(),
+            ],
+            index_count,
+        })
+    }
+
+    /// Read a raw setting value, `None` when the key is missing
+    pub fn get_setting(&self, key: &str) -> Option<String> {
+        self.conn
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?1",
+                rusqlite::params![key],
+                |row| row.get(0),
+            )
+            .ok()
+    }
+
+    /// Name to stamp in `created_by` columns: the `current_user` setting, or "system"
+    pub fn current_user(&self) -> String {
+        self.get_setting(CURRENT_USER_SETTING)
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| DEFAULT_CURRENT_USER.to_string())
+    }
+
+    /// Load the month → season map from settings; invalid entries are skipped
+    pub fn load_season_map(&self) -> HashMap<u32, String> {
+        let parsed: HashMap<String, String> = self
+            .get_setting(SEASON_MAP_SETTING)
+            .and_then(|value| match serde_json::from_str(&value) {
+                Ok(map) => Some(map),
+                Err(e) => {
+                    log::warn!("Ignoring invalid {} setting: {}", SEASON_MAP_SETTING, e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        parsed
+            .into_iter()
+            .filter_map(|(month, season)| {
+                let month = month.trim().parse::<u32>().ok()?;
+                let season = season.trim().to_string();
+                ((1..=12).contains(&month) && !season.is_empty()).then_some((month, season))
+            })
+            .collect()
+    }
+
+    /// Season label for a month from a loaded season map, falling back to calendar quarters
+    pub fn season_for_month(seasons: &HashMap<u32, String>, month: u32) -> String {
+        seasons
+            .get(&month)
+            .cloned()
+            .unwrap_or_else(|| default_season_for_month(month))
+    }
+
+    /// Reapply the current month → season map to every historical sale by its
+    /// sale_date, e.g. after the season map changes. Returns rows relabelled.
+    pub fn recompute_seasons(&self) -> Result<usize> {
+        let seasons = self.load_season_map();
+        let tx = self.conn.unchecked_transaction()?;
+        let mut changed = 0;
+        for month in 1..=12u32 {
+            let season = Self::season_for_month(&seasons, month);
+            changed += tx.execute(
+                "UPDATE historical_sales SET season = ?1
+                 WHERE CAST(strftime('%m', sale_date) AS INTEGER) = ?2
+                   AND season IS NOT ?1",
+                rusqlite::params![season, month],
+            )?;
+        }
+        tx.commit()?;
+        Ok(changed)
+    }
+
+    /// Copy a sold auction result into historical_sales (no-op for buybacks/unsold),
+    /// labelling it from `seasons` as loaded by `load_season_map`
+    pub fn record_historical_sale(
+        &self,
+        result_id: &str,
+        seasons: &HashMap<u32, String>,
+    ) -> Result<usize> {
+        let season = Self::season_for_month(seasons, chrono::Utc::now().month());
+        self.conn.execute(
+            "INSERT OR IGNORE INTO historical_sales (
+                id, normalized_title, extracted_brand, extracted_sku,
+                category, condition, retail_price, cost_price,
+                sale_price, sale_date, platform, season
+            )
+            SELECT
+                ar.id,
+                i.normalized_title,
+                i.extracted_brand,
+                i.sku_extracted,
+                i.category,
+                i.condition,
+                i.retail_price,
+                i.cost_price,
+                ar.high_bid,
+                DATE('now'),
+                'HiBid',
+                ?2
+            FROM auction_results ar
+            JOIN inventory_items i ON i.id = ar.item_id
+            WHERE ar.id = ?1
+              AND ar.is_buyback = FALSE
+              AND COALESCE(ar.item_status, 'Sold') = 'Sold'",
+            rusqlite::params![result_id, season],
+        )
+    }
+
+    /// Copy a cash sale into historical_sales under its own platform
+    pub fn record_cash_historical_sale(
+        &self,
+        sale_id: &str,
+        seasons: &HashMap<u32, String>,
+    ) -> Result<usize> {
+        let season = Self::season_for_month(seasons, chrono::Utc::now().month());
+        self.conn.execute(
+            "INSERT OR IGNORE INTO historical_sales (
+                id, normalized_title, extracted_brand, extracted_sku,
+                category, condition, retail_price, cost_price,
+                sale_price, sale_date, platform, season
+            )
+            SELECT
+                cs.id,
+                COALESCE(i.normalized_title, i.raw_title),
+                i.extracted_brand,
+                i.sku_extracted,
+                i.category,
+                i.condition,
+                i.retail_price,
+                i.cost_price,
+                cs.sale_price,
+                DATE('now'),
+                cs.platform,
+                ?2
+            FROM cash_sales cs
+            JOIN inventory_items i ON i.id = cs.item_id
+            WHERE cs.id = ?1",
+            rusqlite::params![sale_id, season],
+        )
+    }
+
     pub fn get_dashboard_stats(&self) -> Result<DashboardStats> {
         let total_items: i64 =
             self.conn
                 .query_row("SELECT COUNT(*) FROM inventory_items", [], |r| r.get(0))?;
 
+        let total_quantity: i64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(quantity), 0) FROM inventory_items",
+            [],
+            |r| r.get(0),
+        )?;
+
         let in_stock: i64 = self.conn.query_row(
             "SELECT COUNT(*) FROM inventory_items WHERE current_status = 'InStock'",
             [],
@@ -899,6 +1773,7 @@ impl Database {
 
         Ok(DashboardStats {
             total_items,
+            total_quantity,
             in_stock,
             listed,
             sold,
@@ -0,0 +1,170 @@
+// Sugarland — Database Lock
+// Mutex around the shared Database that records who holds it and for how long
+
+use crate::db::Database;
+use serde::Serialize;
+use std::ops::{Deref, DerefMut};
+use std::panic::Location;
+use std::sync::{LockResult, Mutex, MutexGuard, PoisonError, TryLockError};
+use std::time::{Duration, Instant};
+
+/// Lock holds longer than this are logged at warn level
+pub const LOCK_HOLD_WARN_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// Current state of the database lock, as reported by `is_busy`
+#[derive(Debug, Serialize)]
+pub struct LockStatus {
+    pub busy: bool,
+    /// Source location (file:line) of the code holding the lock
+    pub holder: Option<String>,
+    pub held_ms: Option<u64>,
+}
+
+struct Holder {
+    location: &'static Location<'static>,
+    since: Instant,
+}
+
+/// `Mutex<Database>` that tracks the current holder and logs hold durations
+pub struct DbMutex {
+    inner: Mutex<Database>,
+    holder: Mutex<Option<Holder>>,
+}
+
+impl DbMutex {
+    pub fn new(db: Database) -> Self {
+        Self {
+            inner: Mutex::new(db),
+            holder: Mutex::new(None),
+        }
+    }
+
+    /// Lock the database. The caller's location is recorded so slow holders
+    /// can be identified in the logs and by `status`.
+    #[track_caller]
+    pub fn lock(&self) -> LockResult<DbGuard<'_>> {
+        let location = Location::caller();
+        match self.inner.lock() {
+            Ok(guard) => Ok(self.guard(guard, location)),
+            Err(poisoned) => Err(PoisonError::new(
+                self.guard(poisoned.into_inner(), location),
+            )),
+        }
+    }
+
+    /// Report whether the lock is currently held, without waiting for it
+    pub fn status(&self) -> LockStatus {
+        let busy = match self.inner.try_lock() {
+            Ok(_) => false,
+            Err(TryLockError::Poisoned(_)) => false,
+            Err(TryLockError::WouldBlock) => true,
+        };
+        if !busy {
+            return LockStatus {
+                busy,
+                holder: None,
+                held_ms: None,
+            };
+        }
+
+        let holder = self.holder.lock().unwrap_or_else(PoisonError::into_inner);
+        LockStatus {
+            busy,
+            holder: holder
+                .as_ref()
+                .map(|h| format!("{}:{}", h.location.file(), h.location.line())),
+            held_ms: holder
+                .as_ref()
+                .map(|h| h.since.elapsed().as_millis() as u64),
+        }
+    }
+
+    fn guard<'a>(
+        &'a self,
+        guard: MutexGuard<'a, Database>,
+        location: &'static Location<'static>,
+    ) -> DbGuard<'a> {
+        let since = Instant::now();
+        *self.holder.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some(Holder { location, since });
+        DbGuard {
+            guard,
+            owner: self,
+            location,
+            since,
+        }
+    }
+}
+
+/// Guard returned by `DbMutex::lock`; logs how long it was held when dropped
+pub struct DbGuard<'a> {
+    guard: MutexGuard<'a, Database>,
+    owner: &'a DbMutex,
+    location: &'static Location<'static>,
+    since: Instant,
+}
+
+impl Deref for DbGuard<'_> {
+    type Target = Database;
+
+    fn deref(&self) -> &Database {
+        &self.guard
+    }
+}
+
+impl DerefMut for DbGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Database {
+        &mut self.guard
+    }
+}
+
+impl Drop for DbGuard<'_> {
+    fn drop(&mut self) {
+        // Cleared before the inner guard is released, so the next holder's entry is kept
+        *self
+            .owner
+            .holder
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+
+        let held = self.since.elapsed();
+        if held >= LOCK_HOLD_WARN_THRESHOLD {
+            log::warn!(
+                "Database lock held for {} ms at {}:{}",
+                held.as_millis(),
+                self.location.file(),
+                self.location.line()
+            );
+        } else {
+            log::debug!(
+                "Database lock held for {} ms at {}:{}",
+                held.as_millis(),
+                self.location.file(),
+                self.location.line()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_reports_holder_while_locked() {
+        let path = std::env::temp_dir().join(format!("sugarland_lock_{}.db", uuid::Uuid::new_v4()));
+        let db = DbMutex::new(Database::new(path.to_str().unwrap()).expect("test db"));
+
+        assert!(!db.status().busy);
+
+        let guard = db.lock().expect("lock");
+        let status = db.status();
+        assert!(status.busy);
+        assert!(status.holder.expect("holder").contains("db_lock.rs"));
+        drop(guard);
+
+        let status = db.status();
+        assert!(!status.busy);
+        assert!(status.holder.is_none());
+    }
+}
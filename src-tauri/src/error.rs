@@ -0,0 +1,145 @@
+// Sugarland — Application Error
+// Structured error type returned by Tauri commands
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::error::Error;
+
+/// Command error, serialized to the frontend as `{ code, message }`
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    Validation(String),
+    #[error("{0}")]
+    Io(String),
+    #[error("{0}")]
+    Db(String),
+    #[error("{0}")]
+    Parse(String),
+}
+
+impl AppError {
+    /// Stable machine-readable code the UI can branch on
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::Validation(_) => "VALIDATION",
+            AppError::Io(_) => "IO",
+            AppError::Db(_) => "DB",
+            AppError::Parse(_) => "PARSE",
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(e: rusqlite::Error) -> Self {
+        match e {
+            rusqlite::Error::QueryReturnedNoRows => AppError::NotFound("Record not found".into()),
+            rusqlite::Error::SqliteFailure(ref failure, ref message)
+                if failure.code == rusqlite::ErrorCode::ConstraintViolation =>
+            {
+                AppError::Validation(constraint_message(message.as_deref().unwrap_or("")))
+            }
+            other => AppError::Db(other.to_string()),
+        }
+    }
+}
+
+/// Turn SQLite's "UNIQUE constraint failed: vendors.name" style messages
+/// into something a user can act on
+fn constraint_message(raw: &str) -> String {
+    let (kind, detail) = raw.split_once(" constraint failed: ").unwrap_or(("", raw));
+    match kind {
+        "UNIQUE" => format!("A record with the same {} already exists", detail),
+        "NOT NULL" => format!("{} is required", detail),
+        "CHECK" => format!("Value is out of range ({})", detail),
+        "FOREIGN KEY" => "Referenced record does not exist".to_string(),
+        _ if raw.is_empty() => "Constraint violation".to_string(),
+        _ => raw.to_string(),
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        AppError::Io(e.to_string())
+    }
+}
+
+impl From<csv::Error> for AppError {
+    fn from(e: csv::Error) -> Self {
+        match e.kind() {
+            csv::ErrorKind::Io(_) => AppError::Io(e.to_string()),
+            _ => AppError::Parse(e.to_string()),
+        }
+    }
+}
+
+/// File parsers return boxed errors; IO failures keep their own code
+impl From<Box<dyn Error>> for AppError {
+    fn from(e: Box<dyn Error>) -> Self {
+        if e.downcast_ref::<std::io::Error>().is_some() {
+            return AppError::Io(e.to_string());
+        }
+        match e.downcast::<csv::Error>() {
+            Ok(csv_error) => AppError::from(*csv_error),
+            Err(other) => AppError::Parse(other.to_string()),
+        }
+    }
+}
+
+impl<T> From<std::sync::PoisonError<T>> for AppError {
+    fn from(e: std::sync::PoisonError<T>) -> Self {
+        AppError::Db(format!("Database lock poisoned: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_code_and_message() {
+        let json = serde_json::to_value(AppError::NotFound("Item i1 not found".into())).unwrap();
+        assert_eq!(json["code"], "NOT_FOUND");
+        assert_eq!(json["message"], "Item i1 not found");
+    }
+
+    #[test]
+    fn boxed_io_errors_keep_io_code() {
+        let boxed: Box<dyn Error> = Box::new(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "missing.csv",
+        ));
+        assert_eq!(AppError::from(boxed).code(), "IO");
+
+        let boxed: Box<dyn Error> = "bad header".into();
+        assert_eq!(AppError::from(boxed).code(), "PARSE");
+    }
+
+    #[test]
+    fn constraint_violations_map_to_validation() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE v (name TEXT UNIQUE); INSERT INTO v VALUES ('Wayfair');")
+            .unwrap();
+        let err = AppError::from(
+            conn.execute("INSERT INTO v VALUES ('Wayfair')", [])
+                .unwrap_err(),
+        );
+        assert_eq!(err.code(), "VALIDATION");
+        assert_eq!(
+            err.to_string(),
+            "A record with the same v.name already exists"
+        );
+    }
+}
@@ -1,87 +1,732 @@
 // HiBid Exporter — Generate CSV for Auction Flex / HiBid import
 
 use csv::Writer;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::error::Error;
+use std::path::{Path, PathBuf};
 
 use crate::db::InventoryItemRow;
 
+/// Settings key holding the optional HiBid description template
+pub const DESCRIPTION_TEMPLATE_SETTING: &str = "hibid_description_template";
+
+/// Settings key holding the category used when an item has none
+pub const DEFAULT_CATEGORY_SETTING: &str = "default_category";
+
+/// Category used when the `default_category` setting is missing or blank
+pub const FALLBACK_CATEGORY: &str = "General Merchandise";
+
+/// Settings key enabling the MinBid (hidden reserve) export column ("1" = on)
+pub const MIN_BID_COLUMN_SETTING: &str = "hibid_export_min_bid";
+
+/// Settings key holding the StartBid fraction of min price used with MinBid
+pub const START_BID_FRACTION_SETTING: &str = "hibid_start_bid_fraction";
+
+/// Settings key selecting how StartBid is derived ("min_price" or "cost")
+pub const START_BID_STRATEGY_SETTING: &str = "hibid_start_bid_strategy";
+
+/// Settings key holding the StartBid fraction of cost for the "cost" strategy
+pub const START_BID_COST_FRACTION_SETTING: &str = "hibid_start_bid_cost_fraction";
+
+/// Settings key holding the lowest StartBid a cost-based opening may use
+pub const MIN_START_BID_SETTING: &str = "hibid_min_start_bid";
+
+/// Settings key selecting how the Lead is composed ("raw" or "structured")
+pub const LEAD_STRATEGY_SETTING: &str = "hibid_lead_strategy";
+
+/// Settings key holding the base URL of hosted lot photos (e.g. a CDN folder)
+pub const IMAGE_URL_BASE_SETTING: &str = "image_url_base";
+
+/// Settings key holding bid increment ladders: JSON
+/// `{"default": [...], "categories": {"Jewelry": [{"up_to": 100, "increment": 2}, {"increment": 10}]}}`
+pub const BID_INCREMENT_LADDERS_SETTING: &str = "hibid_bid_increment_ladders";
+
+/// Settings key selecting the decimal separator of exported numbers ("dot" or "comma")
+pub const NUMBER_LOCALE_SETTING: &str = "export_number_locale";
+
+/// Settings key holding the export column layout: JSON list of
+/// `{"field": "LotNum", "header": "Lot #"}`; empty means the standard layout
+pub const EXPORT_COLUMNS_SETTING: &str = "hibid_export_columns";
+
+const DEFAULT_START_BID_FRACTION: f64 = 0.5;
+const DEFAULT_START_BID_COST_FRACTION: f64 = 0.3;
+const DEFAULT_MIN_START_BID: f64 = 1.0;
+const DEFAULT_BID_INCREMENT: f64 = 5.0;
+const LEAD_MAX_CHARS: usize = 50;
+
+/// How the StartBid of an exported lot is derived
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StartBidStrategy {
+    /// StartBid is the min price, or `start_bid_fraction` of it when MinBid is exported
+    MinPrice,
+    /// No-reserve style opening at cost × fraction, with min price reported in MinBid
+    FractionOfCost(f64),
+}
+
+/// How the Lead (short title) of an exported lot is composed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LeadStrategy {
+    /// First 50 characters of the raw title
+    #[default]
+    RawTruncated,
+    /// "Brand Model 65\" Category" from extracted fields, capped at 50 characters;
+    /// items with neither brand nor model fall back to the raw title
+    Structured,
+}
+
+impl LeadStrategy {
+    /// Compose the Lead for an item
+    pub fn lead_for(self, item: &InventoryItemRow) -> String {
+        match self {
+            LeadStrategy::RawTruncated => item.raw_title.chars().take(LEAD_MAX_CHARS).collect(),
+            LeadStrategy::Structured => {
+                structured_lead(item).unwrap_or_else(|| LeadStrategy::RawTruncated.lead_for(item))
+            }
+        }
+    }
+}
+
+/// Brand + model + key spec (screen size or capacity) + category, joined
+/// word by word until the next part would pass the cap
+fn structured_lead(item: &InventoryItemRow) -> Option<String> {
+    let non_blank = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let brand = non_blank(&item.extracted_brand);
+    let model = non_blank(&item.extracted_model);
+    if brand.is_none() && model.is_none() {
+        return None;
+    }
+    let spec = crate::nlp::extract_screen_size(&item.raw_title)
+        .map(|size| format!("{}\"", size))
+        .or_else(|| {
+            crate::nlp::extract_capacity(&item.raw_title).map(|cap| format!("{} cu ft", cap))
+        });
+
+    let mut lead = String::new();
+    let parts = [brand, model, spec, non_blank(&item.category)];
+    for word in parts
+        .iter()
+        .flatten()
+        .flat_map(|part| part.split_whitespace())
+    {
+        let needed = word.chars().count() + usize::from(!lead.is_empty());
+        if lead.chars().count() + needed > LEAD_MAX_CHARS {
+            break;
+        }
+        if !lead.is_empty() {
+            lead.push(' ');
+        }
+        lead.push_str(word);
+    }
+    Some(lead).filter(|lead| !lead.is_empty())
+}
+
+/// Decimal separator used for numbers in exported CSVs
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+pub enum NumberLocale {
+    /// 1234.50
+    #[default]
+    Dot,
+    /// 1234,50 for partners whose imports expect comma decimals
+    Comma,
+}
+
+impl NumberLocale {
+    /// Format `value` with `decimals` places using this locale's separator
+    pub fn format(self, value: f64, decimals: usize) -> String {
+        let formatted = format!("{:.*}", decimals, value);
+        match self {
+            NumberLocale::Dot => formatted,
+            NumberLocale::Comma => formatted.replace('.', ","),
+        }
+    }
+}
+
+/// Read the `export_number_locale` setting; anything but "comma" keeps dots
+pub fn load_number_locale(conn: &Connection) -> NumberLocale {
+    match load_setting(conn, NUMBER_LOCALE_SETTING) {
+        Some(value) if value.trim().eq_ignore_ascii_case("comma") => NumberLocale::Comma,
+        _ => NumberLocale::Dot,
+    }
+}
+
+/// Value an export column is filled from
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum ExportField {
+    LotNum,
+    Lead,
+    Description,
+    StartBid,
+    MinBid,
+    BidIncrement,
+    Images,
+    Category,
+    Title,
+    RetailPrice,
+    Quantity,
+    Condition,
+    Brand,
+    Model,
+}
+
+impl ExportField {
+    /// Header used when a column doesn't set its own
+    pub fn default_header(self) -> &'static str {
+        match self {
+            ExportField::LotNum => "LotNum",
+            ExportField::Lead => "Lead",
+            ExportField::Description => "Description",
+            ExportField::StartBid => "StartBid",
+            ExportField::MinBid => "MinBid",
+            ExportField::BidIncrement => "BidIncrement",
+            ExportField::Images => "Images",
+            ExportField::Category => "Category",
+            ExportField::Title => "Title",
+            ExportField::RetailPrice => "RetailPrice",
+            ExportField::Quantity => "Quantity",
+            ExportField::Condition => "Condition",
+            ExportField::Brand => "Brand",
+            ExportField::Model => "Model",
+        }
+    }
+}
+
+/// One rung of an increment ladder: `increment` applies while the opening bid
+/// is below `up_to`; the rung without `up_to` covers everything above
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct IncrementStep {
+    #[serde(default)]
+    pub up_to: Option<f64>,
+    pub increment: f64,
+}
+
+/// Bid increment ladders, per category with a default ladder for the rest
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BidIncrementLadders {
+    /// Ladder for uncategorised lots; `None` keeps the flat $5 increment
+    #[serde(default)]
+    pub default: Option<Vec<IncrementStep>>,
+    /// Ladders keyed by item category (matched case-insensitively)
+    #[serde(default)]
+    pub categories: HashMap<String, Vec<IncrementStep>>,
+}
+
+impl BidIncrementLadders {
+    /// Increment for a lot of `category` opening at `start_bid`
+    pub fn increment_for(&self, category: &str, start_bid: f64) -> f64 {
+        let ladder = self
+            .categories
+            .iter()
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case(category.trim()))
+            .map(|(_, ladder)| ladder)
+            .or(self.default.as_ref());
+        let Some(ladder) = ladder.filter(|ladder| !ladder.is_empty()) else {
+            return DEFAULT_BID_INCREMENT;
+        };
+        ladder
+            .iter()
+            .find(|step| !step.up_to.is_some_and(|up_to| start_bid >= up_to))
+            .or(ladder.last())
+            .map(|step| step.increment)
+            .unwrap_or(DEFAULT_BID_INCREMENT)
+    }
+
+    fn has_invalid_step(&self) -> bool {
+        self.default
+            .iter()
+            .chain(self.categories.values())
+            .flatten()
+            .any(|step| step.increment <= 0.0 || step.up_to.is_some_and(|up_to| up_to <= 0.0))
+    }
+}
+
+/// One output column: the field it carries and the header it is written under
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ExportColumn {
+    pub field: ExportField,
+    #[serde(default)]
+    pub header: Option<String>,
+}
+
+impl ExportColumn {
+    pub fn header(&self) -> &str {
+        self.header
+            .as_deref()
+            .map(str::trim)
+            .filter(|h| !h.is_empty())
+            .unwrap_or(self.field.default_header())
+    }
+}
+
+/// Settings-driven options for a HiBid export
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub description_template: Option<String>,
+    pub default_category: String,
+    /// Write a MinBid column with the min price and open StartBid lower
+    pub include_min_bid: bool,
+    /// StartBid as a fraction of min price when `include_min_bid` is set
+    pub start_bid_fraction: f64,
+    /// Auction lot prefix prepended to LotNum in the file only (e.g. "W34" → "W34-042")
+    pub lot_prefix: Option<String>,
+    pub start_bid_strategy: StartBidStrategy,
+    /// Floor for cost-based StartBids
+    pub min_start_bid: f64,
+    /// Photo directory; when set, Images lists the lot's actual `{lot}-N.jpg` files
+    pub image_dir: Option<PathBuf>,
+    /// Custom column layout; `None` writes the standard HiBid columns
+    pub columns: Option<Vec<ExportColumn>>,
+    /// When set, Images holds `{base}/{file}` URLs instead of bare file names
+    pub image_url_base: Option<String>,
+    /// BidIncrement ladders picked by item category
+    pub bid_increments: BidIncrementLadders,
+    /// Decimal separator for StartBid, MinBid, BidIncrement and RetailPrice
+    pub number_locale: NumberLocale,
+    pub lead_strategy: LeadStrategy,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            description_template: None,
+            default_category: FALLBACK_CATEGORY.to_string(),
+            include_min_bid: false,
+            start_bid_fraction: DEFAULT_START_BID_FRACTION,
+            lot_prefix: None,
+            start_bid_strategy: StartBidStrategy::MinPrice,
+            min_start_bid: DEFAULT_MIN_START_BID,
+            image_dir: None,
+            columns: None,
+            image_url_base: None,
+            bid_increments: BidIncrementLadders::default(),
+            number_locale: NumberLocale::Dot,
+            lead_strategy: LeadStrategy::RawTruncated,
+        }
+    }
+}
+
+impl ExportOptions {
+    /// Load export options from the settings table
+    pub fn load(conn: &Connection) -> Self {
+        Self {
+            description_template: load_description_template(conn),
+            default_category: load_default_category(conn),
+            include_min_bid: load_setting(conn, MIN_BID_COLUMN_SETTING).as_deref() == Some("1"),
+            start_bid_fraction: load_setting(conn, START_BID_FRACTION_SETTING)
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|f| *f > 0.0 && *f <= 1.0)
+                .unwrap_or(DEFAULT_START_BID_FRACTION),
+            lot_prefix: None,
+            start_bid_strategy: match load_setting(conn, START_BID_STRATEGY_SETTING).as_deref() {
+                Some("cost") => StartBidStrategy::FractionOfCost(
+                    load_setting(conn, START_BID_COST_FRACTION_SETTING)
+                        .and_then(|v| v.trim().parse::<f64>().ok())
+                        .filter(|f| *f > 0.0)
+                        .unwrap_or(DEFAULT_START_BID_COST_FRACTION),
+                ),
+                _ => StartBidStrategy::MinPrice,
+            },
+            min_start_bid: load_setting(conn, MIN_START_BID_SETTING)
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|v| *v >= 0.0)
+                .unwrap_or(DEFAULT_MIN_START_BID),
+            image_dir: None,
+            columns: load_export_columns(conn),
+            image_url_base: load_setting(conn, IMAGE_URL_BASE_SETTING)
+                .filter(|base| !base.trim().is_empty())
+                .and_then(|base| match validate_image_url_base(&base) {
+                    Ok(base) => Some(base),
+                    Err(e) => {
+                        log::warn!("Ignoring {} setting: {}", IMAGE_URL_BASE_SETTING, e);
+                        None
+                    }
+                }),
+            bid_increments: load_bid_increment_ladders(conn),
+            number_locale: load_number_locale(conn),
+            lead_strategy: match load_setting(conn, LEAD_STRATEGY_SETTING) {
+                Some(value) if value.trim().eq_ignore_ascii_case("structured") => {
+                    LeadStrategy::Structured
+                }
+                _ => LeadStrategy::RawTruncated,
+            },
+        }
+    }
+
+    /// Whether the export carries a MinBid reserve column
+    pub fn writes_min_bid(&self) -> bool {
+        self.include_min_bid
+            || matches!(self.start_bid_strategy, StartBidStrategy::FractionOfCost(_))
+    }
+
+    /// Columns to write: the configured layout, else LotNum, Lead, Description,
+    /// StartBid, [MinBid], BidIncrement, Images, Category
+    pub fn export_columns(&self) -> Vec<ExportColumn> {
+        if let Some(columns) = &self.columns {
+            return columns.clone();
+        }
+        let mut fields = vec![
+            ExportField::LotNum,
+            ExportField::Lead,
+            ExportField::Description,
+            ExportField::StartBid,
+        ];
+        if self.writes_min_bid() {
+            fields.push(ExportField::MinBid);
+        }
+        fields.extend([
+            ExportField::BidIncrement,
+            ExportField::Images,
+            ExportField::Category,
+        ]);
+        fields
+            .into_iter()
+            .map(|field| ExportColumn {
+                field,
+                header: None,
+            })
+            .collect()
+    }
+}
+
+/// Check an image URL base is an absolute http(s) URL with a host, and return it
+/// without trailing slashes so file names can be appended with a single "/"
+pub fn validate_image_url_base(base: &str) -> Result<String, String> {
+    let base = base.trim();
+    let url =
+        tauri::Url::parse(base).map_err(|e| format!("Invalid image URL base {:?}: {}", base, e))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(format!(
+            "Image URL base must be an http(s) URL with a host, got {:?}",
+            base
+        ));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(format!(
+            "Image URL base cannot have a query or fragment, got {:?}",
+            base
+        ));
+    }
+    Ok(base.trim_end_matches('/').to_string())
+}
+
+/// Parse the `hibid_export_columns` setting. Blank, empty or invalid layouts
+/// fall back to the standard columns.
+fn load_export_columns(conn: &Connection) -> Option<Vec<ExportColumn>> {
+    let raw = load_setting(conn, EXPORT_COLUMNS_SETTING)?;
+    if raw.trim().is_empty() {
+        return None;
+    }
+    match serde_json::from_str::<Vec<ExportColumn>>(&raw) {
+        Ok(columns) if !columns.is_empty() => Some(columns),
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!("Ignoring invalid {} setting: {}", EXPORT_COLUMNS_SETTING, e);
+            None
+        }
+    }
+}
+
+/// Parse the `hibid_bid_increment_ladders` setting. Blank or invalid settings,
+/// including ladders with non-positive steps, keep the flat default increment.
+fn load_bid_increment_ladders(conn: &Connection) -> BidIncrementLadders {
+    let Some(raw) = load_setting(conn, BID_INCREMENT_LADDERS_SETTING) else {
+        return BidIncrementLadders::default();
+    };
+    if raw.trim().is_empty() {
+        return BidIncrementLadders::default();
+    }
+    match serde_json::from_str::<BidIncrementLadders>(&raw) {
+        Ok(ladders) if ladders.has_invalid_step() => {
+            log::warn!(
+                "Ignoring {} setting: increments and up_to bounds must be positive",
+                BID_INCREMENT_LADDERS_SETTING
+            );
+            BidIncrementLadders::default()
+        }
+        Ok(ladders) => ladders,
+        Err(e) => {
+            log::warn!(
+                "Ignoring invalid {} setting: {}",
+                BID_INCREMENT_LADDERS_SETTING,
+                e
+            );
+            BidIncrementLadders::default()
+        }
+    }
+}
+
+fn load_setting(conn: &Connection, key: &str) -> Option<String> {
+    conn.query_row(
+        "SELECT value FROM settings WHERE key = ?1",
+        rusqlite::params![key],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+}
+
 #[derive(Debug)]
 pub struct HiBidLot {
     pub lot_num: String,
     pub lead: String,
     pub description: String,
     pub start_bid: f64,
+    pub min_bid: Option<f64>,
+    pub bid_increment: f64,
     pub images: String,
     pub category: String,
 }
 
 impl HiBidLot {
+    /// Text written for `field` in this lot's export row
+    pub fn field_value(
+        &self,
+        field: ExportField,
+        item: &InventoryItemRow,
+        options: &ExportOptions,
+    ) -> String {
+        match field {
+            ExportField::LotNum => apply_lot_prefix(options.lot_prefix.as_deref(), &self.lot_num),
+            ExportField::Lead => self.lead.clone(),
+            ExportField::Description => self.description.clone(),
+            ExportField::StartBid => options.number_locale.format(self.start_bid, 2),
+            ExportField::MinBid => self
+                .min_bid
+                .map(|bid| options.number_locale.format(bid, 2))
+                .unwrap_or_default(),
+            // Whole-dollar steps stay "5" rather than "5.00"
+            ExportField::BidIncrement if self.bid_increment.fract() == 0.0 => {
+                options.number_locale.format(self.bid_increment, 0)
+            }
+            ExportField::BidIncrement => options.number_locale.format(self.bid_increment, 2),
+            ExportField::Images => self.images.clone(),
+            ExportField::Category => self.category.clone(),
+            ExportField::Title => item.raw_title.clone(),
+            ExportField::RetailPrice => options.number_locale.format(item.retail_price, 2),
+            ExportField::Quantity => item.quantity.to_string(),
+            ExportField::Condition => item.condition.clone().unwrap_or_default(),
+            ExportField::Brand => item.extracted_brand.clone().unwrap_or_default(),
+            ExportField::Model => item.extracted_model.clone().unwrap_or_default(),
+        }
+    }
+
     /// Convert an inventory item to a HiBid lot format
     pub fn from_inventory_item(item: &InventoryItemRow) -> Self {
+        Self::from_inventory_item_with_options(item, &ExportOptions::default())
+    }
+
+    /// Convert an inventory item to a HiBid lot using the given export options
+    pub fn from_inventory_item_with_options(
+        item: &InventoryItemRow,
+        options: &ExportOptions,
+    ) -> Self {
         let lot_num = item.lot_number.as_deref().unwrap_or("0").to_string();
 
-        // Lead: short title (first 50 characters)
-        let lead: String = item.raw_title.chars().take(50).collect();
+        // Lead: short title, raw or assembled from extracted fields
+        let lead = options.lead_strategy.lead_for(item);
 
-        // Description: full title + retail info
-        let condition = item.condition.as_deref().unwrap_or("Unknown");
-        let description = format!(
-            "{}. Retail Value: ${:.2}. Condition: {}. Quantity: {}.",
-            item.raw_title, item.retail_price, condition, item.quantity
-        );
+        // Description: template if configured, otherwise full title + retail info
+        let description = match options.description_template.as_deref() {
+            // Keep the condition visible even when the template leaves it out
+            Some(template) => match item.condition.as_deref() {
+                Some(condition) if !template.contains("{condition}") => format!(
+                    "{} Condition: {}.",
+                    render_description(template, item).trim_end(),
+                    condition
+                ),
+                _ => render_description(template, item),
+            },
+            None => {
+                let condition = item.condition.as_deref().unwrap_or("Unknown");
+                format!(
+                    "{}. Retail Value: ${:.2}. Condition: {}. Quantity: {}.",
+                    item.raw_title, item.retail_price, condition, item.quantity
+                )
+            }
+        };
 
-        // Images: LotNum-1.jpg, LotNum-2.jpg
-        let images = format!("{}-1.jpg,{}-2.jpg", lot_num, lot_num);
+        // Staff notes ("missing remote") go last unless the template placed them
+        let notes = item.notes.as_deref().map(str::trim).unwrap_or("");
+        let template_has_notes = options
+            .description_template
+            .as_deref()
+            .is_some_and(|template| template.contains("{notes}"));
+        let description = if notes.is_empty() || template_has_notes {
+            description
+        } else {
+            format!(
+                "{} Notes: {}.",
+                description.trim_end(),
+                notes.trim_end_matches('.')
+            )
+        };
+
+        // Images: the lot's photos in numeric order, else LotNum-1.jpg, LotNum-2.jpg
+        let found = options
+            .image_dir
+            .as_deref()
+            .map(|dir| collect_lot_images(dir, &lot_num))
+            .unwrap_or_default();
+        let files = if found.is_empty() {
+            vec![format!("{}-1.jpg", lot_num), format!("{}-2.jpg", lot_num)]
+        } else {
+            found
+        };
+        let images = match options.image_url_base.as_deref() {
+            Some(base) => files
+                .iter()
+                .map(|file| format!("{}/{}", base, file))
+                .collect::<Vec<_>>()
+                .join(","),
+            None => files.join(","),
+        };
 
         // Category based on extracted data or default
         let category = item
             .category
             .as_deref()
-            .unwrap_or("General Merchandise")
+            .unwrap_or(&options.default_category)
             .to_string();
 
+        // Start bid: min price, or a fraction of min price/cost when the reserve goes in MinBid
+        let (start_bid, min_bid) = match options.start_bid_strategy {
+            StartBidStrategy::FractionOfCost(fraction) => {
+                let opening = (item.cost_price * fraction * 100.0).round() / 100.0;
+                (opening.max(options.min_start_bid), Some(item.min_price))
+            }
+            StartBidStrategy::MinPrice if options.include_min_bid => {
+                let opening = (item.min_price * options.start_bid_fraction * 100.0).round() / 100.0;
+                (opening, Some(item.min_price))
+            }
+            StartBidStrategy::MinPrice => (item.min_price, None),
+        };
+
+        let bid_increment = options.bid_increments.increment_for(&category, start_bid);
+
         Self {
             lot_num,
             lead,
             description,
-            start_bid: item.min_price,
+            start_bid,
+            min_bid,
+            bid_increment,
             images,
             category,
         }
     }
 }
 
+/// Render a description template for an item.
+///
+/// Supported placeholders: `{title}`, `{retail}`, `{condition}`, `{quantity}`,
+/// `{brand}`, `{model}`, `{notes}`. Missing values render as an empty string.
+pub fn render_description(template: &str, item: &InventoryItemRow) -> String {
+    template
+        .replace("{title}", &item.raw_title)
+        .replace("{retail}", &format!("{:.2}", item.retail_price))
+        .replace(
+            "{condition}",
+            item.condition.as_deref().unwrap_or("Unknown"),
+        )
+        .replace("{quantity}", &item.quantity.to_string())
+        .replace("{brand}", item.extracted_brand.as_deref().unwrap_or(""))
+        .replace("{model}", item.extracted_model.as_deref().unwrap_or(""))
+        .replace("{notes}", item.notes.as_deref().unwrap_or("").trim())
+}
+
+/// File names of `{lot}-N.jpg` photos in `dir`, ordered by N numerically
+/// (1, 2, 10) with one file per N. Matching ignores case; an unreadable
+/// directory yields no images.
+pub fn collect_lot_images(dir: &Path, lot: &str) -> Vec<String> {
+    let prefix = format!("{}-", lot.trim().to_lowercase());
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut images: Vec<(u32, String)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter_map(|name| {
+            let lower = name.to_lowercase();
+            let index = lower
+                .strip_prefix(&prefix)?
+                .strip_suffix(".jpg")?
+                .parse::<u32>()
+                .ok()?;
+            Some((index, name))
+        })
+        .collect();
+    images.sort();
+    // "42-1.jpg" and "42-01.JPG" are the same photo slot
+    images.dedup_by_key(|(index, _)| *index);
+    images.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Exported lot number: `{prefix}-{lot}`, or the lot unchanged without a prefix
+pub fn apply_lot_prefix(prefix: Option<&str>, lot_number: &str) -> String {
+    match prefix.map(|p| p.trim().trim_end_matches('-')) {
+        Some(p) if !p.is_empty() => format!("{}-{}", p, lot_number),
+        _ => lot_number.to_string(),
+    }
+}
+
+/// Inverse of `apply_lot_prefix` for lot numbers read back from HiBid results.
+/// Lots that don't carry the prefix are returned trimmed but otherwise unchanged.
+pub fn strip_lot_prefix(prefix: Option<&str>, lot_number: &str) -> String {
+    let lot_number = lot_number.trim();
+    let prefix = match prefix.map(|p| p.trim().trim_end_matches('-')) {
+        Some(p) if !p.is_empty() => p,
+        _ => return lot_number.to_string(),
+    };
+    let stripped = lot_number
+        .get(..prefix.len())
+        .filter(|head| head.eq_ignore_ascii_case(prefix))
+        .map(|_| &lot_number[prefix.len()..])
+        .and_then(|rest| rest.strip_prefix('-'))
+        .filter(|rest| !rest.is_empty());
+    stripped.unwrap_or(lot_number).to_string()
+}
+
+/// Load the description template from settings; blank or missing means "use the default format"
+pub fn load_description_template(conn: &Connection) -> Option<String> {
+    load_setting(conn, DESCRIPTION_TEMPLATE_SETTING).filter(|v| !v.trim().is_empty())
+}
+
+/// Load the default category from settings, falling back to "General Merchandise"
+pub fn load_default_category(conn: &Connection) -> String {
+    load_setting(conn, DEFAULT_CATEGORY_SETTING)
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| FALLBACK_CATEGORY.to_string())
+}
+
 /// Export a list of inventory items to a HiBid-compatible CSV file
 pub fn export_to_hibid_csv(
     items: &[InventoryItemRow],
     output_path: &str,
+    options: &ExportOptions,
 ) -> Result<usize, Box<dyn Error>> {
     let mut wtr = Writer::from_path(output_path)?;
 
     // Write header
-    wtr.write_record([
-        "LotNum",
-        "Lead",
-        "Description",
-        "StartBid",
-        "BidIncrement",
-        "Images",
-        "Category",
-    ])?;
+    let columns = options.export_columns();
+    wtr.write_record(columns.iter().map(ExportColumn::header))?;
 
     let mut count = 0;
     for item in items {
-        let lot = HiBidLot::from_inventory_item(item);
-
-        wtr.write_record([
-            &lot.lot_num,
-            &lot.lead,
-            &lot.description,
-            &format!("{:.2}", lot.start_bid),
-            "5", // default bid increment
-            &lot.images,
-            &lot.category,
-        ])?;
+        let lot = HiBidLot::from_inventory_item_with_options(item, options);
+        let record: Vec<String> = columns
+            .iter()
+            .map(|column| lot.field_value(column.field, item, options))
+            .collect();
+        wtr.write_record(&record)?;
 
         count += 1;
     }
@@ -90,6 +735,59 @@ pub fn export_to_hibid_csv(
     Ok(count)
 }
 
+/// One file written by `export_to_hibid_csv_parts`
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportPart {
+    pub path: String,
+    pub lots: usize,
+}
+
+/// `{base}-part{n}.csv` next to `output_path`, keeping its extension
+pub fn part_path(output_path: &str, part: usize) -> String {
+    let path = Path::new(output_path);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let file_name = match path.extension() {
+        Some(ext) => format!("{}-part{}.{}", stem, part, ext.to_string_lossy()),
+        None => format!("{}-part{}.csv", stem, part),
+    };
+    path.with_file_name(file_name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Export in files of at most `lots_per_file` lots each, every file with its
+/// own header, to stay under HiBid's practical upload size. `None` (or a
+/// catalog that fits in one file) writes `output_path` alone, as before.
+pub fn export_to_hibid_csv_parts(
+    items: &[InventoryItemRow],
+    output_path: &str,
+    options: &ExportOptions,
+    lots_per_file: Option<usize>,
+) -> Result<Vec<ExportPart>, Box<dyn Error>> {
+    let chunk_size = match lots_per_file {
+        Some(0) => return Err("Lots per file must be at least 1".into()),
+        Some(size) if items.len() > size => size,
+        _ => {
+            let lots = export_to_hibid_csv(items, output_path, options)?;
+            return Ok(vec![ExportPart {
+                path: output_path.to_string(),
+                lots,
+            }]);
+        }
+    };
+
+    let mut parts = Vec::new();
+    for (index, chunk) in items.chunks(chunk_size).enumerate() {
+        let path = part_path(output_path, index + 1);
+        let lots = export_to_hibid_csv(chunk, &path, options)?;
+        parts.push(ExportPart { path, lots });
+    }
+    Ok(parts)
+}
+
 mod tests {
     use super::*;
 
@@ -120,6 +818,12 @@ mod tests {
             buybacker_id: None,
             created_at: "2026-02-10".to_string(),
             updated_at: "2026-02-10".to_string(),
+            notes: None,
+            gross_margin_pct: None,
+            is_flagged: false,
+            flag_reason: None,
+            needs_title: false,
+            location: None,
         }
     }
 
@@ -135,4 +839,340 @@ mod tests {
         assert_eq!(lot.images, "42m-1.jpg,42m-2.jpg");
         assert_eq!(lot.category, "TVs & Electronics");
     }
+
+    #[test]
+    fn test_structured_lead_strategy() {
+        let mut item = mock_item();
+        item.extracted_model = Some("UN65TU7000".to_string());
+        let options = ExportOptions {
+            lead_strategy: LeadStrategy::Structured,
+            ..ExportOptions::default()
+        };
+        let lot = HiBidLot::from_inventory_item_with_options(&item, &options);
+        assert_eq!(lot.lead, "Samsung UN65TU7000 65\" TVs & Electronics");
+
+        // Capped on a word boundary
+        item.category = Some("Televisions, Home Theater and Streaming Devices".to_string());
+        let lead = LeadStrategy::Structured.lead_for(&item);
+        assert!(lead.chars().count() <= 50);
+        assert_eq!(lead, "Samsung UN65TU7000 65\" Televisions, Home Theater");
+
+        // No brand or model: raw title as before
+        item.extracted_brand = None;
+        item.extracted_model = None;
+        assert_eq!(LeadStrategy::Structured.lead_for(&item), item.raw_title);
+    }
+
+    #[test]
+    fn test_hibid_lot_uses_description_template() {
+        let item = mock_item();
+        let options = ExportOptions {
+            description_template: Some(
+                "{brand} {model} - {title} | Retail ${retail} | {condition} x{quantity}"
+                    .to_string(),
+            ),
+            ..ExportOptions::default()
+        };
+        let lot = HiBidLot::from_inventory_item_with_options(&item, &options);
+
+        assert_eq!(
+            lot.description,
+            "Samsung  - Samsung 65\" Class 4K UHD Smart TV | Retail $549.99 | New x1"
+        );
+    }
+
+    #[test]
+    fn test_notes_appended_to_description() {
+        let mut item = mock_item();
+        item.notes = Some(" Missing remote. ".to_string());
+        let lot = HiBidLot::from_inventory_item(&item);
+        assert!(lot
+            .description
+            .ends_with("Quantity: 1. Notes: Missing remote."));
+
+        let options = ExportOptions {
+            description_template: Some("{title} ({notes})".to_string()),
+            ..ExportOptions::default()
+        };
+        let lot = HiBidLot::from_inventory_item_with_options(&item, &options);
+        assert_eq!(
+            lot.description,
+            "Samsung 65\" Class 4K UHD Smart TV (Missing remote.) Condition: New."
+        );
+    }
+
+    #[test]
+    fn test_template_without_condition_still_shows_condition() {
+        let mut item = mock_item();
+        item.condition = Some("New - Open box".to_string());
+        let options = ExportOptions {
+            description_template: Some("{title}".to_string()),
+            ..ExportOptions::default()
+        };
+        let lot = HiBidLot::from_inventory_item_with_options(&item, &options);
+
+        assert_eq!(
+            lot.description,
+            "Samsung 65\" Class 4K UHD Smart TV Condition: New - Open box."
+        );
+    }
+
+    #[test]
+    fn test_hibid_lot_uses_configured_default_category() {
+        let mut item = mock_item();
+        item.category = None;
+
+        let lot = HiBidLot::from_inventory_item(&item);
+        assert_eq!(lot.category, "General Merchandise");
+
+        let options = ExportOptions {
+            default_category: "Liquidation Lot".to_string(),
+            ..ExportOptions::default()
+        };
+        let lot = HiBidLot::from_inventory_item_with_options(&item, &options);
+        assert_eq!(lot.category, "Liquidation Lot");
+    }
+
+    #[test]
+    fn test_export_writes_min_bid_column_when_enabled() {
+        let item = mock_item();
+        let options = ExportOptions {
+            include_min_bid: true,
+            start_bid_fraction: 0.25,
+            ..ExportOptions::default()
+        };
+        let lot = HiBidLot::from_inventory_item_with_options(&item, &options);
+        assert_eq!(lot.start_bid, 33.0);
+        assert_eq!(lot.min_bid, Some(132.0));
+
+        let path = std::env::temp_dir().join(format!("hibid_min_bid_{}.csv", uuid::Uuid::new_v4()));
+        export_to_hibid_csv(&[item], path.to_str().unwrap(), &options).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "LotNum,Lead,Description,StartBid,MinBid,BidIncrement,Images,Category"
+        );
+        assert!(lines.next().unwrap().contains(",33.00,132.00,5,"));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_export_uses_configured_columns() {
+        let item = mock_item();
+        let options = ExportOptions {
+            columns: Some(
+                serde_json::from_str(
+                    r#"[{"field": "LotNum", "header": "Lot #"}, {"field": "RetailPrice"}, {"field": "Lead", "header": "Title"}]"#,
+                )
+                .unwrap(),
+            ),
+            ..ExportOptions::default()
+        };
+
+        let path = std::env::temp_dir().join(format!("hibid_columns_{}.csv", uuid::Uuid::new_v4()));
+        export_to_hibid_csv(&[item], path.to_str().unwrap(), &options).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next().unwrap(), "Lot #,RetailPrice,Title");
+        assert!(lines.next().unwrap().starts_with("42m,549.99,\"Samsung 65"));
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(
+            ExportOptions::default()
+                .export_columns()
+                .iter()
+                .map(ExportColumn::header)
+                .collect::<Vec<_>>(),
+            [
+                "LotNum",
+                "Lead",
+                "Description",
+                "StartBid",
+                "BidIncrement",
+                "Images",
+                "Category"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_category_bid_increment_ladders() {
+        let ladders: BidIncrementLadders = serde_json::from_str(
+            r#"{"categories": {"jewelry": [{"up_to": 100, "increment": 2}, {"increment": 10}]}}"#,
+        )
+        .unwrap();
+        assert_eq!(ladders.increment_for("Jewelry", 50.0), 2.0);
+        assert_eq!(ladders.increment_for("Jewelry", 100.0), 10.0);
+        // Other categories keep the flat default until a default ladder is set
+        assert_eq!(ladders.increment_for("Appliances", 50.0), 5.0);
+
+        let mut item = mock_item();
+        item.category = Some("Jewelry".to_string());
+        let options = ExportOptions {
+            bid_increments: ladders,
+            ..ExportOptions::default()
+        };
+        let lot = HiBidLot::from_inventory_item_with_options(&item, &options);
+        assert_eq!(
+            lot.field_value(ExportField::BidIncrement, &item, &options),
+            "10"
+        );
+
+        let lot = HiBidLot::from_inventory_item(&mock_item());
+        assert_eq!(
+            lot.field_value(
+                ExportField::BidIncrement,
+                &mock_item(),
+                &ExportOptions::default()
+            ),
+            "5"
+        );
+    }
+
+    #[test]
+    fn test_comma_number_locale() {
+        let options = ExportOptions {
+            include_min_bid: true,
+            number_locale: NumberLocale::Comma,
+            ..ExportOptions::default()
+        };
+        let item = mock_item();
+        let lot = HiBidLot::from_inventory_item_with_options(&item, &options);
+        assert_eq!(
+            lot.field_value(ExportField::StartBid, &item, &options),
+            "66,00"
+        );
+        assert_eq!(
+            lot.field_value(ExportField::MinBid, &item, &options),
+            "132,00"
+        );
+        assert_eq!(
+            lot.field_value(ExportField::RetailPrice, &item, &options),
+            "549,99"
+        );
+        assert_eq!(
+            lot.field_value(ExportField::BidIncrement, &item, &options),
+            "5"
+        );
+        assert_eq!(NumberLocale::Dot.format(549.99, 2), "549.99");
+    }
+
+    #[test]
+    fn test_image_url_base() {
+        assert_eq!(
+            validate_image_url_base(" https://cdn.example.com/lots/ ").unwrap(),
+            "https://cdn.example.com/lots"
+        );
+        assert!(validate_image_url_base("cdn.example.com/lots").is_err());
+        assert!(validate_image_url_base("ftp://cdn.example.com").is_err());
+        assert!(validate_image_url_base("https://cdn.example.com/?v=1").is_err());
+
+        let options = ExportOptions {
+            image_url_base: Some("https://cdn.example.com/lots".to_string()),
+            ..ExportOptions::default()
+        };
+        let lot = HiBidLot::from_inventory_item_with_options(&mock_item(), &options);
+        assert_eq!(
+            lot.images,
+            "https://cdn.example.com/lots/42m-1.jpg,https://cdn.example.com/lots/42m-2.jpg"
+        );
+    }
+
+    #[test]
+    fn test_lot_prefix_round_trip() {
+        assert_eq!(apply_lot_prefix(Some("W34"), "042"), "W34-042");
+        assert_eq!(apply_lot_prefix(Some("W34-"), "042"), "W34-042");
+        assert_eq!(apply_lot_prefix(Some(" "), "042"), "042");
+        assert_eq!(apply_lot_prefix(None, "042"), "042");
+
+        assert_eq!(strip_lot_prefix(Some("W34"), "w34-042"), "042");
+        assert_eq!(strip_lot_prefix(Some("W34"), " 042 "), "042");
+        assert_eq!(strip_lot_prefix(Some("W3"), "W34-042"), "W34-042");
+        assert_eq!(strip_lot_prefix(None, "W34-042"), "W34-042");
+    }
+
+    #[test]
+    fn test_cost_fraction_start_bid_keeps_reserve_and_floor() {
+        let mut item = mock_item();
+        let options = ExportOptions {
+            start_bid_strategy: StartBidStrategy::FractionOfCost(0.3),
+            ..ExportOptions::default()
+        };
+        assert!(options.writes_min_bid());
+
+        let lot = HiBidLot::from_inventory_item_with_options(&item, &options);
+        assert_eq!(lot.start_bid, 23.1);
+        assert_eq!(lot.min_bid, Some(item.min_price));
+
+        item.cost_price = 2.0;
+        let lot = HiBidLot::from_inventory_item_with_options(&item, &options);
+        assert_eq!(lot.start_bid, 1.0);
+    }
+
+    #[test]
+    fn test_export_splits_into_parts() {
+        let dir = std::env::temp_dir().join(format!("hibid-parts-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let items: Vec<InventoryItemRow> = (1..=5)
+            .map(|n| InventoryItemRow {
+                lot_number: Some(n.to_string()),
+                ..mock_item()
+            })
+            .collect();
+        let base = dir.join("catalog.csv");
+        let base = base.to_str().unwrap();
+
+        let parts =
+            export_to_hibid_csv_parts(&items, base, &ExportOptions::default(), Some(2)).unwrap();
+        let counts: Vec<usize> = parts.iter().map(|part| part.lots).collect();
+        assert_eq!(counts, [2, 2, 1]);
+        assert!(parts[2].path.ends_with("catalog-part3.csv"));
+        let last = std::fs::read_to_string(&parts[2].path).unwrap();
+        assert!(last.starts_with("LotNum,Lead"));
+        assert!(last.contains("\n5,"));
+        assert!(!Path::new(base).exists());
+
+        // Fits in one file: the plain path, as without a limit
+        let single =
+            export_to_hibid_csv_parts(&items, base, &ExportOptions::default(), Some(10)).unwrap();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].path, base);
+        assert_eq!(single[0].lots, 5);
+
+        assert!(
+            export_to_hibid_csv_parts(&items, base, &ExportOptions::default(), Some(0)).is_err()
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_collect_lot_images_numeric_order_and_dedup() {
+        let dir = std::env::temp_dir().join(format!("hibid_images_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "42-10.jpg",
+            "42-2.jpg",
+            "42-1.jpg",
+            "42-01.JPG",
+            "420-1.jpg",
+            "42-x.jpg",
+        ] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+
+        let images = collect_lot_images(&dir, "42");
+        assert_eq!(images.len(), 3);
+        assert_eq!(images, ["42-01.JPG", "42-2.jpg", "42-10.jpg"]);
+
+        let options = ExportOptions {
+            image_dir: Some(dir.clone()),
+            ..ExportOptions::default()
+        };
+        let mut item = mock_item();
+        item.lot_number = Some("42".to_string());
+        let lot = HiBidLot::from_inventory_item_with_options(&item, &options);
+        assert!(lot.images.ends_with(",42-2.jpg,42-10.jpg"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
 }
@@ -6,6 +6,8 @@
 mod auctions;
 mod csv_parser;
 mod db;
+mod db_lock;
+mod error;
 mod hibid;
 mod nlp;
 mod pallet_manifests;
@@ -13,19 +15,38 @@ mod pricing;
 mod reconciliation;
 
 use db::Database;
-use serde::Serialize;
-use std::sync::Mutex;
+use error::AppError;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
 
 pub struct AppState {
-    pub db: Mutex<Database>,
+    pub db: db_lock::DbMutex,
 }
 
+/// Import outcome. `items_count` and the totals cover the whole manifest, so an
+/// append reports the combined figures; the other fields describe this file.
 #[derive(Debug, Serialize)]
 pub struct ManifestSummary {
     pub id: String,
     pub items_count: usize,
+    pub total_quantity: i64,
     pub total_retail: f64,
     pub total_cost: f64,
+    /// Lot numbers whose source matched no vendor and were priced with the fallback vendor
+    pub unknown_source_lots: Vec<String>,
+    /// Rows imported as Scrap for retail below `min_listable_retail`
+    pub auto_scrapped: usize,
+    /// Rows marked `needs_title` (title too short or without letters)
+    pub needs_title_count: usize,
+    /// Rows whose cost/retail looks wrong for the vendor or brand (likely a mis-mapped source)
+    pub cost_warnings: Vec<String>,
+}
+
+/// Shortest acceptable item title from the `min_title_length` setting
+fn load_min_title_length(db: &db::Database) -> usize {
+    db.get_setting(csv_parser::MIN_TITLE_LENGTH_SETTING)
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(csv_parser::DEFAULT_MIN_TITLE_LENGTH)
 }
 
 #[derive(Debug, Serialize)]
@@ -49,6 +70,21 @@ pub struct AuctionPnlRow {
     pub total_cost: f64,
     pub total_commission: f64,
     pub net_profit: f64,
+    /// Cost of bought-back items, only charged when `include_buyback_cost` is set
+    pub buyback_cost: f64,
+}
+
+/// One row of the `v_sales_analytics` view (historical sales by brand/category/season)
+#[derive(Debug, Serialize)]
+pub struct SalesAnalyticsRow {
+    pub brand: Option<String>,
+    pub category: Option<String>,
+    pub season: Option<String>,
+    pub sales_count: i64,
+    pub avg_sale_price: f64,
+    pub avg_retail_price: f64,
+    pub first_sale: Option<String>,
+    pub last_sale: Option<String>,
 }
 
 // ============================================================
@@ -59,128 +95,390 @@ pub struct AuctionPnlRow {
 fn import_manifest(
     file_path: String,
     auction_id: Option<String>,
+    append_to_manifest_id: Option<String>,
     state: tauri::State<AppState>,
-) -> Result<ManifestSummary, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    // ... implementation same as before ...
-    // Re-implemented for brevity or keep existing if using MultiReplace
-    // Since this is ReplaceFileContent, I must be careful not to delete logic.
-    // The previous prompt had the full implementation.
-    // I will use the existing implementation logic from previous step 156.
+) -> Result<ManifestSummary, AppError> {
+    let mut db = state.db.lock()?;
+    import_manifest_file(&mut db, &file_path, auction_id, append_to_manifest_id)
+}
 
-    let pricing_engine = pricing::PricingEngine::new(&db.conn).map_err(|e| e.to_string())?;
+/// Import a B-Stock manifest in one transaction: a failure part-way leaves
+/// neither a new manifest nor partially appended rows. `items_count` and the
+/// totals cover the whole manifest; the other summary fields cover this file.
+fn import_manifest_file(
+    db: &mut Database,
+    file_path: &str,
+    auction_id: Option<String>,
+    append_to_manifest_id: Option<String>,
+) -> Result<ManifestSummary, AppError> {
+    let pricing_engine = pricing::PricingEngine::new(&db.conn)?;
 
     // 1. Parse CSV
-    let rows = csv_parser::parse_bstock_csv(&file_path).map_err(|e| e.to_string())?;
+    let rows = csv_parser::parse_bstock_csv(file_path)?;
 
-    // 2. Create manifest
-    let manifest_id = uuid::Uuid::new_v4().to_string();
-    let filename = std::path::Path::new(&file_path)
+    // 2. Create manifest, or reuse the one being appended to
+    let filename = std::path::Path::new(file_path)
         .file_name()
         .and_then(|f| f.to_str())
         .unwrap_or("unknown.csv");
 
-    db.conn
-        .execute(
-            "INSERT INTO manifests (id, source_filename, items_count) VALUES (?1, ?2, ?3)",
-            rusqlite::params![manifest_id, filename, rows.len()],
-        )
-        .map_err(|e| e.to_string())?;
+    if rows.is_empty() {
+        return Err(AppError::Validation(format!(
+            "No data rows found in {}; nothing was imported",
+            filename
+        )));
+    }
 
-    // 3. Process each row
-    let mut total_retail = 0.0;
-    let mut total_cost = 0.0;
+    // Settings are read before the transaction takes the connection
+    let nlp_extractor = nlp::EntityExtractor::new()
+        .with_source_category_hints(nlp::load_source_category_hints(&db.conn))
+        .with_normalize_steps(nlp::load_normalize_steps(&db.conn));
 
-    let nlp_extractor = nlp::EntityExtractor::new();
+    // Optionally fill uncategorized items with the configured default category
+    let apply_default_category: bool = db
+        .conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = 'apply_default_category_on_import'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .map(|value| value == "1")
+        .unwrap_or(false);
+    let default_category = hibid::load_default_category(&db.conn);
+    let mut unknown_source_lots = Vec::new();
+    // Rows below this retail aren't worth listing; 0 or unset keeps everything
+    let min_listable_retail = db
+        .get_setting("min_listable_retail")
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| *v > 0.0);
+    let mut auto_scrapped = 0;
+    let min_title_length = load_min_title_length(db);
+    let mut needs_title_count = 0;
+    let max_cost_ratio = db
+        .get_setting(pricing::MAX_COST_RETAIL_RATIO_SETTING)
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| *v > 0.0 && v.is_finite())
+        .unwrap_or(pricing::DEFAULT_MAX_COST_RETAIL_RATIO);
+    let brand_cost_ratios = pricing::load_brand_cost_ratios(&db.conn)?;
+    let mut cost_warnings = Vec::new();
+
+    let current_user = db.current_user();
+    let tx = db.conn.transaction()?;
+
+    let manifest_id = match append_to_manifest_id {
+        Some(existing_id) => {
+            let manifest_status: String = tx
+                .query_row(
+                    "SELECT COALESCE(status, 'Imported') FROM manifests WHERE id = ?1",
+                    rusqlite::params![existing_id],
+                    |row| row.get(0),
+                )
+                .map_err(|e| match e {
+                    rusqlite::Error::QueryReturnedNoRows => {
+                        AppError::NotFound(format!("Manifest {} not found", existing_id))
+                    }
+                    other => other.into(),
+                })?;
+            if manifest_status == "Closed" {
+                return Err(AppError::Validation(
+                    "Cannot append rows to a closed manifest".into(),
+                ));
+            }
+            log::info!(
+                "Appending {} rows from {} to manifest {}",
+                rows.len(),
+                filename,
+                existing_id
+            );
+            existing_id
+        }
+        None => {
+            let manifest_id = uuid::Uuid::new_v4().to_string();
+            tx.execute(
+                "INSERT INTO manifests (id, source_filename, items_count, created_by)
+                 VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![manifest_id, filename, rows.len(), current_user],
+            )?;
+            manifest_id
+        }
+    };
 
+    // 3. Process each row
     for row in &rows {
         let retail_price = csv_parser::clean_price(&row.retail_price);
         let source = csv_parser::normalize_source(&row.source);
-        let (cost, min_price, _vendor) = pricing_engine.calculate_cost(retail_price, &source);
+        let condition = csv_parser::extract_and_normalize_condition(&row.description);
+        let quote = pricing_engine.calculate_cost_for_condition(retail_price, &source, &condition);
+        if quote.used_fallback {
+            log::warn!(
+                "Lot {}: unknown source '{}', priced as {}",
+                row.lot_number,
+                source,
+                quote.vendor
+            );
+            unknown_source_lots.push(row.lot_number.clone());
+        }
 
         let item_id = uuid::Uuid::new_v4().to_string();
-        let status = if auction_id.is_some() {
+        let scrap = min_listable_retail.is_some_and(|min| retail_price < min);
+        let status = if scrap {
+            auto_scrapped += 1;
+            "Scrap"
+        } else if auction_id.is_some() {
             "Listed"
         } else {
             "InStock"
         };
-        let condition = csv_parser::extract_and_normalize_condition(&row.description);
         let read_description_flag =
             csv_parser::parse_read_description_flag(&row.read_description_flag);
-
-        db.conn
-            .execute(
-                "INSERT INTO inventory_items
-                 (id, manifest_id, lot_number, raw_title, vendor_code, source,
-                  retail_price, cost_price, min_price, quantity, current_status, auction_id, condition, read_description_flag)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
-                rusqlite::params![
-                    item_id,
-                    manifest_id,
-                    row.lot_number,
-                    row.title,
-                    row.vendor_code,
-                    source,
-                    retail_price,
-                    cost,
-                    min_price,
-                    row.quantity.parse::<i32>().unwrap_or(1),
-                    status,
-                    auction_id.as_ref(),
-                    condition,
-                    read_description_flag
-                ],
-            )
-            .map_err(|e| e.to_string())?;
+        let needs_title = csv_parser::is_garbage_title(&row.title, min_title_length);
+        if needs_title {
+            log::warn!("Lot {}: unusable title {:?}", row.lot_number, row.title);
+            needs_title_count += 1;
+        }
+
+        let quantity = row.quantity.parse::<i32>().unwrap_or(1);
+
+        tx.execute(
+            "INSERT INTO inventory_items
+             (id, manifest_id, lot_number, raw_title, vendor_code, source,
+              retail_price, cost_price, min_price, quantity, current_status, auction_id, condition, read_description_flag,
+              gross_margin_pct, needs_title)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+            rusqlite::params![
+                item_id,
+                manifest_id,
+                row.lot_number,
+                row.title,
+                row.vendor_code,
+                source,
+                retail_price,
+                quote.cost,
+                quote.min_price,
+                quantity,
+                status,
+                auction_id.as_ref().filter(|_| !scrap),
+                condition,
+                read_description_flag,
+                pricing::gross_margin_pct(quote.cost, quote.min_price),
+                needs_title
+            ],
+        )?;
 
         // NLP: extract brand, model, category from title
-        let entities = nlp_extractor.extract(&row.title);
-        db.conn
-            .execute(
-                "UPDATE inventory_items
+        let entities = nlp_extractor.extract_with_source(&row.title, &source);
+        let category = match entities.category {
+            None if apply_default_category => Some(default_category.clone()),
+            other => other,
+        };
+        // Cost/retail sanity check; scrapped rows stay out of manifest cost
+        let brand_ratio = entities.brand.as_deref().and_then(|brand| {
+            brand_cost_ratios
+                .get(&brand.trim().to_lowercase())
+                .map(|ratio| (brand, *ratio))
+        });
+        if !scrap {
+            if let Some(warning) = pricing::cost_ratio_warning(
+                &row.lot_number,
+                &source,
+                retail_price,
+                quote.cost,
+                max_cost_ratio,
+                brand_ratio,
+            ) {
+                log::warn!("{}", warning);
+                cost_warnings.push(warning);
+            }
+        }
+        tx.execute(
+            "UPDATE inventory_items
                  SET normalized_title = ?1,
                      extracted_brand = ?2,
                      extracted_model = ?3,
-                     category = ?4
-                 WHERE id = ?5",
-                rusqlite::params![
-                    entities.normalized_title,
-                    entities.brand,
-                    entities.model,
-                    entities.category,
-                    item_id
-                ],
-            )
-            .map_err(|e| e.to_string())?;
-
-        total_retail += retail_price;
-        total_cost += cost;
+                     category = ?4,
+                     nlp_confidence = ?5
+                 WHERE id = ?6",
+            rusqlite::params![
+                entities.normalized_title,
+                entities.brand,
+                entities.model,
+                category,
+                entities.confidence.overall(),
+                item_id
+            ],
+        )?;
     }
 
-    // 4. Update manifest totals
-    db.conn
-        .execute(
-            "UPDATE manifests SET total_retail_value = ?1, total_cost = ?2 WHERE id = ?3",
-            rusqlite::params![total_retail, total_cost, manifest_id],
-        )
-        .map_err(|e| e.to_string())?;
+    // 4. Recompute manifest totals over all its rows (covers appended rows too)
+    tx.execute(
+        "UPDATE manifests
+             SET total_retail_value = (
+                     SELECT COALESCE(SUM(retail_price), 0) FROM inventory_items
+                     WHERE manifest_id = ?1
+                 ),
+                 total_cost = (
+                     SELECT COALESCE(SUM(cost_price), 0) FROM inventory_items
+                     WHERE manifest_id = ?1
+                 ),
+                 items_count = (
+                     SELECT COUNT(*) FROM inventory_items WHERE manifest_id = ?1
+                 )
+             WHERE id = ?1",
+        rusqlite::params![manifest_id],
+    )?;
+    let (items_count, total_quantity, total_retail, total_cost): (i64, i64, f64, f64) = tx
+        .query_row(
+            "SELECT m.items_count,
+                    (SELECT COALESCE(SUM(quantity), 0) FROM inventory_items WHERE manifest_id = m.id),
+                    m.total_retail_value, m.total_cost
+             FROM manifests m
+             WHERE m.id = ?1",
+            rusqlite::params![manifest_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+    tx.commit()?;
 
     Ok(ManifestSummary {
         id: manifest_id,
-        items_count: rows.len(),
+        items_count: items_count as usize,
+        total_quantity,
         total_retail,
         total_cost,
+        unknown_source_lots,
+        auto_scrapped,
+        needs_title_count,
+        cost_warnings,
     })
 }
 
+#[tauri::command]
+fn get_manifests(state: tauri::State<AppState>) -> Result<Vec<db::ManifestRow>, AppError> {
+    let db = state.db.lock()?;
+    Ok(db.get_manifests()?)
+}
+
+/// Set or clear (blank) a manifest's note
+#[tauri::command]
+fn set_manifest_notes(
+    manifest_id: String,
+    notes: String,
+    state: tauri::State<AppState>,
+) -> Result<(), AppError> {
+    let db = state.db.lock()?;
+    let updated = db.conn.execute(
+        "UPDATE manifests SET notes = NULLIF(TRIM(?1), '') WHERE id = ?2",
+        rusqlite::params![notes, manifest_id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!(
+            "Manifest {} not found",
+            manifest_id
+        )));
+    }
+    Ok(())
+}
+
+/// Replace a manifest's tags; blanks and case-insensitive duplicates are dropped.
+/// Returns the tags as stored.
+#[tauri::command]
+fn set_manifest_tags(
+    manifest_id: String,
+    tags: Vec<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<String>, AppError> {
+    if tags.iter().any(|tag| tag.contains(',')) {
+        return Err(AppError::Validation(
+            "Tags cannot contain commas".to_string(),
+        ));
+    }
+    let mut cleaned: Vec<String> = Vec::new();
+    for tag in db::parse_tags(Some(&tags.join(","))) {
+        if !cleaned.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+            cleaned.push(tag);
+        }
+    }
+
+    let db = state.db.lock()?;
+    let updated = db.conn.execute(
+        "UPDATE manifests SET tags = NULLIF(?1, '') WHERE id = ?2",
+        rusqlite::params![cleaned.join(","), manifest_id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!(
+            "Manifest {} not found",
+            manifest_id
+        )));
+    }
+    Ok(cleaned)
+}
+
 #[tauri::command]
 fn get_inventory_items(
     status: Option<String>,
+    condition: Option<String>,
+    manifest_id: Option<String>,
+    source: Option<String>,
     state: tauri::State<AppState>,
-) -> Result<Vec<db::InventoryItemRow>, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.get_inventory_items(status.as_deref())
-        .map_err(|e| e.to_string())
+) -> Result<Vec<db::InventoryItemRow>, AppError> {
+    let db = state.db.lock()?;
+    Ok(db.get_inventory_filtered(&db::InventoryFilter {
+        status: status.as_deref(),
+        condition: condition.as_deref(),
+        manifest_id: manifest_id.as_deref(),
+        source: source.as_deref(),
+    })?)
+}
+
+/// Items with a stored gross margin percent between `min_pct` and `max_pct` (inclusive)
+#[tauri::command]
+fn get_inventory_by_margin(
+    min_pct: Option<f64>,
+    max_pct: Option<f64>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<db::InventoryItemRow>, AppError> {
+    if let (Some(min), Some(max)) = (min_pct, max_pct) {
+        if min > max {
+            return Err(AppError::Validation(format!(
+                "min_pct ({}) is greater than max_pct ({})",
+                min, max
+            )));
+        }
+    }
+    let db = state.db.lock()?;
+    Ok(db.get_inventory_by_margin(min_pct, max_pct)?)
+}
+
+#[tauri::command]
+fn get_recent_activity(
+    since: String,
+    limit: Option<i64>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<db::InventoryItemRow>, AppError> {
+    let db = state.db.lock()?;
+    let limit = limit.unwrap_or(50).clamp(1, 1000);
+    Ok(db.get_recent_activity(&since, limit)?)
+}
+
+#[tauri::command]
+fn get_valuation(state: tauri::State<AppState>) -> Result<db::InventoryValuation, AppError> {
+    let db = state.db.lock()?;
+    Ok(db.get_valuation()?)
+}
+
+#[tauri::command]
+fn get_facets(state: tauri::State<AppState>) -> Result<db::InventoryFacets, AppError> {
+    let db = state.db.lock()?;
+    Ok(db.get_facets()?)
+}
+
+#[tauri::command]
+fn get_source_stats(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<db::SourceStats>, AppError> {
+    let db = state.db.lock()?;
+    Ok(db.get_source_stats(start_date.as_deref(), end_date.as_deref())?)
 }
 
 #[tauri::command]
@@ -195,36 +493,228 @@ fn get_vendors(state: tauri::State<AppState>) -> Result<Vec<pricing::Vendor>, St
     pricing::PricingEngine::load_vendors(&db.conn).map_err(|e| e.to_string())
 }
 
+/// Outcome of `import_vendors_csv`; rows listed in `errors` were not written
+#[derive(Debug, Serialize)]
+struct VendorImportSummary {
+    created: usize,
+    updated: usize,
+    /// "Row N: reason", numbered by CSV line
+    errors: Vec<String>,
+}
+
+/// Upsert vendors from a CSV of id, name, cost_coefficient, min_price_margin, active.
+/// Rows match an existing vendor by id, then by name; a blank id on a new vendor
+/// gets a generated one. Invalid rows are reported and skipped.
 #[tauri::command]
-fn get_auction_pnl_list(state: tauri::State<AppState>) -> Result<Vec<AuctionPnlRow>, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let mut stmt = db
+fn import_vendors_csv(
+    file_path: String,
+    state: tauri::State<AppState>,
+) -> Result<VendorImportSummary, AppError> {
+    let rows = csv_parser::parse_vendor_csv(&file_path)?;
+    let mut db = state.db.lock()?;
+    let tx = db.conn.transaction()?;
+
+    let mut summary = VendorImportSummary {
+        created: 0,
+        updated: 0,
+        errors: Vec::new(),
+    };
+    for (index, row) in rows.into_iter().enumerate() {
+        // Line 1 is the header
+        let line = index + 2;
+        let row = match row {
+            Ok(row) => row,
+            Err(e) => {
+                summary.errors.push(format!("Row {}: {}", line, e));
+                continue;
+            }
+        };
+        if row.name.is_empty() {
+            summary
+                .errors
+                .push(format!("Row {}: name is required", line));
+            continue;
+        }
+        let (cost_coefficient, min_price_margin) = match (
+            row.cost_coefficient.parse::<f64>(),
+            row.min_price_margin.parse::<f64>(),
+        ) {
+            (Ok(c), Ok(m)) => (c, m),
+            (Err(_), _) => {
+                summary.errors.push(format!(
+                    "Row {}: invalid cost_coefficient '{}'",
+                    line, row.cost_coefficient
+                ));
+                continue;
+            }
+            (_, Err(_)) => {
+                summary.errors.push(format!(
+                    "Row {}: invalid min_price_margin '{}'",
+                    line, row.min_price_margin
+                ));
+                continue;
+            }
+        };
+        if let Err(e) = pricing::validate_vendor_terms(cost_coefficient, min_price_margin) {
+            summary.errors.push(format!("Row {}: {}", line, e));
+            continue;
+        }
+        // A blank active column keeps vendors active
+        let is_active = match row.active.as_deref() {
+            None | Some("") => true,
+            Some(_) => csv_parser::parse_read_description_flag(&row.active),
+        };
+
+        let existing: Option<String> = tx
+            .query_row(
+                "SELECT id FROM vendors WHERE id = ?1 OR name = ?2
+                 ORDER BY (id = ?1) DESC LIMIT 1",
+                rusqlite::params![row.id, row.name],
+                |r| r.get(0),
+            )
+            .map(Some)
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                other => Err(other),
+            })?;
+
+        let written = match &existing {
+            Some(id) => tx.execute(
+                "UPDATE vendors
+                 SET name = ?1, cost_coefficient = ?2, min_price_margin = ?3, is_active = ?4
+                 WHERE id = ?5",
+                rusqlite::params![row.name, cost_coefficient, min_price_margin, is_active, id],
+            ),
+            None => {
+                let id = if row.id.is_empty() {
+                    uuid::Uuid::new_v4().to_string()
+                } else {
+                    row.id.clone()
+                };
+                tx.execute(
+                    "INSERT INTO vendors (id, name, cost_coefficient, min_price_margin, is_active)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    rusqlite::params![id, row.name, cost_coefficient, min_price_margin, is_active],
+                )
+            }
+        };
+        match written {
+            Ok(_) if existing.is_some() => summary.updated += 1,
+            Ok(_) => summary.created += 1,
+            Err(e) => summary
+                .errors
+                .push(format!("Row {}: {}", line, AppError::from(e))),
+        }
+    }
+
+    tx.commit()?;
+    log::info!(
+        "Vendor import: {} created, {} updated, {} rejected",
+        summary.created,
+        summary.updated,
+        summary.errors.len()
+    );
+    Ok(summary)
+}
+
+/// Create an active vendor named `new_name` with the cost coefficient and min
+/// price margin of `source_vendor_id` (active or not). Aliases aren't copied,
+/// since they would make sources match both vendors.
+#[tauri::command]
+fn clone_vendor(
+    source_vendor_id: String,
+    new_name: String,
+    state: tauri::State<AppState>,
+) -> Result<pricing::Vendor, AppError> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err(AppError::Validation("Vendor name is required".into()));
+    }
+    let db = state.db.lock()?;
+    let (cost_coefficient, min_price_margin): (f64, f64) = db
         .conn
-        .prepare(
-            "SELECT auction_id, auction_name, start_date,
-                total_items, sold_items, buyback_items,
-                total_revenue, total_cost, total_commission, net_profit
-         FROM v_auction_pnl
-         ORDER BY start_date DESC
-         LIMIT 12",
+        .query_row(
+            "SELECT cost_coefficient, min_price_margin FROM vendors WHERE id = ?1",
+            rusqlite::params![source_vendor_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
         )
-        .map_err(|e| e.to_string())?;
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Vendor {} not found", source_vendor_id)))?;
+
+    let vendor = pricing::Vendor {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: new_name.to_string(),
+        cost_coefficient,
+        min_price_margin,
+        is_active: true,
+        aliases: Vec::new(),
+        commission_exempt: false,
+    };
+    db.conn.execute(
+        "INSERT INTO vendors (id, name, cost_coefficient, min_price_margin, is_active)
+         VALUES (?1, ?2, ?3, ?4, TRUE)",
+        rusqlite::params![
+            vendor.id,
+            vendor.name,
+            vendor.cost_coefficient,
+            vendor.min_price_margin
+        ],
+    )?;
+    log::info!(
+        "Cloned vendor {} as '{}' ({})",
+        source_vendor_id,
+        vendor.name,
+        vendor.id
+    );
+    Ok(vendor)
+}
+
+/// Columns shared by the P&L queries; the full view adds buyback_cost
+const AUCTION_PNL_COLUMNS: &str = "auction_id, auction_name, start_date,
+                total_items, sold_items, buyback_items,
+                total_revenue, total_cost, total_commission, net_profit";
+
+fn map_auction_pnl_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<AuctionPnlRow> {
+    Ok(AuctionPnlRow {
+        auction_id: row.get(0)?,
+        auction_name: row.get(1)?,
+        start_date: row.get(2)?,
+        total_items: row.get(3)?,
+        sold_items: row.get(4)?,
+        buyback_items: row.get(5)?,
+        total_revenue: row.get(6)?,
+        total_cost: row.get(7)?,
+        total_commission: row.get(8)?,
+        net_profit: row.get(9)?,
+        buyback_cost: row.get(10)?,
+    })
+}
+
+/// P&L source for the optional buyback cost; v_auction_pnl_full also subtracts
+/// the cost of bought-back items from net profit
+fn auction_pnl_source(include_buyback_cost: Option<bool>) -> (&'static str, &'static str) {
+    if include_buyback_cost.unwrap_or(false) {
+        ("v_auction_pnl_full", "buyback_cost")
+    } else {
+        ("v_auction_pnl", "0.0")
+    }
+}
+
+#[tauri::command]
+fn get_auction_pnl_list(
+    include_buyback_cost: Option<bool>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<AuctionPnlRow>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let (view, buyback_cost) = auction_pnl_source(include_buyback_cost);
+    let sql = format!(
+        "SELECT {}, {} FROM {} ORDER BY start_date DESC LIMIT 12",
+        AUCTION_PNL_COLUMNS, buyback_cost, view
+    );
+    let mut stmt = db.conn.prepare(&sql).map_err(|e| e.to_string())?;
 
     let rows = stmt
-        .query_map([], |row| {
-            Ok(AuctionPnlRow {
-                auction_id: row.get(0)?,
-                auction_name: row.get(1)?,
-                start_date: row.get(2)?,
-                total_items: row.get(3)?,
-                sold_items: row.get(4)?,
-                buyback_items: row.get(5)?,
-                total_revenue: row.get(6)?,
-                total_cost: row.get(7)?,
-                total_commission: row.get(8)?,
-                net_profit: row.get(9)?,
-            })
-        })
+        .query_map([], map_auction_pnl_row)
         .map_err(|e| e.to_string())?
         .collect::<rusqlite::Result<Vec<_>>>()
         .map_err(|e| e.to_string())?;
@@ -232,15 +722,135 @@ fn get_auction_pnl_list(state: tauri::State<AppState>) -> Result<Vec<AuctionPnlR
     Ok(rows)
 }
 
+/// P&L for one auction, including older ones that fall outside the list
+#[tauri::command]
+fn get_auction_pnl(
+    auction_id: String,
+    include_buyback_cost: Option<bool>,
+    state: tauri::State<AppState>,
+) -> Result<AuctionPnlRow, AppError> {
+    let db = state.db.lock()?;
+    let (view, buyback_cost) = auction_pnl_source(include_buyback_cost);
+    let sql = format!(
+        "SELECT {}, {} FROM {} WHERE auction_id = ?1",
+        AUCTION_PNL_COLUMNS, buyback_cost, view
+    );
+    db.conn
+        .query_row(&sql, [&auction_id], map_auction_pnl_row)
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Auction {} not found", auction_id)))
+}
+
+/// Rows of `v_sales_analytics`, optionally narrowed to one brand and/or category
+/// (case-insensitive), busiest first
+fn load_sales_analytics(
+    db: &db::Database,
+    brand: Option<&str>,
+    category: Option<&str>,
+) -> rusqlite::Result<Vec<SalesAnalyticsRow>> {
+    let mut stmt = db.conn.prepare(
+        "SELECT extracted_brand, category, season, sales_count,
+                COALESCE(avg_sale_price, 0), COALESCE(avg_retail_price, 0),
+                first_sale, last_sale
+         FROM v_sales_analytics
+         WHERE (?1 IS NULL OR LOWER(extracted_brand) = LOWER(?1))
+           AND (?2 IS NULL OR LOWER(category) = LOWER(?2))
+         ORDER BY sales_count DESC, extracted_brand, category, season",
+    )?;
+    let rows = stmt
+        .query_map(rusqlite::params![brand, category], |row| {
+            Ok(SalesAnalyticsRow {
+                brand: row.get(0)?,
+                category: row.get(1)?,
+                season: row.get(2)?,
+                sales_count: row.get(3)?,
+                avg_sale_price: row.get(4)?,
+                avg_retail_price: row.get(5)?,
+                first_sale: row.get(6)?,
+                last_sale: row.get(7)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(rows)
+}
+
+/// Blank filters from the UI mean "no filter"
+fn non_blank(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+#[tauri::command]
+fn get_sales_analytics(
+    brand: Option<String>,
+    category: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<SalesAnalyticsRow>, AppError> {
+    let db = state.db.lock()?;
+    Ok(load_sales_analytics(
+        &db,
+        non_blank(&brand),
+        non_blank(&category),
+    )?)
+}
+
+/// Write `v_sales_analytics` (with the same optional filters) to a CSV; returns the row count
+#[tauri::command]
+fn export_sales_analytics_csv(
+    file_path: String,
+    brand: Option<String>,
+    category: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<usize, AppError> {
+    let rows = {
+        let db = state.db.lock()?;
+        load_sales_analytics(&db, non_blank(&brand), non_blank(&category))?
+    };
+
+    let mut wtr = csv::Writer::from_path(&file_path)?;
+    wtr.write_record([
+        "Brand",
+        "Category",
+        "Season",
+        "Sales",
+        "Avg Sale Price",
+        "Avg Retail Price",
+        "First Sale",
+        "Last Sale",
+    ])?;
+    for row in &rows {
+        wtr.write_record([
+            row.brand.as_deref().unwrap_or(""),
+            row.category.as_deref().unwrap_or(""),
+            row.season.as_deref().unwrap_or(""),
+            &row.sales_count.to_string(),
+            &format!("{:.2}", row.avg_sale_price),
+            &format!("{:.2}", row.avg_retail_price),
+            row.first_sale.as_deref().unwrap_or(""),
+            row.last_sale.as_deref().unwrap_or(""),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(rows.len())
+}
+
 #[tauri::command]
 fn export_inventory_csv(
     file_path: String,
     status: Option<String>,
+    number_locale: Option<hibid::NumberLocale>,
+    manifest_id: Option<String>,
+    source: Option<String>,
     state: tauri::State<AppState>,
 ) -> Result<usize, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
+    let locale = number_locale.unwrap_or_else(|| hibid::load_number_locale(&db.conn));
     let items = db
-        .get_inventory_items(status.as_deref())
+        .get_inventory_filtered(&db::InventoryFilter {
+            status: status.as_deref(),
+            manifest_id: manifest_id.as_deref(),
+            source: source.as_deref(),
+            ..Default::default()
+        })
         .map_err(|e| e.to_string())?;
 
     let mut wtr = csv::Writer::from_path(&file_path).map_err(|e| e.to_string())?;
@@ -262,9 +872,9 @@ fn export_inventory_csv(
             &item.raw_title,
             item.source.as_deref().unwrap_or(""),
             &item.current_status,
-            &format!("{:.2}", item.retail_price),
-            &format!("{:.2}", item.cost_price),
-            &format!("{:.2}", item.min_price),
+            &locale.format(item.retail_price, 2),
+            &locale.format(item.cost_price, 2),
+            &locale.format(item.min_price, 2),
             &item.created_at,
         ])
         .map_err(|e| e.to_string())?;
@@ -273,6 +883,93 @@ fn export_inventory_csv(
     Ok(items.len())
 }
 
+#[derive(Debug, Serialize)]
+struct HibidExportSummary {
+    written: usize,
+    /// Listed lots left out by the min_retail / min_start_bid thresholds
+    skipped: usize,
+    /// Files written with their lot counts; one entry unless split
+    files: Vec<hibid::ExportPart>,
+}
+
+/// Per-run choices for `export_hibid_csv`; anything left out uses settings
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct HiBidExportOptions {
+    condition: Option<String>,
+    /// Switches this export to cost-based openings at this fraction of cost
+    start_bid_cost_fraction: Option<f64>,
+    image_dir: Option<String>,
+    /// Lots below this retail are left out
+    min_retail: Option<f64>,
+    /// Lots whose StartBid would be below this are left out
+    min_start_bid: Option<f64>,
+    number_locale: Option<hibid::NumberLocale>,
+    /// Split the export into `{base}-partN.csv` files of this many lots
+    lots_per_file: Option<usize>,
+}
+
+#[tauri::command]
+fn export_hibid_csv(
+    file_path: String,
+    auction_id: String,
+    options: Option<HiBidExportOptions>,
+    state: tauri::State<AppState>,
+) -> Result<HibidExportSummary, String> {
+    let HiBidExportOptions {
+        condition,
+        start_bid_cost_fraction,
+        image_dir,
+        min_retail,
+        min_start_bid,
+        number_locale,
+        lots_per_file,
+    } = options.unwrap_or_default();
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let items = db
+        .get_listed_items_for_auction(&auction_id, condition.as_deref())
+        .map_err(|e| e.to_string())?;
+
+    let mut options = hibid::ExportOptions::load(&db.conn);
+    options.image_dir = image_dir
+        .filter(|dir| !dir.trim().is_empty())
+        .map(std::path::PathBuf::from);
+    // A per-run fraction switches this export to cost-based openings
+    if let Some(fraction) = start_bid_cost_fraction {
+        if !(fraction > 0.0 && fraction.is_finite()) {
+            return Err("Start bid cost fraction must be a positive number".to_string());
+        }
+        options.start_bid_strategy = hibid::StartBidStrategy::FractionOfCost(fraction);
+    }
+    if let Some(locale) = number_locale {
+        options.number_locale = locale;
+    }
+    options.lot_prefix = db
+        .conn
+        .query_row(
+            "SELECT lot_prefix FROM auctions WHERE id = ?1",
+            rusqlite::params![auction_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    // Lots under the thresholds are left Listed for another sale
+    let below_threshold = |item: &db::InventoryItemRow| {
+        min_retail.is_some_and(|min| item.retail_price < min)
+            || min_start_bid.is_some_and(|min| {
+                hibid::HiBidLot::from_inventory_item_with_options(item, &options).start_bid < min
+            })
+    };
+    let (below, items): (Vec<_>, Vec<_>) = items.into_iter().partition(below_threshold);
+    let files = hibid::export_to_hibid_csv_parts(&items, &file_path, &options, lots_per_file)
+        .map_err(|e| e.to_string())?;
+    Ok(HibidExportSummary {
+        written: files.iter().map(|file| file.lots).sum(),
+        skipped: below.len(),
+        files,
+    })
+}
+
 #[tauri::command]
 fn update_item_status(
     item_id: String,
@@ -304,6 +1001,11 @@ fn get_setting(key: String, state: tauri::State<AppState>) -> Result<Option<Stri
 
 #[tauri::command]
 fn save_setting(key: String, value: String, state: tauri::State<AppState>) -> Result<(), String> {
+    let value = if key == hibid::IMAGE_URL_BASE_SETTING && !value.trim().is_empty() {
+        hibid::validate_image_url_base(&value)?
+    } else {
+        value
+    };
     let db = state.db.lock().map_err(|e| e.to_string())?;
     // SQLite upsert
     db.conn
@@ -316,9 +1018,70 @@ fn save_setting(key: String, value: String, state: tauri::State<AppState>) -> Re
     Ok(())
 }
 
+/// Set who is using the app; new manifests, auctions and results are stamped with it.
+/// A blank name goes back to "system".
+#[tauri::command]
+fn set_current_user(name: String, state: tauri::State<AppState>) -> Result<String, AppError> {
+    let db = state.db.lock()?;
+    let name = name.trim();
+    if name.chars().count() > 100 {
+        return Err(AppError::Validation(
+            "User name must be at most 100 characters".to_string(),
+        ));
+    }
+    db.conn.execute(
+        "INSERT INTO settings (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        rusqlite::params![db::CURRENT_USER_SETTING, name],
+    )?;
+    Ok(db.current_user())
+}
+
+/// Describe a file write error, calling out permission and disk-full failures
+fn describe_write_error(path: &std::path::Path, e: &std::io::Error) -> String {
+    match e.kind() {
+        std::io::ErrorKind::PermissionDenied => {
+            format!("Permission denied writing {}", path.display())
+        }
+        std::io::ErrorKind::StorageFull => {
+            format!("Disk full while writing {}", path.display())
+        }
+        _ => format!("Failed to save file {}: {}", path.display(), e),
+    }
+}
+
+/// Write `data` to a temp file next to `path`, then rename it over `path`.
+/// The temp file is removed if any step fails, leaving the destination untouched.
+fn write_file_atomic(path: &std::path::Path, data: &[u8]) -> Result<(), String> {
+    use std::io::Write;
+
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => std::path::PathBuf::from("."),
+    };
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("Invalid file path: {}", path.display()))?
+        .to_string_lossy();
+    let temp_path = dir.join(format!(".{}.{}.tmp", file_name, uuid::Uuid::new_v4()));
+
+    let result = (|| -> std::io::Result<()> {
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+        drop(file);
+        std::fs::rename(&temp_path, path)
+    })();
+
+    result.map_err(|e| {
+        let _ = std::fs::remove_file(&temp_path);
+        describe_write_error(path, &e)
+    })
+}
+
 #[tauri::command]
 fn save_binary_file(file_path: String, data: Vec<u8>) -> Result<(), String> {
-    std::fs::write(&file_path, data).map_err(|e| format!("Failed to save file: {}", e))
+    write_file_atomic(std::path::Path::new(&file_path), &data)
 }
 
 // ============================================================
@@ -512,60 +1275,815 @@ fn update_item_buybacker(
     Ok(())
 }
 
-// ============================================================
-// Pricing Rules Commands
-// ============================================================
-
+/// Set or clear (blank) an item's staff note
 #[tauri::command]
-fn get_pricing_rules(state: tauri::State<AppState>) -> Result<Vec<pricing::PricingRule>, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    pricing::get_pricing_rules(&db.conn).map_err(|e| e.to_string())
+fn update_item_notes(
+    item_id: String,
+    notes: String,
+    state: tauri::State<AppState>,
+) -> Result<(), AppError> {
+    let db = state.db.lock()?;
+    let updated = db.conn.execute(
+        "UPDATE inventory_items SET notes = NULLIF(TRIM(?1), '') WHERE id = ?2",
+        rusqlite::params![notes, item_id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Item {} not found", item_id)));
+    }
+    Ok(())
 }
 
+/// Manually set an item's cost and/or min price. A min price below cost is
+/// rejected unless `allow_below_cost` is set.
 #[tauri::command]
-fn update_pricing_rule(
-    condition_category: String,
-    level: i32,
-    multiplier: f64,
+fn update_item_prices(
+    item_id: String,
+    cost_price: Option<f64>,
+    min_price: Option<f64>,
+    allow_below_cost: Option<bool>,
     state: tauri::State<AppState>,
-) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    pricing::update_pricing_rule(&db.conn, &condition_category, level, multiplier)
-        .map_err(|e| e.to_string())
+) -> Result<(), AppError> {
+    for (name, value) in [("cost_price", cost_price), ("min_price", min_price)] {
+        if value.is_some_and(|v| !(v.is_finite() && v >= 0.0)) {
+            return Err(AppError::Validation(format!(
+                "{} must be zero or greater",
+                name
+            )));
+        }
+    }
+    let db = state.db.lock()?;
+    let (old_cost, old_min): (f64, f64) = db
+        .conn
+        .query_row(
+            "SELECT cost_price, min_price FROM inventory_items WHERE id = ?1",
+            rusqlite::params![item_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Item {} not found", item_id)))?;
+    let cost_price = cost_price.unwrap_or(old_cost);
+    let min_price = min_price.unwrap_or(old_min);
+    pricing::check_min_price_covers_cost(cost_price, min_price, allow_below_cost.unwrap_or(false))
+        .map_err(AppError::Validation)?;
+
+    db.conn.execute(
+        "UPDATE inventory_items SET cost_price = ?1, min_price = ?2, gross_margin_pct = ?3
+         WHERE id = ?4",
+        rusqlite::params![
+            cost_price,
+            min_price,
+            pricing::gross_margin_pct(cost_price, min_price),
+            item_id
+        ],
+    )?;
+    Ok(())
 }
 
+/// Set the warehouse bin/location of an item; blank clears it
 #[tauri::command]
-fn recalculate_prices(
-    auction_id: String,
-    vendor_costs: std::collections::HashMap<String, f64>,
-    condition_margins_by_supplier: Option<
-        std::collections::HashMap<String, std::collections::HashMap<String, f64>>,
-    >,
-    condition_margins: Option<std::collections::HashMap<String, f64>>,
+fn set_item_location(
+    item_id: String,
+    location: String,
     state: tauri::State<AppState>,
-) -> Result<i32, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+) -> Result<(), AppError> {
+    let db = state.db.lock()?;
+    let updated = db.conn.execute(
+        "UPDATE inventory_items SET location = NULLIF(TRIM(?1), '') WHERE id = ?2",
+        rusqlite::params![location, item_id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Item {} not found", item_id)));
+    }
+    Ok(())
+}
 
-    // Get all items in the auction
-    let mut stmt = db
+/// Record salvage recovered on a scrapped item; it reduces the P&L write-off
+#[tauri::command]
+fn set_scrap_value(
+    item_id: String,
+    scrap_value: f64,
+    state: tauri::State<AppState>,
+) -> Result<(), AppError> {
+    if !(scrap_value >= 0.0 && scrap_value.is_finite()) {
+        return Err(AppError::Validation(
+            "Scrap value must be zero or a positive number".to_string(),
+        ));
+    }
+    let db = state.db.lock()?;
+    let status: String = db
         .conn
-        .prepare(
-            "SELECT id, retail_price, source, condition FROM inventory_items WHERE auction_id = ?1",
+        .query_row(
+            "SELECT current_status FROM inventory_items WHERE id = ?1",
+            rusqlite::params![item_id],
+            |row| row.get(0),
         )
-        .map_err(|e| e.to_string())?;
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Item {} not found", item_id)))?;
+    if status != "Scrap" {
+        return Err(AppError::Validation(format!(
+            "Item {} is {}, not Scrap",
+            item_id, status
+        )));
+    }
+    db.conn.execute(
+        "UPDATE inventory_items SET scrap_value = ?1 WHERE id = ?2",
+        rusqlite::params![scrap_value, item_id],
+    )?;
+    Ok(())
+}
 
-    struct ItemData {
-        id: String,
-        retail: f64,
-        source: String,
-        condition: String,
+/// Flag an item for manager review with a reason ("wrong price", "check condition")
+#[tauri::command]
+fn flag_item(
+    item_id: String,
+    reason: String,
+    state: tauri::State<AppState>,
+) -> Result<(), AppError> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(AppError::Validation(
+            "A reason is required to flag an item".to_string(),
+        ));
     }
-    let items: Vec<ItemData> = stmt
-        .query_map(rusqlite::params![auction_id], |row| {
-            Ok(ItemData {
-                id: row.get(0)?,
-                retail: row.get::<_, Option<f64>>(1)?.unwrap_or(0.0),
-                source: row
+    let db = state.db.lock()?;
+    let updated = db.conn.execute(
+        "UPDATE inventory_items SET is_flagged = 1, flag_reason = ?1 WHERE id = ?2",
+        rusqlite::params![reason, item_id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Item {} not found", item_id)));
+    }
+    Ok(())
+}
+
+/// Take an item out of the review queue once it has been handled
+#[tauri::command]
+fn unflag_item(item_id: String, state: tauri::State<AppState>) -> Result<(), AppError> {
+    let db = state.db.lock()?;
+    let updated = db.conn.execute(
+        "UPDATE inventory_items SET is_flagged = 0, flag_reason = NULL WHERE id = ?1",
+        rusqlite::params![item_id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Item {} not found", item_id)));
+    }
+    Ok(())
+}
+
+/// Items imported with a missing or garbage title, to be fixed before listing
+#[tauri::command]
+fn get_items_needing_title(
+    state: tauri::State<AppState>,
+) -> Result<Vec<db::InventoryItemRow>, AppError> {
+    let db = state.db.lock()?;
+    Ok(db.get_items_needing_title()?)
+}
+
+/// Replace an item's title and re-run title extraction (brand, model). The
+/// `needs_title` marker is cleared once the title passes the import check.
+/// Returns whether the item still needs a title.
+#[tauri::command]
+fn update_item_title(
+    item_id: String,
+    title: String,
+    state: tauri::State<AppState>,
+) -> Result<bool, AppError> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(AppError::Validation("Title is required".to_string()));
+    }
+    let db = state.db.lock()?;
+    let source: String = db
+        .conn
+        .query_row(
+            "SELECT COALESCE(source, '') FROM inventory_items WHERE id = ?1",
+            rusqlite::params![item_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Item {} not found", item_id)))?;
+
+    let entities = nlp::EntityExtractor::new()
+        .with_source_category_hints(nlp::load_source_category_hints(&db.conn))
+        .with_normalize_steps(nlp::load_normalize_steps(&db.conn))
+        .extract_with_source(title, &source);
+    let needs_title = csv_parser::is_garbage_title(title, load_min_title_length(&db));
+    db.conn.execute(
+        "UPDATE inventory_items
+             SET raw_title = ?1,
+                 normalized_title = ?2,
+                 extracted_brand = ?3,
+                 extracted_model = ?4,
+                 nlp_confidence = ?5,
+                 needs_title = ?6
+             WHERE id = ?7",
+        rusqlite::params![
+            title,
+            entities.normalized_title,
+            entities.brand,
+            entities.model,
+            entities.confidence.overall(),
+            needs_title,
+            item_id
+        ],
+    )?;
+    Ok(needs_title)
+}
+
+/// Manager review queue: flagged items, longest-waiting first
+#[tauri::command]
+fn get_flagged_items(state: tauri::State<AppState>) -> Result<Vec<db::InventoryItemRow>, AppError> {
+    let db = state.db.lock()?;
+    Ok(db.get_flagged_items()?)
+}
+
+/// Default token-set similarity for `find_duplicate_items`
+const DEFAULT_DUPLICATE_THRESHOLD: f64 = 0.8;
+
+/// Retail prices within this fraction of each other count as the same item
+const DUPLICATE_RETAIL_TOLERANCE: f64 = 0.1;
+
+/// In-stock items that look like the same goods imported twice
+#[derive(Debug, Serialize)]
+struct DuplicateGroup {
+    source: String,
+    /// Lowest title similarity among the pairs that joined the group
+    similarity: f64,
+    items: Vec<db::InventoryItemRow>,
+}
+
+/// Candidate double imports: InStock items from the same source whose titles
+/// reach `threshold` token-set similarity (default 0.8) and whose retail
+/// prices are within 10%. Pairs chain into groups; largest groups first.
+#[tauri::command]
+fn find_duplicate_items(
+    threshold: Option<f64>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<DuplicateGroup>, AppError> {
+    let threshold = threshold.unwrap_or(DEFAULT_DUPLICATE_THRESHOLD);
+    if !(threshold > 0.0 && threshold <= 1.0) {
+        return Err(AppError::Validation(
+            "Threshold must be between 0 and 1".to_string(),
+        ));
+    }
+    let db = state.db.lock()?;
+    let items = db.get_items_by_status("InStock")?;
+    Ok(group_duplicate_items(items, threshold))
+}
+
+fn group_duplicate_items(items: Vec<db::InventoryItemRow>, threshold: f64) -> Vec<DuplicateGroup> {
+    let title = |item: &db::InventoryItemRow| {
+        item.normalized_title
+            .clone()
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or_else(|| item.raw_title.clone())
+    };
+    let similar_retail = |a: f64, b: f64| {
+        let high = a.max(b);
+        high <= 0.0 || (a - b).abs() <= high * DUPLICATE_RETAIL_TOLERANCE
+    };
+
+    // Union-find over matching pairs, compared only within one source
+    let mut by_source: std::collections::HashMap<String, Vec<usize>> =
+        std::collections::HashMap::new();
+    for (index, item) in items.iter().enumerate() {
+        let source = item.source.as_deref().unwrap_or("").trim().to_lowercase();
+        by_source.entry(source).or_default().push(index);
+    }
+    let mut parent: Vec<usize> = (0..items.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    let mut link_score = vec![1.0_f64; items.len()];
+    for indices in by_source.values() {
+        for (n, &a) in indices.iter().enumerate() {
+            for &b in &indices[n + 1..] {
+                if !similar_retail(items[a].retail_price, items[b].retail_price) {
+                    continue;
+                }
+                let score = nlp::token_set_similarity(&title(&items[a]), &title(&items[b]));
+                if score < threshold {
+                    continue;
+                }
+                let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+                let lowest = score.min(link_score[ra]).min(link_score[rb]);
+                if ra != rb {
+                    parent[rb] = ra;
+                }
+                link_score[ra] = lowest;
+            }
+        }
+    }
+
+    let mut groups: std::collections::HashMap<usize, Vec<db::InventoryItemRow>> =
+        std::collections::HashMap::new();
+    for (index, item) in items.into_iter().enumerate() {
+        let r = root(&mut parent, index);
+        groups.entry(r).or_default().push(item);
+    }
+    let mut groups: Vec<DuplicateGroup> = groups
+        .into_iter()
+        .filter(|(_, items)| items.len() > 1)
+        .map(|(r, items)| DuplicateGroup {
+            source: items[0].source.clone().unwrap_or_default(),
+            similarity: (link_score[r] * 100.0).round() / 100.0,
+            items,
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.items
+            .len()
+            .cmp(&a.items.len())
+            .then_with(|| a.source.cmp(&b.source))
+    });
+    groups
+}
+
+// ============================================================
+// NLP Review Commands
+// ============================================================
+
+#[derive(Debug, Serialize)]
+pub struct LowConfidenceItem {
+    pub item_id: String,
+    pub lot_number: Option<String>,
+    pub raw_title: String,
+    pub extracted_brand: Option<String>,
+    pub extracted_model: Option<String>,
+    pub category: Option<String>,
+    pub current_status: String,
+    pub nlp_confidence: f64,
+}
+
+/// Items whose NLP extraction confidence is below `threshold`, least confident first.
+/// Sold and scrapped items are left out; items imported before scoring have no confidence.
+#[tauri::command]
+fn get_low_confidence_items(
+    threshold: f64,
+    state: tauri::State<AppState>,
+) -> Result<Vec<LowConfidenceItem>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let mut stmt = db
+        .conn
+        .prepare(
+            "SELECT id, lot_number, raw_title, extracted_brand, extracted_model, category,
+                    current_status, nlp_confidence
+             FROM inventory_items
+             WHERE nlp_confidence IS NOT NULL
+               AND nlp_confidence < ?1
+               AND current_status NOT IN ('Sold', 'Scrap')
+             ORDER BY nlp_confidence ASC, created_at DESC
+             LIMIT 1000",
+        )
+        .map_err(|e| e.to_string())?;
+    let items = stmt
+        .query_map(rusqlite::params![threshold], |row| {
+            Ok(LowConfidenceItem {
+                item_id: row.get(0)?,
+                lot_number: row.get(1)?,
+                raw_title: row.get(2)?,
+                extracted_brand: row.get(3)?,
+                extracted_model: row.get(4)?,
+                category: row.get(5)?,
+                current_status: row.get(6)?,
+                nlp_confidence: row.get(7)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+    Ok(items)
+}
+
+// ============================================================
+// Item Split / Merge Commands
+// ============================================================
+
+#[derive(Debug, Deserialize)]
+pub struct SplitPiece {
+    pub title: String,
+    pub retail_price: f64,
+    /// Units in this piece; by default the original's quantity is shared
+    /// evenly across the pieces (at least 1 each)
+    #[serde(default)]
+    pub quantity: Option<i32>,
+}
+
+/// Quantity of piece `index` of `count` when the original's `quantity` is
+/// shared evenly; earlier pieces take the remainder
+fn split_quantity(quantity: i32, index: usize, count: usize) -> i32 {
+    let count = count.max(1) as i32;
+    let index = index as i32;
+    (quantity / count + i32::from(index < quantity % count)).max(1)
+}
+
+#[tauri::command]
+fn split_item(
+    item_id: String,
+    pieces: Vec<SplitPiece>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<String>, String> {
+    let mut db = state.db.lock().map_err(|e| e.to_string())?;
+    split_item_rows(&mut db, &item_id, &pieces)
+}
+
+/// Replace an item with `pieces`: cost and min price are shared by retail
+/// value, quantity per piece or evenly, and Listed pieces keep the
+/// original's listing time. The original is kept as Scrap for history.
+fn split_item_rows(
+    db: &mut Database,
+    item_id: &str,
+    pieces: &[SplitPiece],
+) -> Result<Vec<String>, String> {
+    if pieces.is_empty() {
+        return Err("At least one piece is required to split an item".to_string());
+    }
+    if pieces
+        .iter()
+        .any(|p| p.title.trim().is_empty() || p.retail_price < 0.0)
+    {
+        return Err("Every piece needs a title and a non-negative retail price".to_string());
+    }
+    if pieces.iter().any(|p| p.quantity.is_some_and(|q| q < 1)) {
+        return Err("Piece quantities must be at least 1".to_string());
+    }
+
+    let tx = db.conn.transaction().map_err(|e| e.to_string())?;
+
+    struct Original {
+        manifest_id: String,
+        lot_number: Option<String>,
+        vendor_code: Option<String>,
+        source: Option<String>,
+        condition: Option<String>,
+        cost_price: f64,
+        min_price: f64,
+        current_status: String,
+        auction_id: Option<String>,
+        quantity: i32,
+        listed_at: Option<String>,
+    }
+    let original = tx
+        .query_row(
+            "SELECT manifest_id, lot_number, vendor_code, source, condition,
+                    cost_price, min_price, current_status, auction_id,
+                    COALESCE(quantity, 1), listed_at
+             FROM inventory_items WHERE id = ?1",
+            rusqlite::params![item_id],
+            |row| {
+                Ok(Original {
+                    manifest_id: row.get(0)?,
+                    lot_number: row.get(1)?,
+                    vendor_code: row.get(2)?,
+                    source: row.get(3)?,
+                    condition: row.get(4)?,
+                    cost_price: row.get(5)?,
+                    min_price: row.get(6)?,
+                    current_status: row.get::<_, Option<String>>(7)?.unwrap_or_default(),
+                    auction_id: row.get(8)?,
+                    quantity: row.get(9)?,
+                    listed_at: row.get(10)?,
+                })
+            },
+        )
+        .map_err(|e| format!("Item not found: {}", e))?;
+
+    if !matches!(
+        original.current_status.as_str(),
+        "InStock" | "Listed" | "Unsold"
+    ) {
+        return Err(format!(
+            "Cannot split an item with status {}",
+            original.current_status
+        ));
+    }
+
+    // Allocate cost/min price proportionally to each piece's retail value
+    let total_piece_retail: f64 = pieces.iter().map(|p| p.retail_price).sum();
+    let nlp_extractor =
+        nlp::EntityExtractor::new().with_normalize_steps(nlp::load_normalize_steps(&tx));
+    let mut new_ids = Vec::with_capacity(pieces.len());
+
+    for (index, piece) in pieces.iter().enumerate() {
+        let share = if total_piece_retail > 0.0 {
+            piece.retail_price / total_piece_retail
+        } else {
+            1.0 / pieces.len() as f64
+        };
+        let cost = (original.cost_price * share * 100.0).round() / 100.0;
+        let min_price = (original.min_price * share * 100.0).round() / 100.0;
+        let lot_number = original
+            .lot_number
+            .as_ref()
+            .map(|lot| format!("{}-{}", lot, index + 1));
+        let entities = nlp_extractor.extract(&piece.title);
+        let quantity = piece
+            .quantity
+            .unwrap_or_else(|| split_quantity(original.quantity, index, pieces.len()));
+
+        let new_id = uuid::Uuid::new_v4().to_string();
+        tx.execute(
+            "INSERT INTO inventory_items
+             (id, manifest_id, lot_number, raw_title, vendor_code, source, condition,
+              normalized_title, extracted_brand, extracted_model, category,
+              retail_price, cost_price, min_price, quantity, current_status, auction_id, listed_at,
+              nlp_confidence, gross_margin_pct, split_from_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?20, ?15, ?16,
+                     CASE WHEN ?15 = 'Listed' THEN COALESCE(?21, CURRENT_TIMESTAMP) ELSE NULL END,
+                     ?17, ?18, ?19)",
+            rusqlite::params![
+                new_id,
+                original.manifest_id,
+                lot_number,
+                piece.title.trim(),
+                original.vendor_code,
+                original.source,
+                original.condition,
+                entities.normalized_title,
+                entities.brand,
+                entities.model,
+                entities.category,
+                piece.retail_price,
+                cost,
+                min_price,
+                original.current_status,
+                original.auction_id,
+                entities.confidence.overall(),
+                pricing::gross_margin_pct(cost, min_price),
+                item_id,
+                quantity,
+                original.listed_at,
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        new_ids.push(new_id);
+    }
+
+    // Keep the original row for history, but take it out of circulation
+    tx.execute(
+        "UPDATE inventory_items SET current_status = 'Scrap', auction_id = NULL WHERE id = ?1",
+        rusqlite::params![item_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    if let Some(auction_id) = &original.auction_id {
+        tx.execute(
+            "UPDATE auctions SET total_lots = (
+                SELECT COUNT(*) FROM inventory_items WHERE auction_id = ?1
+             ) WHERE id = ?1",
+            rusqlite::params![auction_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(new_ids)
+}
+
+#[tauri::command]
+fn merge_items(
+    item_ids: Vec<String>,
+    combined_title: String,
+    state: tauri::State<AppState>,
+) -> Result<String, String> {
+    let mut ids = item_ids
+        .into_iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect::<Vec<_>>();
+    let mut seen = std::collections::HashSet::new();
+    ids.retain(|id| seen.insert(id.clone()));
+    if ids.len() < 2 {
+        return Err("At least two items are required to merge".to_string());
+    }
+    if combined_title.trim().is_empty() {
+        return Err("Combined title is required".to_string());
+    }
+
+    let mut db = state.db.lock().map_err(|e| e.to_string())?;
+    let tx = db.conn.transaction().map_err(|e| e.to_string())?;
+
+    struct Source {
+        manifest_id: String,
+        lot_number: Option<String>,
+        source: Option<String>,
+        condition: Option<String>,
+        quantity: i32,
+        retail_price: f64,
+        cost_price: f64,
+        min_price: f64,
+        current_status: String,
+        auction_id: Option<String>,
+    }
+    let mut sources = Vec::with_capacity(ids.len());
+    for id in &ids {
+        let source = tx
+            .query_row(
+                "SELECT manifest_id, lot_number, source, condition, quantity,
+                        retail_price, cost_price, min_price, current_status, auction_id
+                 FROM inventory_items WHERE id = ?1",
+                rusqlite::params![id],
+                |row| {
+                    Ok(Source {
+                        manifest_id: row.get(0)?,
+                        lot_number: row.get(1)?,
+                        source: row.get(2)?,
+                        condition: row.get(3)?,
+                        quantity: row.get::<_, Option<i32>>(4)?.unwrap_or(1),
+                        retail_price: row.get(5)?,
+                        cost_price: row.get(6)?,
+                        min_price: row.get(7)?,
+                        current_status: row.get::<_, Option<String>>(8)?.unwrap_or_default(),
+                        auction_id: row.get(9)?,
+                    })
+                },
+            )
+            .map_err(|e| format!("Item {} not found: {}", id, e))?;
+        if !matches!(
+            source.current_status.as_str(),
+            "InStock" | "Listed" | "Unsold"
+        ) {
+            return Err(format!(
+                "Cannot merge item {} with status {}",
+                id, source.current_status
+            ));
+        }
+        sources.push(source);
+    }
+
+    let first = &sources[0];
+    let retail_price: f64 = sources.iter().map(|s| s.retail_price).sum();
+    let cost_price: f64 = sources.iter().map(|s| s.cost_price).sum();
+    let min_price: f64 = sources.iter().map(|s| s.min_price).sum();
+    let quantity: i32 = sources.iter().map(|s| s.quantity).sum();
+    let same_source = sources.iter().all(|s| s.source == first.source);
+    let source = if same_source {
+        first.source.clone()
+    } else {
+        Some("Mixed".to_string())
+    };
+
+    // Stay in the auction only if every original is listed in the same one
+    let shared_auction = first.auction_id.clone().filter(|auction_id| {
+        sources.iter().all(|s| {
+            s.current_status == "Listed" && s.auction_id.as_deref() == Some(auction_id.as_str())
+        })
+    });
+    let status = if shared_auction.is_some() {
+        "Listed"
+    } else {
+        "InStock"
+    };
+
+    let entities = nlp::EntityExtractor::new()
+        .with_normalize_steps(nlp::load_normalize_steps(&tx))
+        .extract(&combined_title);
+    let new_id = uuid::Uuid::new_v4().to_string();
+    tx.execute(
+        "INSERT INTO inventory_items
+         (id, manifest_id, lot_number, raw_title, source, condition,
+          normalized_title, extracted_brand, extracted_model, category,
+          retail_price, cost_price, min_price, quantity, current_status, auction_id, listed_at,
+          nlp_confidence)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                 CASE WHEN ?15 = 'Listed' THEN CURRENT_TIMESTAMP ELSE NULL END, ?17)",
+        rusqlite::params![
+            new_id,
+            first.manifest_id,
+            first.lot_number,
+            combined_title.trim(),
+            source,
+            first.condition,
+            entities.normalized_title,
+            entities.brand,
+            entities.model,
+            entities.category,
+            (retail_price * 100.0).round() / 100.0,
+            (cost_price * 100.0).round() / 100.0,
+            (min_price * 100.0).round() / 100.0,
+            quantity,
+            status,
+            shared_auction,
+            entities.confidence.overall(),
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut touched_auctions: Vec<String> = sources
+        .iter()
+        .filter_map(|s| s.auction_id.clone())
+        .collect();
+    touched_auctions.sort();
+    touched_auctions.dedup();
+
+    for id in &ids {
+        tx.execute(
+            "UPDATE inventory_items
+             SET current_status = 'Scrap', auction_id = NULL, merged_into_id = ?1
+             WHERE id = ?2",
+            rusqlite::params![new_id, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    for auction_id in &touched_auctions {
+        tx.execute(
+            "UPDATE auctions SET total_lots = (
+                SELECT COUNT(*) FROM inventory_items WHERE auction_id = ?1
+             ) WHERE id = ?1",
+            rusqlite::params![auction_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(new_id)
+}
+
+// ============================================================
+// Pricing Rules Commands
+// ============================================================
+
+#[tauri::command]
+fn get_pricing_rules(state: tauri::State<AppState>) -> Result<Vec<pricing::PricingRule>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    pricing::get_pricing_rules(&db.conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn update_pricing_rule(
+    condition_category: String,
+    level: i32,
+    multiplier: f64,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    pricing::update_pricing_rule(&db.conn, &condition_category, level, multiplier)
+        .map_err(|e| e.to_string())
+}
+
+/// Price a hypothetical item without importing it. `category` is a condition
+/// label or pricing category ("Used - Good", "Broken"); defaults to "New".
+#[tauri::command]
+fn simulate_pricing(
+    retail_price: f64,
+    source: String,
+    category: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<pricing::PricingBreakdown, AppError> {
+    if retail_price < 0.0 || !retail_price.is_finite() {
+        return Err(AppError::Validation(
+            "Retail price must be a non-negative number".to_string(),
+        ));
+    }
+    let db = state.db.lock()?;
+    let engine = pricing::PricingEngine::new(&db.conn)?;
+    let condition = category
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| "New".to_string());
+    Ok(engine.simulate(retail_price, source.trim(), &condition))
+}
+
+#[tauri::command]
+fn recalculate_prices(
+    auction_id: String,
+    vendor_costs: std::collections::HashMap<String, f64>,
+    condition_margins_by_supplier: Option<
+        std::collections::HashMap<String, std::collections::HashMap<String, f64>>,
+    >,
+    condition_margins: Option<std::collections::HashMap<String, f64>>,
+    allow_below_cost: Option<bool>,
+    state: tauri::State<AppState>,
+) -> Result<i32, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+
+    // Get all items in the auction
+    let mut stmt = db
+        .conn
+        .prepare(
+            "SELECT id, retail_price, source, condition FROM inventory_items WHERE auction_id = ?1",
+        )
+        .map_err(|e| e.to_string())?;
+
+    struct ItemData {
+        id: String,
+        retail: f64,
+        source: String,
+        condition: String,
+    }
+    let items: Vec<ItemData> = stmt
+        .query_map(rusqlite::params![auction_id], |row| {
+            Ok(ItemData {
+                id: row.get(0)?,
+                retail: row.get::<_, Option<f64>>(1)?.unwrap_or(0.0),
+                source: row
                     .get::<_, Option<String>>(2)?
                     .unwrap_or_else(|| "".to_string()),
                 condition: row
@@ -577,7 +2095,7 @@ fn recalculate_prices(
         .collect::<rusqlite::Result<Vec<_>>>()
         .map_err(|e| e.to_string())?;
 
-    let mut count = 0;
+    let mut updates = Vec::with_capacity(items.len());
     for item in &items {
         let cost_pct = vendor_costs.get(&item.source).copied().unwrap_or(0.15); // default 15%
         let cost_price = (item.retail * cost_pct * 100.0).round() / 100.0;
@@ -595,17 +2113,474 @@ fn recalculate_prices(
             })
             .unwrap_or(0.10); // default 10%
         let new_min_price = (cost_price + (item.retail * margin_pct)).ceil();
+        pricing::check_min_price_covers_cost(
+            cost_price,
+            new_min_price,
+            allow_below_cost.unwrap_or(false),
+        )
+        .map_err(|e| format!("Item {}: {}", item.id, e))?;
+        updates.push((&item.id, cost_price, new_min_price));
+    }
 
+    // Checked up front so a rejected price leaves the auction untouched
+    for (id, cost_price, new_min_price) in &updates {
         db.conn
             .execute(
-                "UPDATE inventory_items SET min_price = ?1, cost_price = ?2 WHERE id = ?3",
-                rusqlite::params![new_min_price, cost_price, item.id],
+                "UPDATE inventory_items SET min_price = ?1, cost_price = ?2, gross_margin_pct = ?3
+                 WHERE id = ?4",
+                rusqlite::params![
+                    new_min_price,
+                    cost_price,
+                    pricing::gross_margin_pct(*cost_price, *new_min_price),
+                    id
+                ],
             )
             .map_err(|e| e.to_string())?;
-        count += 1;
     }
 
-    Ok(count)
+    Ok(updates.len() as i32)
+}
+
+// ============================================================
+// Vendor Change Commands
+// ============================================================
+
+#[derive(Debug, Serialize)]
+pub struct VendorChangePreview {
+    pub vendor_id: String,
+    pub vendor_name: String,
+    pub old_coefficient: f64,
+    pub new_coefficient: f64,
+    pub affected_items: usize,
+    pub current_total_cost: f64,
+    pub new_total_cost: f64,
+    pub cost_delta: f64,
+}
+
+struct RepricedItem {
+    id: String,
+    cost_price: f64,
+    min_price: f64,
+}
+
+/// Work out how a vendor coefficient change would re-price the InStock items
+/// priced through that vendor. Sold and listed items are never included.
+fn plan_vendor_change(
+    conn: &rusqlite::Connection,
+    vendor_id: &str,
+    new_coefficient: f64,
+) -> Result<(VendorChangePreview, Vec<RepricedItem>), AppError> {
+    if !(new_coefficient > 0.0 && new_coefficient < 1.0) {
+        return Err(AppError::Validation(
+            "Cost coefficient must be between 0 and 1".to_string(),
+        ));
+    }
+
+    let mut engine = pricing::PricingEngine::new(conn)?;
+    let vendor = engine
+        .vendor(vendor_id)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(format!("Active vendor {} not found", vendor_id)))?;
+    engine.set_vendor_coefficient(vendor_id, new_coefficient);
+
+    let mut stmt = conn.prepare(
+        "SELECT id, retail_price, COALESCE(source, ''), COALESCE(condition, 'New'), cost_price
+         FROM inventory_items
+         WHERE current_status = 'InStock'",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, f64>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, f64>(4)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut items = Vec::new();
+    let mut current_total_cost = 0.0;
+    let mut new_total_cost = 0.0;
+    for (id, retail, source, condition, current_cost) in rows {
+        let quote = engine.calculate_cost_for_condition(retail, &source, &condition);
+        if quote.vendor != vendor.name {
+            continue;
+        }
+        current_total_cost += current_cost;
+        new_total_cost += quote.cost;
+        items.push(RepricedItem {
+            id,
+            cost_price: quote.cost,
+            min_price: quote.min_price,
+        });
+    }
+
+    let preview = VendorChangePreview {
+        vendor_id: vendor.id,
+        vendor_name: vendor.name,
+        old_coefficient: vendor.cost_coefficient,
+        new_coefficient,
+        affected_items: items.len(),
+        current_total_cost: (current_total_cost * 100.0).round() / 100.0,
+        new_total_cost: (new_total_cost * 100.0).round() / 100.0,
+        cost_delta: ((new_total_cost - current_total_cost) * 100.0).round() / 100.0,
+    };
+    Ok((preview, items))
+}
+
+#[tauri::command]
+fn preview_vendor_change(
+    vendor_id: String,
+    new_coefficient: f64,
+    state: tauri::State<AppState>,
+) -> Result<VendorChangePreview, AppError> {
+    let db = state.db.lock()?;
+    plan_vendor_change(&db.conn, &vendor_id, new_coefficient).map(|(preview, _)| preview)
+}
+
+#[tauri::command]
+fn apply_vendor_change(
+    vendor_id: String,
+    new_coefficient: f64,
+    reprice_in_stock: bool,
+    allow_below_cost: Option<bool>,
+    state: tauri::State<AppState>,
+) -> Result<VendorChangePreview, AppError> {
+    let mut db = state.db.lock()?;
+    let (preview, items) = plan_vendor_change(&db.conn, &vendor_id, new_coefficient)?;
+
+    let tx = db.conn.transaction()?;
+    tx.execute(
+        "UPDATE vendors SET cost_coefficient = ?1 WHERE id = ?2",
+        rusqlite::params![new_coefficient, vendor_id],
+    )?;
+
+    if reprice_in_stock {
+        for item in &items {
+            pricing::check_min_price_covers_cost(
+                item.cost_price,
+                item.min_price,
+                allow_below_cost.unwrap_or(false),
+            )
+            .map_err(|e| AppError::Validation(format!("Item {}: {}", item.id, e)))?;
+            tx.execute(
+                "UPDATE inventory_items SET cost_price = ?1, min_price = ?2, gross_margin_pct = ?3
+                 WHERE id = ?4 AND current_status = 'InStock'",
+                rusqlite::params![
+                    item.cost_price,
+                    item.min_price,
+                    pricing::gross_margin_pct(item.cost_price, item.min_price),
+                    item.id
+                ],
+            )?;
+        }
+    }
+    tx.commit()?;
+
+    log::info!(
+        "Vendor {} coefficient {} -> {} ({} InStock items{})",
+        preview.vendor_name,
+        preview.old_coefficient,
+        new_coefficient,
+        preview.affected_items,
+        if reprice_in_stock { " re-priced" } else { "" }
+    );
+    Ok(preview)
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepriceSummary {
+    /// Items matching the filters that have a pricing vendor
+    pub matched: usize,
+    /// Items whose cost or min price actually changed
+    pub changed: usize,
+    /// New total cost minus old total cost over the changed items
+    pub cost_delta: f64,
+    pub min_price_delta: f64,
+}
+
+/// Trimmed status/source filters for the re-pricing commands; blank means no
+/// filter. Sold, Buyback and Scrap items are never re-priced.
+fn reprice_filters(
+    status: Option<String>,
+    source: Option<String>,
+) -> Result<(Option<String>, Option<String>), AppError> {
+    let status = status
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    if let Some(status) = status.as_deref() {
+        if matches!(status, "Sold" | "Buyback" | "Scrap") {
+            return Err(AppError::Validation(format!(
+                "{} items are never re-priced",
+                status
+            )));
+        }
+    }
+    let source = source
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    Ok((status, source))
+}
+
+/// Recompute cost and min price with the current vendor coefficients and
+/// condition factors, e.g. after a coefficient change. Optional filters narrow
+/// by status and source (case-insensitive). Sold, Buyback and Scrap items are
+/// never touched; items with no pricing vendor keep their prices. A new min price
+/// below cost fails the whole run unless `allow_below_cost` is set.
+#[tauri::command]
+fn reprice_inventory(
+    status: Option<String>,
+    source: Option<String>,
+    allow_below_cost: Option<bool>,
+    state: tauri::State<AppState>,
+) -> Result<RepriceSummary, AppError> {
+    let (status, source) = reprice_filters(status, source)?;
+
+    let mut db = state.db.lock()?;
+    let engine = pricing::PricingEngine::new(&db.conn)?;
+    let tx = db.conn.transaction()?;
+    let rows = {
+        let mut stmt = tx.prepare(
+            "SELECT id, retail_price, COALESCE(source, ''), COALESCE(condition, 'New'),
+                    cost_price, min_price
+             FROM inventory_items
+             WHERE current_status NOT IN ('Sold', 'Buyback', 'Scrap')
+               AND (?1 IS NULL OR current_status = ?1)
+               AND (?2 IS NULL OR LOWER(TRIM(source)) = LOWER(?2))",
+        )?;
+        let rows = stmt
+            .query_map(rusqlite::params![status, source], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, f64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, f64>(4)?,
+                    row.get::<_, f64>(5)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows
+    };
+
+    let mut summary = RepriceSummary {
+        matched: 0,
+        changed: 0,
+        cost_delta: 0.0,
+        min_price_delta: 0.0,
+    };
+    for (id, retail, source, condition, old_cost, old_min) in rows {
+        if engine.vendor_for_source(&source).is_none() {
+            continue;
+        }
+        summary.matched += 1;
+        let quote = engine.calculate_cost_for_condition(retail, &source, &condition);
+        if (quote.cost - old_cost).abs() < 0.005 && (quote.min_price - old_min).abs() < 0.005 {
+            continue;
+        }
+        pricing::check_min_price_covers_cost(
+            quote.cost,
+            quote.min_price,
+            allow_below_cost.unwrap_or(false),
+        )
+        .map_err(|e| AppError::Validation(format!("Item {}: {}", id, e)))?;
+        tx.execute(
+            "UPDATE inventory_items SET cost_price = ?1, min_price = ?2, gross_margin_pct = ?3
+             WHERE id = ?4",
+            rusqlite::params![
+                quote.cost,
+                quote.min_price,
+                pricing::gross_margin_pct(quote.cost, quote.min_price),
+                id
+            ],
+        )?;
+        summary.changed += 1;
+        summary.cost_delta += quote.cost - old_cost;
+        summary.min_price_delta += quote.min_price - old_min;
+    }
+    tx.commit()?;
+
+    summary.cost_delta = (summary.cost_delta * 100.0).round() / 100.0;
+    summary.min_price_delta = (summary.min_price_delta * 100.0).round() / 100.0;
+    log::info!(
+        "Re-priced {} of {} items (cost delta {:.2})",
+        summary.changed,
+        summary.matched,
+        summary.cost_delta
+    );
+    Ok(summary)
+}
+
+#[derive(Debug, Serialize)]
+pub struct MarginRepriceSummary {
+    /// Items matching the filters with a positive cost
+    pub matched: usize,
+    /// Items whose min price actually changed
+    pub changed: usize,
+    /// Lowest and highest resulting min price over the matched items
+    pub min_price_low: Option<f64>,
+    pub min_price_high: Option<f64>,
+}
+
+/// Set min price so each item's gross margin hits `target_margin_pct` (percent of
+/// the min price): min = cost / (1 − target). Filters work as in
+/// `reprice_inventory`; items without a positive cost are skipped.
+#[tauri::command]
+fn reprice_to_margin(
+    target_margin_pct: f64,
+    status: Option<String>,
+    source: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<MarginRepriceSummary, AppError> {
+    if !(0.0..100.0).contains(&target_margin_pct) {
+        return Err(AppError::Validation(format!(
+            "target_margin_pct must be at least 0 and below 100, got {}",
+            target_margin_pct
+        )));
+    }
+    let (status, source) = reprice_filters(status, source)?;
+    let divisor = 1.0 - target_margin_pct / 100.0;
+
+    let mut db = state.db.lock()?;
+    let tx = db.conn.transaction()?;
+    let rows = {
+        let mut stmt = tx.prepare(
+            "SELECT id, cost_price, min_price
+             FROM inventory_items
+             WHERE current_status NOT IN ('Sold', 'Buyback', 'Scrap')
+               AND cost_price > 0
+               AND (?1 IS NULL OR current_status = ?1)
+               AND (?2 IS NULL OR LOWER(TRIM(source)) = LOWER(?2))",
+        )?;
+        let rows = stmt
+            .query_map(rusqlite::params![status, source], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, f64>(1)?,
+                    row.get::<_, f64>(2)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows
+    };
+
+    let mut summary = MarginRepriceSummary {
+        matched: rows.len(),
+        changed: 0,
+        min_price_low: None,
+        min_price_high: None,
+    };
+    for (id, cost, old_min) in rows {
+        let min_price = (cost / divisor * 100.0).round() / 100.0;
+        let low = summary.min_price_low.get_or_insert(min_price);
+        *low = low.min(min_price);
+        let high = summary.min_price_high.get_or_insert(min_price);
+        *high = high.max(min_price);
+        if (min_price - old_min).abs() < 0.005 {
+            continue;
+        }
+        tx.execute(
+            "UPDATE inventory_items SET min_price = ?1, gross_margin_pct = ?2 WHERE id = ?3",
+            rusqlite::params![min_price, pricing::gross_margin_pct(cost, min_price), id],
+        )?;
+        summary.changed += 1;
+    }
+    tx.commit()?;
+
+    log::info!(
+        "Re-priced {} of {} items to a {}% margin",
+        summary.changed,
+        summary.matched,
+        target_margin_pct
+    );
+    Ok(summary)
+}
+
+#[derive(Debug, Serialize)]
+pub struct PricingViolation {
+    pub id: String,
+    pub lot_number: Option<String>,
+    pub vendor: String,
+    pub current_status: String,
+    pub cost_price: f64,
+    pub min_price: f64,
+    pub expected_min_price: f64,
+}
+
+/// Items not yet sold whose min_price is below cost + vendor margin (scaled by the
+/// condition factor). With `auto_correct`, raises them to the expected floor.
+#[tauri::command]
+fn validate_pricing_integrity(
+    auto_correct: Option<bool>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<PricingViolation>, AppError> {
+    let mut db = state.db.lock()?;
+    let engine = pricing::PricingEngine::new(&db.conn)?;
+
+    let mut stmt = db.conn.prepare(
+        "SELECT id, lot_number, COALESCE(source, ''), COALESCE(condition, 'New'),
+                current_status, retail_price, cost_price, min_price
+         FROM inventory_items
+         WHERE current_status NOT IN ('Sold', 'Scrap')
+         ORDER BY lot_number",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, f64>(5)?,
+                row.get::<_, f64>(6)?,
+                row.get::<_, f64>(7)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    drop(stmt);
+
+    let mut violations = Vec::new();
+    for (id, lot_number, source, condition, current_status, retail, cost, min_price) in rows {
+        let Some((vendor, expected)) = engine.min_price_floor(retail, cost, &source, &condition)
+        else {
+            continue;
+        };
+        if min_price + 0.005 < expected {
+            violations.push(PricingViolation {
+                id,
+                lot_number,
+                vendor: vendor.name.clone(),
+                current_status,
+                cost_price: cost,
+                min_price,
+                expected_min_price: expected,
+            });
+        }
+    }
+
+    if auto_correct.unwrap_or(false) && !violations.is_empty() {
+        let tx = db.conn.transaction()?;
+        for violation in &violations {
+            tx.execute(
+                "UPDATE inventory_items SET min_price = ?1, gross_margin_pct = ?2 WHERE id = ?3",
+                rusqlite::params![
+                    violation.expected_min_price,
+                    pricing::gross_margin_pct(violation.cost_price, violation.expected_min_price),
+                    violation.id
+                ],
+            )?;
+        }
+        tx.commit()?;
+        log::info!(
+            "Raised min_price to the vendor floor on {} items",
+            violations.len()
+        );
+    }
+
+    Ok(violations)
 }
 
 // ============================================================
@@ -732,6 +2707,204 @@ fn get_item_history(
     Ok(entries)
 }
 
+// ============================================================
+// Item Detail Command
+// ============================================================
+
+/// Latest auction result recorded for an item
+#[derive(Debug, Serialize)]
+pub struct ItemSaleOutcome {
+    pub auction_id: String,
+    pub auction_name: String,
+    pub item_status: String,
+    pub high_bid: f64,
+    pub winning_bidder: Option<String>,
+    pub bidder_id: Option<String>,
+    pub commission_amount: f64,
+    pub net_profit: f64,
+    pub is_paid: bool,
+    pub recorded_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ItemDetail {
+    #[serde(flatten)]
+    pub item: db::InventoryItemRow,
+    pub manifest_filename: Option<String>,
+    pub sale: Option<ItemSaleOutcome>,
+    /// Sum of profit adjustments (credits positive)
+    pub adjustments_total: f64,
+    /// Margin on the sale price when sold, otherwise the margin at min price
+    pub margin_percent: Option<f64>,
+}
+
+#[tauri::command]
+fn get_item_detail(item_id: String, state: tauri::State<AppState>) -> Result<ItemDetail, AppError> {
+    let db = state.db.lock()?;
+    let item = db
+        .conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM inventory_items WHERE id = ?1",
+                db::INVENTORY_ITEM_COLUMNS
+            ),
+            rusqlite::params![item_id],
+            db::map_inventory_item_row,
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                AppError::NotFound(format!("Item {} not found", item_id))
+            }
+            other => AppError::from(other),
+        })?;
+
+    let manifest_filename: Option<String> = db
+        .conn
+        .query_row(
+            "SELECT source_filename FROM manifests WHERE id = ?1",
+            rusqlite::params![item.manifest_id],
+            |row| row.get(0),
+        )
+        .ok();
+
+    let sale = match db.conn.query_row(
+        "SELECT ar.auction_id, a.name,
+                COALESCE(ar.item_status,
+                         CASE WHEN ar.is_buyback THEN 'Buyback'
+                              WHEN COALESCE(ar.high_bid, 0) > 0 THEN 'Sold'
+                              ELSE 'Unsold' END),
+                COALESCE(ar.high_bid, 0), ar.winning_bidder, ar.bidder_id,
+                COALESCE(ar.commission_amount, 0), COALESCE(ar.net_profit, 0),
+                COALESCE(ar.is_paid, 0), ar.created_at
+         FROM auction_results ar
+         JOIN auctions a ON a.id = ar.auction_id
+         WHERE ar.item_id = ?1
+         ORDER BY ar.created_at DESC
+         LIMIT 1",
+        rusqlite::params![item_id],
+        |row| {
+            Ok(ItemSaleOutcome {
+                auction_id: row.get(0)?,
+                auction_name: row.get(1)?,
+                item_status: row.get(2)?,
+                high_bid: row.get(3)?,
+                winning_bidder: row.get(4)?,
+                bidder_id: row.get(5)?,
+                commission_amount: row.get(6)?,
+                net_profit: row.get(7)?,
+                is_paid: row.get(8)?,
+                recorded_at: row.get(9)?,
+            })
+        },
+    ) {
+        Ok(sale) => Some(sale),
+        Err(rusqlite::Error::QueryReturnedNoRows) => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    let adjustments_total: f64 = db.conn.query_row(
+        "SELECT COALESCE(SUM(amount), 0) FROM adjustments WHERE item_id = ?1",
+        rusqlite::params![item_id],
+        |row| row.get(0),
+    )?;
+
+    let price = match &sale {
+        Some(sale) if sale.item_status == "Sold" => sale.high_bid,
+        _ => item.min_price,
+    };
+    let margin_percent = if price > 0.0 {
+        Some(((price - item.cost_price) / price * 10000.0).round() / 100.0)
+    } else {
+        None
+    };
+
+    Ok(ItemDetail {
+        item,
+        manifest_filename,
+        sale,
+        adjustments_total,
+        margin_percent,
+    })
+}
+
+// ============================================================
+// Scrap Candidate Commands
+// ============================================================
+
+#[derive(Debug, Serialize)]
+pub struct ScrapCandidate {
+    pub id: String,
+    pub lot_number: Option<String>,
+    pub title: String,
+    pub current_status: String,
+    pub cost_price: f64,
+    pub buyback_count: i64,
+    pub age_days: i64,
+    pub reasons: Vec<String>,
+}
+
+/// Unsold items bought back `min_buybacks`+ times or older than `max_age_days`,
+/// ranked by tied-up cost. Thresholds default to the scrap_* settings.
+#[tauri::command]
+fn get_scrap_candidates(
+    min_buybacks: Option<i64>,
+    max_age_days: Option<i64>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<ScrapCandidate>, AppError> {
+    let db = state.db.lock()?;
+    let min_buybacks = min_buybacks
+        .or_else(|| db.get_setting("scrap_min_buybacks")?.trim().parse().ok())
+        .unwrap_or(2)
+        .max(1);
+    let max_age_days = max_age_days
+        .or_else(|| db.get_setting("scrap_max_age_days")?.trim().parse().ok())
+        .unwrap_or(120)
+        .max(0);
+
+    let mut stmt = db.conn.prepare(
+        "SELECT id, lot_number, title, current_status, cost_price, buyback_count, age_days
+         FROM (
+             SELECT i.id, i.lot_number,
+                    COALESCE(i.normalized_title, i.raw_title) AS title,
+                    i.current_status, i.cost_price,
+                    (SELECT COUNT(*) FROM auction_results ar
+                     WHERE ar.item_id = i.id
+                       AND (ar.is_buyback = 1 OR ar.item_status = 'Buyback')) AS buyback_count,
+                    CAST(julianday('now') - julianday(i.created_at) AS INTEGER) AS age_days
+             FROM inventory_items i
+             WHERE i.current_status NOT IN ('Sold', 'Scrap')
+         )
+         WHERE buyback_count >= ?1 OR age_days >= ?2
+         ORDER BY cost_price DESC, age_days DESC",
+    )?;
+
+    let candidates = stmt
+        .query_map(rusqlite::params![min_buybacks, max_age_days], |row| {
+            let buyback_count: i64 = row.get(5)?;
+            let age_days: i64 = row.get(6)?;
+            let mut reasons = Vec::new();
+            if buyback_count >= min_buybacks {
+                reasons.push(format!("Bought back {} times", buyback_count));
+            }
+            if age_days >= max_age_days {
+                reasons.push(format!("Unsold for {} days", age_days));
+            }
+            Ok(ScrapCandidate {
+                id: row.get(0)?,
+                lot_number: row.get(1)?,
+                title: row.get(2)?,
+                current_status: row.get(3)?,
+                cost_price: row.get(4)?,
+                buyback_count,
+                age_days,
+                reasons,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(candidates)
+}
+
 // ============================================================
 // Maintenance Commands
 // ============================================================
@@ -746,6 +2919,8 @@ fn wipe_database(state: tauri::State<AppState>) -> Result<(), String> {
         DELETE FROM auction_reports;
         DELETE FROM auction_results;
         DELETE FROM historical_sales;
+        DELETE FROM adjustments;
+        DELETE FROM cash_sales;
         DELETE FROM inventory_items;
         DELETE FROM auctions;
         DELETE FROM manifests;
@@ -757,6 +2932,402 @@ fn wipe_database(state: tauri::State<AppState>) -> Result<(), String> {
     Ok(())
 }
 
+/// What `purge_data` removes. Serialized as `"all"`, `{"manifests_before": "2026-01-31"}`
+/// or `{"test_prefix": "test_"}`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum PurgeScope {
+    /// Every manifest and auction
+    All,
+    /// Manifests imported before this date (YYYY-MM-DD)
+    ManifestsBefore(String),
+    /// Manifests whose file name and auctions whose name start with this prefix
+    TestPrefix(String),
+}
+
+#[derive(Debug, Serialize)]
+struct PurgeSummary {
+    /// Rows deleted per table (inventory_items_unlisted counts items reset to InStock)
+    deleted: std::collections::BTreeMap<&'static str, usize>,
+    /// Matching manifests/auctions kept because they have paid auction results
+    skipped_manifests: usize,
+    skipped_auctions: usize,
+}
+
+/// Delete test/demo manifests and auctions with everything hanging off them.
+/// Manifests with a paid result on any item and auctions with any paid result
+/// are left untouched. Items of a purged auction that belong to a kept manifest
+/// go back to InStock, as with `delete_auction`.
+#[tauri::command]
+fn purge_data(scope: PurgeScope, state: tauri::State<AppState>) -> Result<PurgeSummary, AppError> {
+    let mut db = state.db.lock()?;
+    purge_scope(&mut db, &scope)
+}
+
+/// `purge_data` against an open database, in one transaction
+fn purge_scope(db: &mut Database, scope: &PurgeScope) -> Result<PurgeSummary, AppError> {
+    let (manifest_filter, auction_filter, param) = match scope {
+        PurgeScope::All => ("1 = 1", "1 = 1", String::new()),
+        PurgeScope::ManifestsBefore(date) => {
+            chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| {
+                AppError::Validation(format!("Invalid date '{}', expected YYYY-MM-DD", date))
+            })?;
+            ("import_date < ?1", "0", date.trim().to_string())
+        }
+        PurgeScope::TestPrefix(prefix) => {
+            let prefix = prefix.trim();
+            if prefix.is_empty() {
+                return Err(AppError::Validation("Prefix must not be empty".into()));
+            }
+            // A literal match: LIKE would treat '_' and '%' in the prefix as wildcards
+            (
+                "substr(source_filename, 1, length(?1)) = ?1",
+                "substr(name, 1, length(?1)) = ?1",
+                prefix.to_string(),
+            )
+        }
+    };
+    // ?1 is only bound when the filters reference it
+    let bind = |sql: &str| -> Vec<String> {
+        if sql.contains("?1") {
+            vec![param.clone()]
+        } else {
+            Vec::new()
+        }
+    };
+
+    let tx = db.conn.transaction()?;
+
+    let protected_manifests = "SELECT i.manifest_id FROM inventory_items i
+         JOIN auction_results ar ON ar.item_id = i.id
+         WHERE ar.is_paid = 1";
+    let protected_auctions = "SELECT auction_id FROM auction_results WHERE is_paid = 1";
+
+    tx.execute_batch(
+        "CREATE TEMP TABLE IF NOT EXISTS purge_manifests (id TEXT PRIMARY KEY);
+         CREATE TEMP TABLE IF NOT EXISTS purge_auctions (id TEXT PRIMARY KEY);
+         CREATE TEMP TABLE IF NOT EXISTS purge_items (id TEXT PRIMARY KEY);
+         DELETE FROM purge_manifests;
+         DELETE FROM purge_auctions;
+         DELETE FROM purge_items;",
+    )?;
+    let sql = format!(
+        "INSERT INTO purge_manifests SELECT id FROM manifests
+         WHERE {} AND id NOT IN ({})",
+        manifest_filter, protected_manifests
+    );
+    tx.execute(&sql, rusqlite::params_from_iter(bind(&sql)))?;
+    let sql = format!(
+        "INSERT INTO purge_auctions SELECT id FROM auctions
+         WHERE {} AND id NOT IN ({})",
+        auction_filter, protected_auctions
+    );
+    tx.execute(&sql, rusqlite::params_from_iter(bind(&sql)))?;
+    tx.execute(
+        "INSERT INTO purge_items SELECT id FROM inventory_items
+         WHERE manifest_id IN (SELECT id FROM purge_manifests)",
+        [],
+    )?;
+
+    let sql = format!(
+        "SELECT COUNT(*) FROM manifests WHERE {} AND id IN ({})",
+        manifest_filter, protected_manifests
+    );
+    let skipped_manifests: i64 =
+        tx.query_row(&sql, rusqlite::params_from_iter(bind(&sql)), |r| r.get(0))?;
+    let sql = format!(
+        "SELECT COUNT(*) FROM auctions WHERE {} AND id IN ({})",
+        auction_filter, protected_auctions
+    );
+    let skipped_auctions: i64 =
+        tx.query_row(&sql, rusqlite::params_from_iter(bind(&sql)), |r| r.get(0))?;
+
+    // Kept auctions losing lots to a purged manifest; their total_lots is recounted below
+    let recount_auctions: Vec<String> = tx
+        .prepare(
+            "SELECT DISTINCT auction_id FROM inventory_items
+             WHERE id IN (SELECT id FROM purge_items)
+               AND auction_id IS NOT NULL
+               AND auction_id NOT IN (SELECT id FROM purge_auctions)",
+        )?
+        .query_map([], |r| r.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    // Children first; foreign keys are enforced
+    let steps: [(&'static str, &str); 9] = [
+        (
+            "historical_sales",
+            "DELETE FROM historical_sales WHERE id IN (
+                 SELECT id FROM auction_results
+                 WHERE item_id IN (SELECT id FROM purge_items)
+                    OR auction_id IN (SELECT id FROM purge_auctions)
+                 UNION
+                 SELECT id FROM cash_sales WHERE item_id IN (SELECT id FROM purge_items)
+             )",
+        ),
+        (
+            "auction_results",
+            "DELETE FROM auction_results
+             WHERE item_id IN (SELECT id FROM purge_items)
+                OR auction_id IN (SELECT id FROM purge_auctions)",
+        ),
+        (
+            "cash_sales",
+            "DELETE FROM cash_sales WHERE item_id IN (SELECT id FROM purge_items)",
+        ),
+        (
+            "adjustments",
+            "DELETE FROM adjustments WHERE item_id IN (SELECT id FROM purge_items)",
+        ),
+        (
+            "auction_reports",
+            "DELETE FROM auction_reports WHERE auction_id IN (SELECT id FROM purge_auctions)",
+        ),
+        (
+            "inventory_items_unlisted",
+            "UPDATE inventory_items
+             SET current_status = 'InStock', auction_id = NULL, listed_at = NULL
+             WHERE auction_id IN (SELECT id FROM purge_auctions)
+               AND id NOT IN (SELECT id FROM purge_items)",
+        ),
+        (
+            "inventory_items",
+            "DELETE FROM inventory_items WHERE id IN (SELECT id FROM purge_items)",
+        ),
+        (
+            "auctions",
+            "DELETE FROM auctions WHERE id IN (SELECT id FROM purge_auctions)",
+        ),
+        (
+            "manifests",
+            "DELETE FROM manifests WHERE id IN (SELECT id FROM purge_manifests)",
+        ),
+    ];
+    let mut deleted = std::collections::BTreeMap::new();
+    for (table, sql) in steps {
+        deleted.insert(table, tx.execute(sql, [])?);
+    }
+    for auction_id in &recount_auctions {
+        tx.execute(
+            "UPDATE auctions SET total_lots = (SELECT COUNT(*) FROM inventory_items WHERE auction_id = ?1) WHERE id = ?1",
+            rusqlite::params![auction_id],
+        )?;
+    }
+
+    tx.execute_batch(
+        "DROP TABLE temp.purge_manifests;
+         DROP TABLE temp.purge_auctions;
+         DROP TABLE temp.purge_items;",
+    )?;
+    tx.commit()?;
+
+    log::info!("Purged data for {:?}: {:?}", scope, deleted);
+    Ok(PurgeSummary {
+        deleted,
+        skipped_manifests: skipped_manifests as usize,
+        skipped_auctions: skipped_auctions as usize,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrphanItem {
+    pub item_id: String,
+    pub lot_number: Option<String>,
+    pub auction_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrphanResult {
+    pub result_id: String,
+    pub auction_id: String,
+    pub item_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrphanReport {
+    pub items_missing_auction: Vec<OrphanItem>,
+    pub results_missing_item: Vec<OrphanResult>,
+}
+
+#[tauri::command]
+fn find_orphans(state: tauri::State<AppState>) -> Result<OrphanReport, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+
+    let mut item_stmt = db
+        .conn
+        .prepare(
+            "SELECT i.id, i.lot_number, i.auction_id
+             FROM inventory_items i
+             LEFT JOIN auctions a ON a.id = i.auction_id
+             WHERE i.auction_id IS NOT NULL AND a.id IS NULL
+             ORDER BY i.lot_number",
+        )
+        .map_err(|e| e.to_string())?;
+    let items_missing_auction = item_stmt
+        .query_map([], |row| {
+            Ok(OrphanItem {
+                item_id: row.get(0)?,
+                lot_number: row.get(1)?,
+                auction_id: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut result_stmt = db
+        .conn
+        .prepare(
+            "SELECT ar.id, ar.auction_id, ar.item_id
+             FROM auction_results ar
+             LEFT JOIN inventory_items i ON i.id = ar.item_id
+             WHERE i.id IS NULL
+             ORDER BY ar.created_at",
+        )
+        .map_err(|e| e.to_string())?;
+    let results_missing_item = result_stmt
+        .query_map([], |row| {
+            Ok(OrphanResult {
+                result_id: row.get(0)?,
+                auction_id: row.get(1)?,
+                item_id: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(OrphanReport {
+        items_missing_auction,
+        results_missing_item,
+    })
+}
+
+#[tauri::command]
+fn repair_orphans(state: tauri::State<AppState>) -> Result<usize, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    // Only dangling item -> auction links are repaired; orphaned results are left for review
+    db.conn
+        .execute(
+            "UPDATE inventory_items
+             SET current_status = 'InStock', auction_id = NULL, listed_at = NULL
+             WHERE auction_id IS NOT NULL
+               AND auction_id NOT IN (SELECT id FROM auctions)",
+            [],
+        )
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Serialize)]
+pub struct TableRowCount {
+    pub name: String,
+    pub kind: String,
+    pub row_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DbDiagnostics {
+    pub db_version: Option<String>,
+    pub app_version: Option<String>,
+    pub tables: Vec<TableRowCount>,
+    pub wal_size_bytes: Option<u64>,
+}
+
+/// Whether a command currently holds the database lock (never blocks)
+#[tauri::command]
+fn is_busy(state: tauri::State<AppState>) -> db_lock::LockStatus {
+    state.db.status()
+}
+
+/// Recreate derived views and refresh index statistics, e.g. after a bulk external load
+#[tauri::command]
+fn rebuild_derived(state: tauri::State<AppState>) -> Result<db::DerivedRebuild, AppError> {
+    let db = state.db.lock()?;
+    let summary = db.rebuild_derived()?;
+    log::info!(
+        "Rebuilt {} views; {} indexes analyzed",
+        summary.views.len(),
+        summary.index_count
+    );
+    Ok(summary)
+}
+
+/// Relabel historical sales with the current season map so analytics stay consistent
+#[tauri::command]
+fn recompute_seasons(state: tauri::State<AppState>) -> Result<usize, AppError> {
+    let db = state.db.lock()?;
+    let changed = db.recompute_seasons()?;
+    log::info!(
+        "Recomputed seasons: {} historical sales relabelled",
+        changed
+    );
+    Ok(changed)
+}
+
+/// Read-only snapshot of table/view row counts and version settings for support requests
+#[tauri::command]
+fn get_db_diagnostics(state: tauri::State<AppState>) -> Result<DbDiagnostics, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = db
+        .conn
+        .prepare(
+            "SELECT name, type FROM sqlite_master
+             WHERE type IN ('table', 'view')
+               AND name NOT LIKE 'sqlite_%'
+             ORDER BY type, name",
+        )
+        .map_err(|e| e.to_string())?;
+    let objects = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut tables = Vec::with_capacity(objects.len());
+    for (name, kind) in objects {
+        let row_count: i64 = db
+            .conn
+            .query_row(
+                &format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\"")),
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to count {}: {}", name, e))?;
+        tables.push(TableRowCount {
+            name,
+            kind,
+            row_count,
+        });
+    }
+
+    let setting = |key: &str| -> Option<String> {
+        db.conn
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?1",
+                rusqlite::params![key],
+                |row| row.get(0),
+            )
+            .ok()
+    };
+
+    let wal_size_bytes = db
+        .conn
+        .path()
+        .filter(|path| !path.is_empty())
+        .and_then(|path| std::fs::metadata(format!("{}-wal", path)).ok())
+        .map(|meta| meta.len());
+
+    Ok(DbDiagnostics {
+        db_version: setting("db_version"),
+        app_version: setting("app_version"),
+        tables,
+        wal_size_bytes,
+    })
+}
+
 // ============================================================
 // Main
 // ============================================================
@@ -773,18 +3344,35 @@ fn main() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
-        .manage(AppState { db: Mutex::new(db) })
+        .manage(AppState {
+            db: db_lock::DbMutex::new(db),
+        })
         .invoke_handler(tauri::generate_handler![
             save_binary_file,
             import_manifest,
+            get_manifests,
+            set_manifest_notes,
+            set_manifest_tags,
             get_inventory_items,
+            get_inventory_by_margin,
+            get_recent_activity,
             get_dashboard_stats,
+            get_valuation,
+            get_facets,
+            get_source_stats,
             get_vendors,
+            import_vendors_csv,
+            clone_vendor,
             get_auction_pnl_list,
+            get_auction_pnl,
             export_inventory_csv,
+            get_sales_analytics,
+            export_sales_analytics_csv,
+            export_hibid_csv,
             update_item_status,
             get_setting,
             save_setting,
+            set_current_user,
             // Condition / Source types
             get_condition_types,
             get_source_types,
@@ -795,10 +3383,31 @@ fn main() {
             update_item_source,
             update_item_sale_order,
             update_item_buybacker,
+            update_item_notes,
+            update_item_prices,
+            set_item_location,
+            set_scrap_value,
+            flag_item,
+            unflag_item,
+            get_flagged_items,
+            find_duplicate_items,
+            get_items_needing_title,
+            update_item_title,
+            // NLP review
+            get_low_confidence_items,
+            // Item split / merge
+            split_item,
+            merge_items,
             // Pricing rules
             get_pricing_rules,
             update_pricing_rule,
+            simulate_pricing,
             recalculate_prices,
+            reprice_inventory,
+            reprice_to_margin,
+            preview_vendor_change,
+            apply_vendor_change,
+            validate_pricing_integrity,
             // Buy-backers
             get_buybackers,
             add_buybacker,
@@ -806,13 +3415,17 @@ fn main() {
             delete_buybacker,
             // Item history (Repeaters)
             get_item_history,
+            get_item_detail,
+            get_scrap_candidates,
             // Auctions
             auctions::create_auction,
             auctions::get_auctions,
+            auctions::get_auctions_filtered,
             auctions::get_auction_by_id,
             auctions::update_auction_status,
             auctions::update_vendor,
             auctions::unassign_item,
+            auctions::reassign_item,
             auctions::get_relistable_inventory_items,
             auctions::assign_items_to_auction,
             auctions::finish_auction,
@@ -822,19 +3435,218 @@ fn main() {
             auctions::get_item_repeater_stats,
             auctions::get_item_first_auction_map,
             auctions::open_report_file,
+            auctions::set_auction_lot_prefix,
+            auctions::set_auction_max_lots,
+            auctions::set_auction_type,
             auctions::rename_auction,
             auctions::delete_auction,
+            auctions::get_items_missing_images,
+            auctions::check_auction_images,
+            auctions::get_auction_items,
             // Reconciliation
             reconciliation::reconcile_auction,
+            reconciliation::reconcile_multi,
+            reconciliation::detect_auction_from_results,
+            reconciliation::revert_lots,
+            reconciliation::export_picklist,
+            reconciliation::estimate_payout,
             reconciliation::get_pl_report,
             reconciliation::get_pl_report_filtered,
             reconciliation::get_auction_summaries,
             reconciliation::get_vendor_breakdown,
+            reconciliation::get_auction_commission,
+            reconciliation::get_bid_spread,
+            reconciliation::get_reconciliation_variance,
+            reconciliation::add_adjustment,
+            reconciliation::record_cash_sale,
+            reconciliation::get_velocity_stats,
+            reconciliation::get_brand_performance,
+            reconciliation::get_status_durations,
+            reconciliation::get_reorder_suggestions,
+            reconciliation::export_consignor_statement,
             // CSV Validation
             csv_parser::validate_csv,
+            csv_parser::validate_hibid_results,
+            csv_parser::probe_csv,
             pallet_manifests::generate_pallet_manifest_report,
             wipe_database,
+            purge_data,
+            find_orphans,
+            repair_orphans,
+            rebuild_derived,
+            recompute_seasons,
+            get_db_diagnostics,
+            is_busy,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_db(label: &str) -> (std::path::PathBuf, Database) {
+        let base_dir =
+            std::env::temp_dir().join(format!("sugarland_{}_{}", label, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&base_dir).expect("Failed to create temp dir");
+        let db_path = base_dir.join("test.db");
+        let db = Database::new(db_path.to_str().expect("Path should be valid UTF-8"))
+            .expect("Failed to create test db");
+        db.conn
+            .execute_batch(
+                "INSERT INTO manifests (id, source_filename) VALUES ('m-test', 'manifest.csv');
+                 INSERT INTO auctions (id, name, status) VALUES ('auction-1', 'Sugarland 42', 'Active');",
+            )
+            .expect("Failed to seed db");
+        (base_dir, db)
+    }
+
+    fn piece(title: &str, retail_price: f64, quantity: Option<i32>) -> SplitPiece {
+        SplitPiece {
+            title: title.to_string(),
+            retail_price,
+            quantity,
+        }
+    }
+
+    #[test]
+    fn import_summary_counts_every_inserted_row() {
+        let (base_dir, mut db) = test_db("import_summary");
+        db.conn
+            .execute(
+                "UPDATE settings SET value = '10' WHERE key = 'min_listable_retail'",
+                [],
+            )
+            .expect("Failed to set min_listable_retail");
+        let csv_path = base_dir.join("manifest.csv");
+        std::fs::write(
+            &csv_path,
+            "LotNumber,Quantity,Title,Retail Price,Source\n\
+             1,2,Samsung 55 inch TV,$400,Best Buy\n\
+             2,1,Phone case,$5,Best Buy\n",
+        )
+        .expect("Failed to write manifest");
+        let path = csv_path.to_str().expect("Path should be valid UTF-8");
+
+        let summary = import_manifest_file(&mut db, path, None, None).expect("import");
+        assert_eq!(summary.items_count, 2);
+        assert_eq!(summary.auto_scrapped, 1);
+        assert_eq!(summary.total_quantity, 3);
+        assert_eq!(summary.total_retail, 405.0);
+
+        // Appending reports the combined manifest
+        let appended =
+            import_manifest_file(&mut db, path, None, Some(summary.id.clone())).expect("append");
+        assert_eq!(appended.items_count, 4);
+        assert_eq!(appended.total_quantity, 6);
+        assert_eq!(appended.total_retail, 810.0);
+        let manifest_count: i64 = db
+            .conn
+            .query_row(
+                "SELECT items_count FROM manifests WHERE id = ?1",
+                rusqlite::params![summary.id],
+                |row| row.get(0),
+            )
+            .expect("manifest");
+        assert_eq!(manifest_count, 4);
+        let _ = std::fs::remove_dir_all(&base_dir);
+    }
+
+    #[test]
+    fn split_item_shares_quantity_and_keeps_listing_time() {
+        let (base_dir, mut db) = test_db("split_item");
+        db.conn
+            .execute(
+                "INSERT INTO inventory_items (id, manifest_id, lot_number, raw_title, retail_price,
+                     cost_price, min_price, quantity, current_status, auction_id, listed_at)
+                 VALUES ('item-1', 'm-test', '7', 'TV and mount', 500.0, 70.0, 120.0, 5, 'Listed',
+                         'auction-1', '2026-03-01 09:30:00')",
+                [],
+            )
+            .expect("Failed to insert item");
+
+        let ids = split_item_rows(
+            &mut db,
+            "item-1",
+            &[piece("TV", 400.0, None), piece("Wall mount", 100.0, None)],
+        )
+        .expect("split");
+        let pieces: Vec<(i32, Option<String>, String)> = ids
+            .iter()
+            .map(|id| {
+                db.conn
+                    .query_row(
+                        "SELECT quantity, listed_at, current_status FROM inventory_items WHERE id = ?1",
+                        rusqlite::params![id],
+                        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                    )
+                    .expect("piece")
+            })
+            .collect();
+        assert_eq!(pieces[0].0, 3);
+        assert_eq!(pieces[1].0, 2);
+        for (_, listed_at, status) in &pieces {
+            assert_eq!(listed_at.as_deref(), Some("2026-03-01 09:30:00"));
+            assert_eq!(status, "Listed");
+        }
+
+        // An explicit piece quantity wins over the even share
+        let ids = split_item_rows(
+            &mut db,
+            &ids[0],
+            &[piece("TV", 400.0, Some(1)), piece("Remote", 0.0, None)],
+        )
+        .expect("split again");
+        let quantity: i32 = db
+            .conn
+            .query_row(
+                "SELECT quantity FROM inventory_items WHERE id = ?1",
+                rusqlite::params![ids[0]],
+                |row| row.get(0),
+            )
+            .expect("piece");
+        assert_eq!(quantity, 1);
+        assert_eq!(split_quantity(1, 1, 2), 1);
+        let _ = std::fs::remove_dir_all(&base_dir);
+    }
+
+    #[test]
+    fn purge_prefix_is_literal_and_recounts_kept_auctions() {
+        let (base_dir, mut db) = test_db("purge");
+        db.conn
+            .execute_batch(
+                "INSERT INTO manifests (id, source_filename) VALUES
+                     ('m-purge', 'test_load.csv'), ('m-keep', 'testXload.csv');
+                 INSERT INTO inventory_items (id, manifest_id, lot_number, raw_title, retail_price,
+                     cost_price, min_price, current_status, auction_id)
+                 VALUES ('item-1', 'm-purge', '1', 'Lamp', 100.0, 14.0, 24.0, 'Listed', 'auction-1'),
+                        ('item-2', 'm-keep', '2', 'Chair', 100.0, 14.0, 24.0, 'Listed', 'auction-1');
+                 UPDATE auctions SET total_lots = 2 WHERE id = 'auction-1';",
+            )
+            .expect("Failed to seed purge data");
+
+        let summary = purge_scope(&mut db, &PurgeScope::TestPrefix("test_".into())).expect("purge");
+        assert_eq!(summary.deleted["manifests"], 1);
+        assert_eq!(summary.deleted["inventory_items"], 1);
+        let kept: i64 = db
+            .conn
+            .query_row(
+                "SELECT COUNT(*) FROM manifests WHERE id = 'm-keep'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("manifest");
+        assert_eq!(kept, 1);
+        let total_lots: i64 = db
+            .conn
+            .query_row(
+                "SELECT total_lots FROM auctions WHERE id = 'auction-1'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("auction");
+        assert_eq!(total_lots, 1);
+        let _ = std::fs::remove_dir_all(&base_dir);
+    }
+}
@@ -9,6 +9,8 @@
 
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
 
 // ============================================================================
 // Структуры данных
@@ -20,6 +22,22 @@ pub struct ExtractedEntities {
     pub brand: Option<String>,
     pub model: Option<String>,
     pub category: Option<String>,
+    pub confidence: EntityConfidence,
+}
+
+/// Уверенность извлечения по каждому полю (0.0 — не найдено, 1.0 — точное совпадение)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntityConfidence {
+    pub brand: f64,
+    pub model: f64,
+    pub category: f64,
+}
+
+impl EntityConfidence {
+    /// Общая уверенность — среднее по полям
+    pub fn overall(&self) -> f64 {
+        (self.brand + self.model + self.category) / 3.0
+    }
 }
 
 // ============================================================================
@@ -85,6 +103,8 @@ const BRANDS: &[&str] = &[
     "Craftsman",
     "Black & Decker",
     "Stanley",
+    // Одежда
+    "Original Penguin",
 ];
 
 // Стоп-слова (не несут смысловой нагрузки)
@@ -201,6 +221,38 @@ const CATEGORIES: &[(&str, &[&str])] = &[
     ),
 ];
 
+// Уверенность для разных способов извлечения
+const EXACT_MATCH_CONFIDENCE: f64 = 1.0;
+const GENERIC_MODEL_CONFIDENCE: f64 = 0.6;
+const UPC_MODEL_CONFIDENCE: f64 = 0.4;
+const CATEGORY_KEYWORD_CONFIDENCE: f64 = 0.8;
+/// Категория взята из подсказки по источнику, а не из названия
+const SOURCE_HINT_CATEGORY_CONFIDENCE: f64 = 0.5;
+
+/// Ключ настройки с картой источник → категория по умолчанию (JSON-объект)
+pub const SOURCE_CATEGORY_HINTS_SETTING: &str = "source_category_hints";
+
+/// Ключ настройки с порядком шагов нормализации (JSON-список `NormalizeStep`)
+pub const NORMALIZE_STEPS_SETTING: &str = "nlp_normalize_steps";
+
+/// Шаг нормализации названия
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum NormalizeStep {
+    /// Удалить стоп-слова ("new", "open box", ...), кроме слов бренда
+    StopWords,
+    /// Убрать спецсимволы; дефис внутри слова ("hi-def") сохраняется
+    SpecialChars,
+    /// Схлопнуть пробелы
+    Whitespace,
+}
+
+/// Порядок по умолчанию: пробелы схлопываются последними, после удаления символов
+pub const DEFAULT_NORMALIZE_STEPS: &[NormalizeStep] = &[
+    NormalizeStep::StopWords,
+    NormalizeStep::SpecialChars,
+    NormalizeStep::Whitespace,
+];
+
 // ============================================================================
 // Regex паттерны для извлечения моделей
 // ============================================================================
@@ -241,6 +293,10 @@ lazy_static! {
 pub struct EntityExtractor {
     brands: Vec<String>,
     categories: Vec<(String, Vec<String>)>,
+    /// Источник (в нижнем регистре) → категория, если в названии нет ключевых слов
+    source_category_hints: HashMap<String, String>,
+    /// Шаги нормализации в порядке выполнения; отсутствующий шаг выключен
+    normalize_steps: Vec<NormalizeStep>,
 }
 
 impl EntityExtractor {
@@ -257,7 +313,44 @@ impl EntityExtractor {
             })
             .collect();
 
-        Self { brands, categories }
+        Self {
+            brands,
+            categories,
+            source_category_hints: HashMap::new(),
+            normalize_steps: DEFAULT_NORMALIZE_STEPS.to_vec(),
+        }
+    }
+
+    /// Задать порядок шагов нормализации (пустой список — только нижний регистр)
+    pub fn with_normalize_steps(mut self, steps: Vec<NormalizeStep>) -> Self {
+        self.normalize_steps = steps;
+        self
+    }
+
+    /// Задать подсказки категорий по источнику (ключи без учёта регистра)
+    pub fn with_source_category_hints(mut self, hints: HashMap<String, String>) -> Self {
+        self.source_category_hints = hints
+            .into_iter()
+            .map(|(source, category)| (source.trim().to_lowercase(), category.trim().to_string()))
+            .filter(|(source, category)| !source.is_empty() && !category.is_empty())
+            .collect();
+        self
+    }
+
+    /// То же, что `extract`, но если категория не найдена по названию,
+    /// берётся категория по умолчанию для источника
+    pub fn extract_with_source(&self, raw_title: &str, source: &str) -> ExtractedEntities {
+        let mut entities = self.extract(raw_title);
+        if entities.category.is_none() {
+            if let Some(category) = self
+                .source_category_hints
+                .get(&source.trim().to_lowercase())
+            {
+                entities.category = Some(category.clone());
+                entities.confidence.category = SOURCE_HINT_CATEGORY_CONFIDENCE;
+            }
+        }
+        entities
     }
 
     /// Главный метод: извлечь все сущности из названия
@@ -267,11 +360,26 @@ impl EntityExtractor {
         let model = self.find_model(raw_title); // Используем raw для regex
         let category = self.find_category(&normalized);
 
+        let confidence = EntityConfidence {
+            brand: if brand.is_some() {
+                EXACT_MATCH_CONFIDENCE
+            } else {
+                0.0
+            },
+            model: model.as_ref().map_or(0.0, |(_, score)| *score),
+            category: if category.is_some() {
+                CATEGORY_KEYWORD_CONFIDENCE
+            } else {
+                0.0
+            },
+        };
+
         ExtractedEntities {
             normalized_title: normalized,
             brand,
-            model,
+            model: model.map(|(model, _)| model),
             category,
+            confidence,
         }
     }
 
@@ -282,26 +390,35 @@ impl EntityExtractor {
     fn normalize_title(&self, title: &str) -> String {
         let mut result = title.to_lowercase();
 
-        // Удаляем стоп-слова
-        for stop_word in STOP_WORDS {
-            let pattern = format!(r"\b{}\b", regex::escape(stop_word));
-            if let Ok(re) = Regex::new(&pattern) {
-                result = re.replace_all(&result, "").to_string();
-            }
-        }
+        // Слова, входящие в найденный в названии бренд, не считаются стоп-словами
+        let brand_words = self.brand_words_in(&result);
 
-        // Удаляем лишние пробелы
-        result = result.split_whitespace().collect::<Vec<_>>().join(" ");
-
-        // Удаляем специальные символы (оставляем буквы, цифры, пробелы)
-        result = result
-            .chars()
-            .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-            .collect();
+        for step in &self.normalize_steps {
+            result = match step {
+                NormalizeStep::StopWords => remove_stop_words(&result, &brand_words),
+                NormalizeStep::SpecialChars => strip_special_chars(&result),
+                NormalizeStep::Whitespace => collapse_whitespace(&result),
+            };
+        }
 
         result.trim().to_string()
     }
 
+    /// Слова всех брендов, которые целиком встречаются в названии
+    fn brand_words_in(&self, lower_title: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        for brand in &self.brands {
+            let brand_lower = brand.to_lowercase();
+            let pattern = format!(r"\b{}\b", regex::escape(&brand_lower));
+            if let Ok(re) = Regex::new(&pattern) {
+                if re.is_match(lower_title) {
+                    words.extend(brand_lower.split_whitespace().map(|w| w.to_string()));
+                }
+            }
+        }
+        words
+    }
+
     // ========================================================================
     // Шаг 2: Поиск бренда
     // ========================================================================
@@ -312,12 +429,16 @@ impl EntityExtractor {
         // Ищем точное совпадение или вхождение
         for brand in &self.brands {
             let brand_lower = brand.to_lowercase();
+            // "Black & Decker" после нормализации выглядит как "black decker"
+            let brand_normalized = collapse_whitespace(&strip_special_chars(&brand_lower));
 
             // Проверяем вхождение как целое слово
-            let pattern = format!(r"\b{}\b", regex::escape(&brand_lower));
-            if let Ok(re) = Regex::new(&pattern) {
-                if re.is_match(&lower) {
-                    return Some(brand.clone());
+            for form in [&brand_lower, &brand_normalized] {
+                let pattern = format!(r"\b{}\b", regex::escape(form));
+                if let Ok(re) = Regex::new(&pattern) {
+                    if re.is_match(&lower) {
+                        return Some(brand.clone());
+                    }
                 }
             }
         }
@@ -329,20 +450,21 @@ impl EntityExtractor {
     // Шаг 3: Извлечение модели
     // ========================================================================
 
-    fn find_model(&self, raw_title: &str) -> Option<String> {
+    /// Модель вместе с уверенностью: брендовые паттерны точнее общего и UPC
+    fn find_model(&self, raw_title: &str) -> Option<(String, f64)> {
         let upper = raw_title.to_uppercase();
 
         // Пробуем специфичные паттерны сначала
         if let Some(cap) = SAMSUNG_MODEL.captures(&upper) {
-            return Some(cap[1].to_string());
+            return Some((cap[1].to_string(), EXACT_MATCH_CONFIDENCE));
         }
 
         if let Some(cap) = LG_MODEL.captures(&upper) {
-            return Some(cap[1].to_string());
+            return Some((cap[1].to_string(), EXACT_MATCH_CONFIDENCE));
         }
 
         if let Some(cap) = GE_MODEL.captures(&upper) {
-            return Some(cap[1].to_string());
+            return Some((cap[1].to_string(), EXACT_MATCH_CONFIDENCE));
         }
 
         // Пробуем общий паттерн
@@ -350,13 +472,13 @@ impl EntityExtractor {
             let model = &cap[1];
             // Фильтруем очевидно неправильные (например, "NEW2024")
             if !model.starts_with("NEW") && !model.starts_with("BOX") {
-                return Some(model.to_string());
+                return Some((model.to_string(), GENERIC_MODEL_CONFIDENCE));
             }
         }
 
         // Ищем UPC код как fallback
         if let Some(cap) = UPC_CODE.captures(&upper) {
-            return Some(format!("UPC:{}", &cap[1]));
+            return Some((format!("UPC:{}", &cap[1]), UPC_MODEL_CONFIDENCE));
         }
 
         None
@@ -385,6 +507,113 @@ impl EntityExtractor {
 // Дополнительные утилиты
 // ============================================================================
 
+/// Удалить стоп-слова, кроме входящих в бренд из названия
+fn remove_stop_words(text: &str, brand_words: &[String]) -> String {
+    let mut result = text.to_string();
+    for stop_word in STOP_WORDS {
+        if brand_words.iter().any(|word| word == stop_word) {
+            continue;
+        }
+        let pattern = format!(r"\b{}\b", regex::escape(stop_word));
+        if let Ok(re) = Regex::new(&pattern) {
+            result = re.replace_all(&result, "").to_string();
+        }
+    }
+    result
+}
+
+/// Заменить спецсимволы пробелом, чтобы слова не склеивались ("65\"tv" → "65 tv").
+/// Апострофы удаляются ("men's" → "mens"), дефис между буквами/цифрами остаётся.
+fn strip_special_chars(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    chars
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &c)| match c {
+            c if c.is_alphanumeric() || c.is_whitespace() => Some(c),
+            '\'' | '\u{2019}' => None,
+            '-' if i > 0
+                && chars[i - 1].is_alphanumeric()
+                && chars.get(i + 1).is_some_and(|next| next.is_alphanumeric()) =>
+            {
+                Some(c)
+            }
+            _ => Some(' '),
+        })
+        .collect()
+}
+
+/// Сходство наборов слов двух названий (Жаккар): общие слова / все слова.
+/// Порядок и повторы слов не важны, регистр игнорируется. 1.0 — одинаковые наборы.
+pub fn token_set_similarity(a: &str, b: &str) -> f64 {
+    let tokens = |text: &str| -> std::collections::HashSet<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let (a, b) = (tokens(a), tokens(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Схлопнуть последовательности пробелов в один
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Загрузить порядок шагов нормализации из настроек.
+/// Пустая, отсутствующая или некорректная настройка даёт порядок по умолчанию.
+pub fn load_normalize_steps(conn: &rusqlite::Connection) -> Vec<NormalizeStep> {
+    let raw: Option<String> = conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            rusqlite::params![NORMALIZE_STEPS_SETTING],
+            |row| row.get(0),
+        )
+        .ok()
+        .filter(|value: &String| !value.trim().is_empty());
+
+    match raw {
+        Some(value) => serde_json::from_str(&value).unwrap_or_else(|e| {
+            log::warn!(
+                "Ignoring invalid {} setting: {}",
+                NORMALIZE_STEPS_SETTING,
+                e
+            );
+            DEFAULT_NORMALIZE_STEPS.to_vec()
+        }),
+        None => DEFAULT_NORMALIZE_STEPS.to_vec(),
+    }
+}
+
+/// Загрузить подсказки категорий по источнику из настроек.
+/// Отсутствующая или некорректная настройка даёт пустую карту (подсказок нет).
+pub fn load_source_category_hints(conn: &rusqlite::Connection) -> HashMap<String, String> {
+    let raw: Option<String> = conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            rusqlite::params![SOURCE_CATEGORY_HINTS_SETTING],
+            |row| row.get(0),
+        )
+        .ok();
+
+    match raw {
+        Some(value) => serde_json::from_str(&value).unwrap_or_else(|e| {
+            log::warn!(
+                "Ignoring invalid {} setting: {}",
+                SOURCE_CATEGORY_HINTS_SETTING,
+                e
+            );
+            HashMap::new()
+        }),
+        None => HashMap::new(),
+    }
+}
+
 /// Извлечь размер (дюймы) из названия TV/монитора
 pub fn extract_screen_size(title: &str) -> Option<u32> {
     let re = Regex::new(r#"\b(\d{2,3})[\"'\s]?(inch|in|tv|television|monitor)?\b"#).ok()?;
@@ -425,6 +654,16 @@ pub fn extract_capacity(title: &str) -> Option<f64> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_token_set_similarity() {
+        assert_eq!(
+            token_set_similarity("samsung 65 4k smart tv", "Smart TV Samsung 4K 65"),
+            1.0
+        );
+        assert_eq!(token_set_similarity("samsung 65 tv", "samsung 55 tv"), 0.5);
+        assert_eq!(token_set_similarity("", ""), 0.0);
+    }
+
     #[test]
     fn test_normalize_title() {
         let extractor = EntityExtractor::new();
@@ -438,6 +677,64 @@ mod tests {
         assert!(normalized.contains("65"));
     }
 
+    #[test]
+    fn test_normalize_title_keeps_stop_words_inside_brand() {
+        let extractor = EntityExtractor::new();
+
+        let normalized = extractor.normalize_title("NEW Original Penguin Polo Shirt");
+        assert!(normalized.contains("original penguin"));
+        assert!(!normalized.contains("new"));
+
+        let normalized = extractor.normalize_title("Original Packaging Blender");
+        assert!(!normalized.contains("original"));
+
+        let entities = extractor.extract("Original Penguin Polo Shirt");
+        assert_eq!(entities.brand, Some("Original Penguin".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_title_keeps_hyphenated_tokens() {
+        let extractor = EntityExtractor::new();
+
+        let normalized = extractor.normalize_title("NEW Sony Hi-Def Blu-Ray Player - Open Box");
+        assert_eq!(normalized, "sony hi-def blu-ray player");
+
+        let entities = extractor.extract("Samsung Blu-Ray Player");
+        assert_eq!(entities.category, Some("Electronics".to_string()));
+
+        // Дефис на краю слова убирается, слова не склеиваются
+        let normalized = extractor.normalize_title("Drill -Cordless- 20V");
+        assert_eq!(normalized, "drill cordless 20v");
+    }
+
+    #[test]
+    fn test_normalize_title_collapses_whitespace_last() {
+        let extractor = EntityExtractor::new();
+
+        // Раньше "&" удалялся после схлопывания пробелов и оставлял двойной пробел
+        let normalized = extractor.normalize_title("Black & Decker 20\"Drill");
+        assert_eq!(normalized, "black decker 20 drill");
+        assert_eq!(
+            extractor.find_brand(&normalized),
+            Some("Black & Decker".to_string())
+        );
+    }
+
+    #[test]
+    fn test_normalize_steps_are_configurable() {
+        let extractor =
+            EntityExtractor::new().with_normalize_steps(vec![NormalizeStep::Whitespace]);
+        assert_eq!(
+            extractor.normalize_title("NEW  Hi-Def TV!"),
+            "new hi-def tv!"
+        );
+
+        let steps: Vec<NormalizeStep> =
+            serde_json::from_str(r#"["SpecialChars", "Whitespace"]"#).unwrap();
+        let extractor = EntityExtractor::new().with_normalize_steps(steps);
+        assert_eq!(extractor.normalize_title("Open Box: Lamp"), "open box lamp");
+    }
+
     #[test]
     fn test_find_brand() {
         let extractor = EntityExtractor::new();
@@ -465,7 +762,7 @@ mod tests {
         let extractor = EntityExtractor::new();
 
         let input = "Samsung UN65TU8000FXZA 65\" 4K UHD TV";
-        let model = extractor.find_model(input);
+        let model = extractor.find_model(input).map(|(model, _)| model);
 
         assert_eq!(model, Some("UN65TU8000FXZA".to_string()));
     }
@@ -475,7 +772,7 @@ mod tests {
         let extractor = EntityExtractor::new();
 
         let input = "LG OLED65C1PUB 65\" OLED TV";
-        let model = extractor.find_model(input);
+        let model = extractor.find_model(input).map(|(model, _)| model);
 
         assert_eq!(model, Some("OLED65C1PUB".to_string()));
     }
@@ -485,7 +782,7 @@ mod tests {
         let extractor = EntityExtractor::new();
 
         let input = "GE Profile JVM3160RFSS Over-the-Range Microwave";
-        let model = extractor.find_model(input);
+        let model = extractor.find_model(input).map(|(model, _)| model);
 
         assert_eq!(model, Some("JVM3160RFSS".to_string()));
     }
@@ -513,6 +810,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_source_category_hint_only_when_title_has_no_category() {
+        let hints = HashMap::from([("Wayfair".to_string(), "Furniture".to_string())]);
+        let extractor = EntityExtractor::new().with_source_category_hints(hints);
+
+        let entities = extractor.extract_with_source("Mystery Item Set of 2", "wayfair");
+        assert_eq!(entities.category, Some("Furniture".to_string()));
+        assert_eq!(
+            entities.confidence.category,
+            SOURCE_HINT_CATEGORY_CONFIDENCE
+        );
+
+        let entities = extractor.extract_with_source("DeWalt Drill Set", "Wayfair");
+        assert_eq!(entities.category, Some("Tools".to_string()));
+
+        let entities = extractor.extract_with_source("Mystery Item", "Best Buy");
+        assert_eq!(entities.category, None);
+    }
+
     #[test]
     fn test_extract_screen_size() {
         assert_eq!(extract_screen_size("Samsung 65\" TV"), Some(65));
@@ -539,6 +855,19 @@ mod tests {
         assert_eq!(entities.model, Some("JVM3160RFSS".to_string()));
         assert_eq!(entities.category, Some("Appliances".to_string()));
         assert!(entities.normalized_title.contains("profile"));
+        assert_eq!(entities.confidence.brand, 1.0);
+        assert_eq!(entities.confidence.model, 1.0);
+    }
+
+    #[test]
+    fn test_confidence_lower_for_generic_model_and_missing_fields() {
+        let extractor = EntityExtractor::new();
+
+        let entities = extractor.extract("Widget Pro XY1234 Gadget");
+        assert_eq!(entities.model, Some("XY1234".to_string()));
+        assert_eq!(entities.confidence.brand, 0.0);
+        assert_eq!(entities.confidence.model, GENERIC_MODEL_CONFIDENCE);
+        assert!(entities.confidence.overall() < 0.5);
     }
 }
 
@@ -2,6 +2,16 @@
 
 use rusqlite::{Connection, Result};
 use serde::Serialize;
+use std::collections::HashMap;
+
+/// Settings key holding the condition → min price factor map (JSON object)
+pub const CONDITION_FACTORS_SETTING: &str = "condition_price_factors";
+
+/// Settings key naming the vendor used when no vendor matches an item's source
+pub const DEFAULT_PRICING_VENDOR_SETTING: &str = "default_pricing_vendor";
+
+/// Fallback vendor when `default_pricing_vendor` is missing or blank
+pub const DEFAULT_PRICING_VENDOR: &str = "Amazon Bstock";
 
 #[derive(Debug, Clone, Serialize)]
 pub struct Vendor {
@@ -10,23 +20,247 @@ pub struct Vendor {
     pub cost_coefficient: f64,
     pub min_price_margin: f64,
     pub is_active: bool,
+    /// Alternative source spellings that also map to this vendor (e.g. "BBY")
+    pub aliases: Vec<String>,
+    /// Lots from this vendor are sold without auction house commission
+    pub commission_exempt: bool,
+}
+
+/// Split the comma-separated `vendors.aliases` column into trimmed names
+pub fn parse_vendor_aliases(raw: Option<&str>) -> Vec<String> {
+    raw.unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|alias| !alias.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Gross margin of the min price over cost, in percent: (min − cost) / min × 100,
+/// rounded to 2 decimals. `None` when there is no positive min price.
+pub fn gross_margin_pct(cost_price: f64, min_price: f64) -> Option<f64> {
+    if min_price > 0.0 {
+        Some(((min_price - cost_price) / min_price * 10000.0).round() / 100.0)
+    } else {
+        None
+    }
+}
+
+/// Check vendor terms against the `vendors` table constraints. SQLite runs with
+/// `ignore_check_constraints`, so these are enforced here rather than by the schema.
+pub fn validate_vendor_terms(
+    cost_coefficient: f64,
+    min_price_margin: f64,
+) -> std::result::Result<(), String> {
+    if !(cost_coefficient > 0.0 && cost_coefficient < 1.0) {
+        return Err(format!(
+            "cost_coefficient must be between 0 and 1 (exclusive), got {}",
+            cost_coefficient
+        ));
+    }
+    if !(min_price_margin >= 0.0 && min_price_margin.is_finite()) {
+        return Err(format!(
+            "min_price_margin must be zero or greater, got {}",
+            min_price_margin
+        ));
+    }
+    Ok(())
+}
+
+/// A min price below cost guarantees a loss, so price writes reject it unless
+/// `allow_below_cost` is set. Enforced here because SQLite runs with
+/// `ignore_check_constraints` and a trigger couldn't honour the override.
+pub fn check_min_price_covers_cost(
+    cost_price: f64,
+    min_price: f64,
+    allow_below_cost: bool,
+) -> std::result::Result<(), String> {
+    if allow_below_cost || min_price + 0.005 >= cost_price {
+        return Ok(());
+    }
+    Err(format!(
+        "min_price {:.2} is below cost {:.2}; pass allow_below_cost to keep a loss-making price",
+        min_price, cost_price
+    ))
+}
+
+/// Settings key holding the highest believable cost/retail ratio on import
+pub const MAX_COST_RETAIL_RATIO_SETTING: &str = "max_cost_retail_ratio";
+
+/// Ceiling used when `max_cost_retail_ratio` is missing or invalid
+pub const DEFAULT_MAX_COST_RETAIL_RATIO: f64 = 0.5;
+
+/// A row this many times above or below its brand's usual cost/retail ratio
+/// was probably priced through the wrong vendor
+const BRAND_RATIO_MISMATCH_FACTOR: f64 = 2.0;
+
+/// Items of a brand needed before its usual ratio is trusted
+const MIN_BRAND_RATIO_SAMPLES: usize = 5;
+
+/// Median cost/retail ratio per brand (lowercased) over existing inventory,
+/// for brands with enough priced items to judge by
+pub fn load_brand_cost_ratios(conn: &Connection) -> Result<HashMap<String, f64>> {
+    let mut stmt = conn.prepare(
+        "SELECT LOWER(TRIM(extracted_brand)), cost_price / retail_price
+         FROM inventory_items
+         WHERE retail_price > 0 AND cost_price > 0
+           AND TRIM(COALESCE(extracted_brand, '')) != ''",
+    )?;
+    let mut ratios: HashMap<String, Vec<f64>> = HashMap::new();
+    for row in stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+    })? {
+        let (brand, ratio) = row?;
+        ratios.entry(brand).or_default().push(ratio);
+    }
+    Ok(ratios
+        .into_iter()
+        .filter(|(_, values)| values.len() >= MIN_BRAND_RATIO_SAMPLES)
+        .map(|(brand, mut values)| {
+            values.sort_by(|a, b| a.total_cmp(b));
+            let mid = values.len() / 2;
+            let median = if values.len() % 2 == 1 {
+                values[mid]
+            } else {
+                (values[mid - 1] + values[mid]) / 2.0
+            };
+            (brand, median)
+        })
+        .collect())
+}
+
+/// Import sanity check on a priced row: warns when cost/retail is above
+/// `ceiling`, or when it is far off the usual ratio of the detected brand
+/// (`brand_ratio`). Either usually means the source mapped to the wrong vendor.
+pub fn cost_ratio_warning(
+    lot_number: &str,
+    source: &str,
+    retail_price: f64,
+    cost_price: f64,
+    ceiling: f64,
+    brand: Option<(&str, f64)>,
+) -> Option<String> {
+    if retail_price <= 0.0 {
+        return None;
+    }
+    let ratio = cost_price / retail_price;
+    if ratio > ceiling {
+        return Some(format!(
+            "Lot {}: cost {:.2} is {:.0}% of retail {:.2} (ceiling {:.0}%); check the '{}' source mapping",
+            lot_number,
+            cost_price,
+            ratio * 100.0,
+            retail_price,
+            ceiling * 100.0,
+            source
+        ));
+    }
+    let (brand, usual) = brand?;
+    if usual > 0.0
+        && (ratio > usual * BRAND_RATIO_MISMATCH_FACTOR
+            || ratio * BRAND_RATIO_MISMATCH_FACTOR < usual)
+    {
+        return Some(format!(
+            "Lot {}: cost is {:.0}% of retail but {} items usually cost {:.0}%; check the '{}' source mapping",
+            lot_number,
+            ratio * 100.0,
+            brand,
+            usual * 100.0,
+            source
+        ));
+    }
+    None
+}
+
+/// Priced cost for an item, noting whether the fallback vendor was applied
+#[derive(Debug, Clone, Serialize)]
+pub struct CostQuote {
+    pub cost: f64,
+    pub min_price: f64,
+    pub vendor: String,
+    /// No vendor matched the source, so the default pricing vendor was used
+    pub used_fallback: bool,
+}
+
+/// Step-by-step pricing for a hypothetical item (see `PricingEngine::simulate`)
+#[derive(Debug, Clone, Serialize)]
+pub struct PricingBreakdown {
+    pub retail_price: f64,
+    pub source: String,
+    pub condition: String,
+    pub condition_category: String,
+    pub vendor: String,
+    pub used_fallback: bool,
+    pub cost_coefficient: f64,
+    pub min_price_margin: f64,
+    pub cost: f64,
+    /// Vendor min price before the condition factor
+    pub base_min_price: f64,
+    pub condition_factor: f64,
+    pub min_price: f64,
 }
 
 pub struct PricingEngine {
     vendors: Vec<Vendor>,
+    condition_factors: HashMap<String, f64>,
+    fallback_vendor: String,
 }
 
 impl PricingEngine {
-    /// Create a new PricingEngine, loading vendors from the database
+    /// Create a new PricingEngine, loading vendors, condition factors and the
+    /// fallback vendor from the database
     pub fn new(conn: &Connection) -> Result<Self> {
         let vendors = Self::load_vendors(conn)?;
-        Ok(Self { vendors })
+        let condition_factors = Self::load_condition_factors(conn);
+        let fallback_vendor = conn
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?1",
+                rusqlite::params![DEFAULT_PRICING_VENDOR_SETTING],
+                |row| row.get::<_, String>(0),
+            )
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| DEFAULT_PRICING_VENDOR.to_string());
+        Ok(Self {
+            vendors,
+            condition_factors,
+            fallback_vendor,
+        })
+    }
+
+    /// Load the condition → min price factor map from settings.
+    ///
+    /// Keys may be exact condition labels ("Used - Good") or pricing
+    /// categories ("Broken"). A missing or invalid setting yields an empty map,
+    /// which prices every condition at factor 1.0.
+    pub fn load_condition_factors(conn: &Connection) -> HashMap<String, f64> {
+        let raw: Option<String> = conn
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?1",
+                rusqlite::params![CONDITION_FACTORS_SETTING],
+                |row| row.get(0),
+            )
+            .ok();
+
+        match raw {
+            Some(value) => serde_json::from_str(&value).unwrap_or_else(|e| {
+                log::warn!(
+                    "Ignoring invalid {} setting: {}",
+                    CONDITION_FACTORS_SETTING,
+                    e
+                );
+                HashMap::new()
+            }),
+            None => HashMap::new(),
+        }
     }
 
     /// Load all active vendors from the database
     pub fn load_vendors(conn: &Connection) -> Result<Vec<Vendor>> {
         let mut stmt = conn.prepare(
-            "SELECT id, name, cost_coefficient, min_price_margin, is_active
+            "SELECT id, name, cost_coefficient, min_price_margin, is_active, aliases,
+                    commission_exempt
              FROM vendors WHERE is_active = TRUE",
         )?;
 
@@ -38,6 +272,8 @@ impl PricingEngine {
                     cost_coefficient: row.get(2)?,
                     min_price_margin: row.get(3)?,
                     is_active: row.get(4)?,
+                    aliases: parse_vendor_aliases(row.get::<_, Option<String>>(5)?.as_deref()),
+                    commission_exempt: row.get(6)?,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
@@ -45,31 +281,151 @@ impl PricingEngine {
         Ok(vendors)
     }
 
+    /// Vendor whose name or alias appears in `source`. When several match
+    /// (e.g. "Amazon" and "Amazon Warehouse"), the longest, most specific one wins.
+    pub fn match_vendor(&self, source: &str) -> Option<&Vendor> {
+        let source_lower = source.to_lowercase();
+        self.vendors
+            .iter()
+            .filter_map(|v| {
+                std::iter::once(&v.name)
+                    .chain(v.aliases.iter())
+                    .map(|name| name.to_lowercase())
+                    .filter(|name| !name.is_empty() && source_lower.contains(name.as_str()))
+                    .map(|name| name.len())
+                    .max()
+                    .map(|len| (len, v))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, v)| v)
+    }
+
+    /// Find a loaded vendor by id
+    pub fn vendor(&self, vendor_id: &str) -> Option<&Vendor> {
+        self.vendors.iter().find(|v| v.id == vendor_id)
+    }
+
+    /// Whether lots from `source` skip auction house commission. Only a direct
+    /// name/alias match counts; the fallback pricing vendor never makes a lot exempt.
+    pub fn is_commission_exempt(&self, source: &str) -> bool {
+        self.match_vendor(source)
+            .is_some_and(|vendor| vendor.commission_exempt)
+    }
+
+    /// Override a vendor's cost coefficient for this engine only (used for previews)
+    pub fn set_vendor_coefficient(&mut self, vendor_id: &str, cost_coefficient: f64) {
+        if let Some(vendor) = self.vendors.iter_mut().find(|v| v.id == vendor_id) {
+            vendor.cost_coefficient = cost_coefficient;
+        }
+    }
+
     /// Calculate cost and minimum price for a given retail price and source
     ///
-    /// Returns (cost_price, min_price, vendor_name)
+    /// Falls back to the configured default pricing vendor when no vendor matches
+    /// the source; the returned quote's `used_fallback` flags that case.
     ///
     /// Formula:
     ///   cost = retail_price × vendor.cost_coefficient
     ///   min_price = cost + (retail_price × vendor.min_price_margin)
-    pub fn calculate_cost(&self, retail_price: f64, source: &str) -> (f64, f64, String) {
-        // Find matching vendor by source name
-        let vendor = self
-            .vendors
-            .iter()
-            .find(|v| source.to_lowercase().contains(&v.name.to_lowercase()))
-            .or_else(|| {
-                // Fallback to Amazon Bstock as default
-                self.vendors.iter().find(|v| v.name == "Amazon Bstock")
-            });
+    pub fn calculate_cost(&self, retail_price: f64, source: &str) -> CostQuote {
+        let used_fallback = self.match_vendor(source).is_none();
+        let vendor = self.vendor_for_source(source);
 
         match vendor {
             Some(v) => {
                 let cost = (retail_price * v.cost_coefficient * 100.0).round() / 100.0;
                 let min_price = (cost + retail_price * v.min_price_margin).ceil();
-                (cost, min_price, v.name.clone())
+                CostQuote {
+                    cost,
+                    min_price,
+                    vendor: v.name.clone(),
+                    used_fallback,
+                }
             }
-            None => (0.0, 0.0, "Unknown".to_string()),
+            None => CostQuote {
+                cost: 0.0,
+                min_price: 0.0,
+                vendor: "Unknown".to_string(),
+                used_fallback,
+            },
+        }
+    }
+
+    /// Vendor pricing `source`: the best name/alias match, else the configured fallback vendor
+    pub fn vendor_for_source(&self, source: &str) -> Option<&Vendor> {
+        self.match_vendor(source).or_else(|| {
+            self.vendors
+                .iter()
+                .find(|v| v.name.eq_ignore_ascii_case(&self.fallback_vendor))
+        })
+    }
+
+    /// Lowest acceptable min price for an item at its stored cost:
+    /// cost + retail × vendor margin, scaled by the condition factor
+    pub fn min_price_floor(
+        &self,
+        retail_price: f64,
+        cost_price: f64,
+        source: &str,
+        condition: &str,
+    ) -> Option<(&Vendor, f64)> {
+        let vendor = self.vendor_for_source(source)?;
+        let floor = (cost_price + retail_price * vendor.min_price_margin).ceil();
+        let factor = self.condition_factor(condition);
+        if (factor - 1.0).abs() < f64::EPSILON {
+            return Some((vendor, floor));
+        }
+        Some((vendor, (floor * factor).ceil()))
+    }
+
+    /// Factor applied to min price for a condition: exact label first, then its category
+    pub fn condition_factor(&self, condition: &str) -> f64 {
+        self.condition_factors
+            .get(condition)
+            .or_else(|| {
+                self.condition_factors
+                    .get(Self::condition_to_category(condition))
+            })
+            .copied()
+            .filter(|factor| *factor > 0.0)
+            .unwrap_or(1.0)
+    }
+
+    /// Calculate cost and minimum price, scaling the minimum price by the
+    /// configured factor for the item's condition
+    pub fn calculate_cost_for_condition(
+        &self,
+        retail_price: f64,
+        source: &str,
+        condition: &str,
+    ) -> CostQuote {
+        let mut quote = self.calculate_cost(retail_price, source);
+        let factor = self.condition_factor(condition);
+        if (factor - 1.0).abs() >= f64::EPSILON {
+            quote.min_price = (quote.min_price * factor).ceil();
+        }
+        quote
+    }
+
+    /// Price a hypothetical item without touching the database, returning each
+    /// intermediate value so the UI can show how the min price was reached
+    pub fn simulate(&self, retail_price: f64, source: &str, condition: &str) -> PricingBreakdown {
+        let base = self.calculate_cost(retail_price, source);
+        let quote = self.calculate_cost_for_condition(retail_price, source, condition);
+        let vendor = self.vendors.iter().find(|v| v.name == quote.vendor);
+        PricingBreakdown {
+            retail_price,
+            source: source.to_string(),
+            condition: condition.to_string(),
+            condition_category: Self::condition_to_category(condition).to_string(),
+            vendor: quote.vendor.clone(),
+            used_fallback: quote.used_fallback,
+            cost_coefficient: vendor.map(|v| v.cost_coefficient).unwrap_or(0.0),
+            min_price_margin: vendor.map(|v| v.min_price_margin).unwrap_or(0.0),
+            cost: quote.cost,
+            base_min_price: base.min_price,
+            condition_factor: self.condition_factor(condition),
+            min_price: quote.min_price,
         }
     }
 
@@ -169,6 +525,23 @@ pub fn update_pricing_rule(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cost_ratio_warning() {
+        // 14% of retail: fine without brand history
+        assert!(cost_ratio_warning("1", "Best Buy", 100.0, 14.0, 0.5, None).is_none());
+        // Above the ceiling
+        let warning = cost_ratio_warning("7", "Wayfair", 100.0, 60.0, 0.5, None).unwrap();
+        assert!(warning.starts_with("Lot 7: cost 60.00 is 60% of retail"));
+        // Far above the brand's usual ratio, though under the ceiling
+        let warning =
+            cost_ratio_warning("8", "Target", 100.0, 40.0, 0.5, Some(("samsung", 0.14))).unwrap();
+        assert!(warning.contains("samsung items usually cost 14%"));
+        assert!(
+            cost_ratio_warning("9", "Best Buy", 100.0, 16.0, 0.5, Some(("samsung", 0.14)))
+                .is_none()
+        );
+    }
+
     fn make_engine() -> PricingEngine {
         PricingEngine {
             vendors: vec![
@@ -178,6 +551,8 @@ mod tests {
                     cost_coefficient: 0.14,
                     min_price_margin: 0.10,
                     is_active: true,
+                    aliases: vec![],
+                    commission_exempt: false,
                 },
                 Vendor {
                     id: "wayfair".to_string(),
@@ -185,6 +560,8 @@ mod tests {
                     cost_coefficient: 0.07,
                     min_price_margin: 0.10,
                     is_active: true,
+                    aliases: vec![],
+                    commission_exempt: false,
                 },
                 Vendor {
                     id: "mech".to_string(),
@@ -192,6 +569,8 @@ mod tests {
                     cost_coefficient: 0.20,
                     min_price_margin: 0.10,
                     is_active: true,
+                    aliases: vec![],
+                    commission_exempt: false,
                 },
                 Vendor {
                     id: "amazon".to_string(),
@@ -199,15 +578,24 @@ mod tests {
                     cost_coefficient: 0.20,
                     min_price_margin: 0.10,
                     is_active: true,
+                    aliases: vec![],
+                    commission_exempt: false,
                 },
             ],
+            condition_factors: HashMap::new(),
+            fallback_vendor: DEFAULT_PRICING_VENDOR.to_string(),
         }
     }
 
     #[test]
     fn test_best_buy_pricing() {
         let engine = make_engine();
-        let (cost, min_price, vendor) = engine.calculate_cost(3199.0, "Best Buy");
+        let CostQuote {
+            cost,
+            min_price,
+            vendor,
+            ..
+        } = engine.calculate_cost(3199.0, "Best Buy");
 
         assert_eq!(vendor, "Best Buy");
         assert_eq!(cost, 447.86);
@@ -217,7 +605,12 @@ mod tests {
     #[test]
     fn test_wayfair_pricing() {
         let engine = make_engine();
-        let (cost, min_price, vendor) = engine.calculate_cost(1000.0, "Wayfair");
+        let CostQuote {
+            cost,
+            min_price,
+            vendor,
+            ..
+        } = engine.calculate_cost(1000.0, "Wayfair");
 
         assert_eq!(vendor, "Wayfair");
         assert_eq!(cost, 70.0);
@@ -227,9 +620,141 @@ mod tests {
     #[test]
     fn test_unknown_source_fallback() {
         let engine = make_engine();
-        let (_cost, _min_price, vendor) = engine.calculate_cost(500.0, "Unknown Vendor");
+        let quote = engine.calculate_cost(500.0, "Unknown Vendor");
 
         // Should fall back to Amazon Bstock
-        assert_eq!(vendor, "Amazon Bstock");
+        assert_eq!(quote.vendor, "Amazon Bstock");
+        assert!(quote.used_fallback);
+    }
+
+    #[test]
+    fn test_configured_fallback_vendor_is_flagged() {
+        let mut engine = make_engine();
+        engine.fallback_vendor = "Wayfair".to_string();
+
+        let quote = engine.calculate_cost(1000.0, "Mystery Liquidator");
+        assert_eq!(quote.vendor, "Wayfair");
+        assert_eq!(quote.cost, 70.0);
+        assert!(quote.used_fallback);
+
+        let quote = engine.calculate_cost(1000.0, "Best Buy");
+        assert_eq!(quote.vendor, "Best Buy");
+        assert!(!quote.used_fallback);
+    }
+
+    #[test]
+    fn test_condition_factor_scales_min_price() {
+        let mut engine = make_engine();
+        engine.condition_factors = HashMap::from([
+            ("Broken".to_string(), 0.5),
+            ("Used - Good".to_string(), 0.8),
+        ]);
+
+        // Unconfigured condition keeps the vendor pricing
+        let quote = engine.calculate_cost_for_condition(1000.0, "Wayfair", "New - Open box");
+        assert_eq!(quote.cost, 70.0);
+        assert_eq!(quote.min_price, 170.0);
+
+        // Exact label match
+        let quote = engine.calculate_cost_for_condition(1000.0, "Wayfair", "Used - Good");
+        assert_eq!(quote.cost, 70.0);
+        assert_eq!(quote.min_price, 136.0);
+
+        // Category match ("Broken" category)
+        let quote = engine.calculate_cost_for_condition(1000.0, "Wayfair", "Broken");
+        assert_eq!(quote.min_price, 85.0);
+    }
+
+    #[test]
+    fn test_most_specific_vendor_and_alias_match() {
+        let mut engine = make_engine();
+        engine.vendors.push(Vendor {
+            id: "amazon-plain".to_string(),
+            name: "Amazon".to_string(),
+            cost_coefficient: 0.20,
+            min_price_margin: 0.10,
+            is_active: true,
+            aliases: vec![],
+            commission_exempt: false,
+        });
+        engine.vendors.push(Vendor {
+            id: "amazon-warehouse".to_string(),
+            name: "Amazon Warehouse".to_string(),
+            cost_coefficient: 0.10,
+            min_price_margin: 0.10,
+            is_active: true,
+            aliases: vec![],
+            commission_exempt: false,
+        });
+        engine.vendors[0].aliases = vec!["BBY".to_string()];
+
+        let vendor = engine
+            .calculate_cost(100.0, "Amazon Warehouse Returns")
+            .vendor;
+        assert_eq!(vendor, "Amazon Warehouse");
+
+        let vendor = engine.calculate_cost(100.0, "Amazon Liquidation").vendor;
+        assert_eq!(vendor, "Amazon");
+
+        let CostQuote { cost, vendor, .. } = engine.calculate_cost(100.0, "BBY Returns");
+        assert_eq!(vendor, "Best Buy");
+        assert_eq!(cost, 14.0);
+
+        assert_eq!(
+            parse_vendor_aliases(Some(" BBY, ,Best Buy Business ")),
+            vec!["BBY", "Best Buy Business"]
+        );
+    }
+
+    #[test]
+    fn test_simulate_reports_breakdown() {
+        let mut engine = make_engine();
+        engine.condition_factors = HashMap::from([("Broken".to_string(), 0.5)]);
+
+        let breakdown = engine.simulate(1000.0, "Wayfair", "Broken");
+        assert_eq!(breakdown.vendor, "Wayfair");
+        assert!(!breakdown.used_fallback);
+        assert_eq!(breakdown.cost_coefficient, 0.07);
+        assert_eq!(breakdown.cost, 70.0);
+        assert_eq!(breakdown.base_min_price, 170.0);
+        assert_eq!(breakdown.condition_category, "Broken");
+        assert_eq!(breakdown.condition_factor, 0.5);
+        assert_eq!(breakdown.min_price, 85.0);
+    }
+
+    #[test]
+    fn test_min_price_floor_uses_stored_cost() {
+        let engine = make_engine();
+        // Wayfair margin is 10%: 50 + 1000 × 0.10
+        let (vendor, floor) = engine
+            .min_price_floor(1000.0, 50.0, "Wayfair", "New")
+            .expect("vendor");
+        assert_eq!(vendor.name, "Wayfair");
+        assert_eq!(floor, 150.0);
+    }
+
+    #[test]
+    fn test_gross_margin_pct() {
+        assert_eq!(gross_margin_pct(140.0, 240.0), Some(41.67));
+        assert_eq!(gross_margin_pct(50.0, 40.0), Some(-25.0));
+        assert_eq!(gross_margin_pct(10.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_validate_vendor_terms() {
+        assert!(validate_vendor_terms(0.14, 0.10).is_ok());
+        assert!(validate_vendor_terms(0.0, 0.10).is_err());
+        assert!(validate_vendor_terms(1.0, 0.10).is_err());
+        assert!(validate_vendor_terms(f64::NAN, 0.10).is_err());
+        assert!(validate_vendor_terms(0.5, -0.01).is_err());
+    }
+
+    #[test]
+    fn test_min_price_must_cover_cost() {
+        assert!(check_min_price_covers_cost(100.0, 100.0, false).is_ok());
+        assert!(check_min_price_covers_cost(100.0, 99.999, false).is_ok());
+        let err = check_min_price_covers_cost(100.0, 80.0, false).unwrap_err();
+        assert!(err.contains("min_price 80.00 is below cost 100.00"));
+        assert!(check_min_price_covers_cost(100.0, 80.0, true).is_ok());
     }
 }
@@ -1,7 +1,12 @@
+use crate::auctions::AuctionType;
 use crate::csv_parser;
 use crate::db::Database;
+use crate::error::AppError;
+use crate::hibid;
+use crate::pricing::PricingEngine;
 use rusqlite::{params, OptionalExtension, Result};
 use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use tauri::State;
 
 const ITEM_STATUS_SQL: &str = "COALESCE(
@@ -12,7 +17,32 @@ const ITEM_STATUS_SQL: &str = "COALESCE(
         ELSE 'Unsold'
     END
 )";
+/// Sales from every channel, shaped like `auction_results` (alias it `ar`):
+/// HiBid results plus cash sales, which carry no auction. Revenue reports read
+/// this; reports scoped to one auction keep reading `auction_results`.
+const SALE_RESULTS_SQL: &str = "(
+    SELECT id, auction_id, item_id, high_bid, max_bid, is_buyback, item_status,
+           commission_amount, net_profit, commission_exempt, days_listed, created_at,
+           'HiBid' AS platform
+    FROM auction_results
+    UNION ALL
+    SELECT cs.id, NULL, cs.item_id, cs.sale_price, NULL, 0, 'Sold',
+           cs.commission_amount, cs.net_profit, 0,
+           (SELECT julianday(i.sold_at) - julianday(i.listed_at)
+            FROM inventory_items i WHERE i.id = cs.item_id),
+           cs.created_at, cs.platform
+    FROM cash_sales cs
+)";
 const REPORT_BONUS_RATE: f64 = 0.11;
+/// Max bid this far above the high bid (as a fraction) counts as money left on the table
+const BID_SPREAD_FLAG_RATIO: f64 = 0.5;
+/// Groups with fewer sold + on-hand units than this are not scored
+const REORDER_MIN_SAMPLE: i64 = 3;
+/// Sell-through (%) and margin (%) a group needs to be worth buying more of
+const REORDER_BUY_SELL_THROUGH: f64 = 60.0;
+const REORDER_BUY_MARGIN: f64 = 30.0;
+/// Below this sell-through (%) or at a loss, a group is marked to avoid
+const REORDER_AVOID_SELL_THROUGH: f64 = 30.0;
 
 #[derive(Debug, Serialize)]
 pub struct ReconciliationResult {
@@ -20,7 +50,85 @@ pub struct ReconciliationResult {
     pub buyback_count: i32,
     pub total_revenue: f64,
     pub total_profit: f64,
+    /// Buyer's premium on sold lots; paid by buyers, not part of revenue or profit
+    pub total_buyers_premium: f64,
     pub errors: Vec<String>,
+    /// Problems that didn't stop the run but may have skewed it, e.g. the
+    /// commission rate falling back because the setting is missing
+    pub warnings: Vec<String>,
+    /// One entry per results row that matched an item, in file order
+    pub details: Vec<LotOutcome>,
+    /// Items of the auction the results file never mentioned
+    pub unsold: Vec<UnreportedLot>,
+}
+
+/// Listed item left without a result after reconciliation
+#[derive(Debug, Serialize)]
+pub struct UnreportedLot {
+    pub item_id: String,
+    pub lot_number: String,
+    pub title: String,
+    /// Status after reconciliation: "Listed" unless `reconcile_unreported_status` moved it
+    pub status: String,
+}
+
+/// Settings key holding HiBid's buyer's premium rate (fraction of the hammer price)
+pub const BUYERS_PREMIUM_RATE_SETTING: &str = "buyers_premium_rate";
+
+/// Buyer's premium rate from settings; missing, invalid or negative values mean none
+pub fn load_buyers_premium_rate(db: &Database) -> f64 {
+    db.get_setting(BUYERS_PREMIUM_RATE_SETTING)
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|rate| rate.is_finite() && *rate > 0.0)
+        .unwrap_or(0.0)
+}
+
+/// Settings key for the status given to listed items missing from a results file
+pub const UNREPORTED_STATUS_SETTING: &str = "reconcile_unreported_status";
+
+/// How a results row was matched to an inventory item
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum MatchStrategy {
+    /// Lot number equal to the stored lot, ignoring case and surrounding
+    /// whitespace (after stripping the auction prefix)
+    Exact,
+    /// Lot number equal after normalization ("42" matched "42m")
+    Normalized,
+}
+
+/// Per-lot reconciliation outcome for the results grid
+#[derive(Debug, Serialize)]
+pub struct LotOutcome {
+    /// Lot number as it appears in the results file
+    pub lot_number: String,
+    pub item_id: String,
+    pub title: String,
+    pub status: String,
+    pub high_bid: f64,
+    pub commission: f64,
+    pub net_profit: f64,
+    pub match_strategy: MatchStrategy,
+}
+
+/// Auction matching an `Auction` value found in a results file
+#[derive(Debug, Serialize)]
+pub struct AuctionCandidate {
+    /// Value as it appears in the file's Auction column
+    pub file_value: String,
+    pub auction_id: String,
+    pub auction_name: String,
+    pub hibid_auction_id: Option<String>,
+    pub status: String,
+    /// Which auction field matched: "id", "hibid_auction_id" or "name"
+    pub matched_on: String,
+}
+
+/// Outcome of one auction within a combined results file
+#[derive(Debug, Serialize)]
+pub struct AuctionReconciliation {
+    pub auction_id: String,
+    pub auction_name: String,
+    pub result: ReconciliationResult,
 }
 
 #[derive(Debug, Serialize)]
@@ -37,6 +145,13 @@ pub struct ProfitLossReport {
     pub unsold_count: i32,
     pub sell_through_rate: f64,
     pub avg_sale_price: f64,
+    /// Sum of item adjustments (credits positive) included in net_profit
+    pub total_adjustments: f64,
+    /// Sold revenue from commission-exempt vendors, included in total_revenue
+    pub exempt_revenue: f64,
+    /// Cost of scrapped items less their scrap_value, subtracted from net_profit
+    pub scrap_writeoff: f64,
+    pub scrapped_count: i32,
     pub period_label: String,
 }
 
@@ -70,6 +185,164 @@ pub struct VendorBreakdown {
     pub profit_loss: f64,
 }
 
+#[derive(Debug, Serialize)]
+pub struct AuctionCommissionSummary {
+    pub auction_id: String,
+    pub sold_count: i32,
+    pub total_revenue: f64,
+    pub total_commission: f64,
+    pub average_commission_rate: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BidSpreadRow {
+    pub item_id: String,
+    pub lot_number: Option<String>,
+    pub title: String,
+    pub item_status: String,
+    pub high_bid: f64,
+    pub max_bid: f64,
+    pub spread: f64,
+    pub spread_pct: f64,
+    pub left_on_table: bool,
+    pub closed_at_max: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VarianceRow {
+    pub item_id: String,
+    pub lot_number: Option<String>,
+    pub title: String,
+    pub min_price: f64,
+    pub high_bid: f64,
+    pub variance: f64,
+    pub variance_pct: f64,
+    pub projected_profit: f64,
+    pub actual_profit: f64,
+    pub profit_variance: f64,
+}
+
+/// Days from listing to sale for one category (or all categories)
+#[derive(Debug, Serialize)]
+pub struct VelocityBucket {
+    pub category: String,
+    pub sold_count: i32,
+    pub avg_days: f64,
+    pub median_days: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VelocityStats {
+    pub overall: VelocityBucket,
+    pub by_category: Vec<VelocityBucket>,
+}
+
+/// Dwell time between two pipeline statuses, in days
+#[derive(Debug, Serialize)]
+pub struct StatusDuration {
+    pub from_status: String,
+    pub to_status: String,
+    pub item_count: i32,
+    pub avg_days: f64,
+    pub median_days: f64,
+}
+
+/// Sales performance of one brand over a date range (buybacks excluded)
+#[derive(Debug, Serialize)]
+pub struct BrandPerformance {
+    pub brand: String,
+    pub units_sold: i64,
+    pub total_revenue: f64,
+    pub total_net_profit: f64,
+    /// Net profit as a percentage of revenue
+    pub avg_margin_percent: f64,
+}
+
+/// Totals of a consignor statement written by `export_consignor_statement`
+#[derive(Debug, Serialize)]
+pub struct ConsignorStatement {
+    pub vendor_name: String,
+    pub items_sold: usize,
+    pub hammer_total: f64,
+    pub commission_total: f64,
+    /// Hammer total less commission retained
+    pub net_due: f64,
+}
+
+/// Outcome of `record_cash_sale`
+#[derive(Debug, Serialize)]
+pub struct CashSaleResult {
+    pub sale_id: String,
+    pub commission_rate: f64,
+    pub commission_amount: f64,
+    /// Sale price less cost and commission
+    pub net_profit: f64,
+    /// Set when `cash_sale_commission_rate` was unusable and the fallback rate was charged
+    pub warning: Option<String>,
+}
+
+/// Quote of what an item would pay out at an estimated hammer price
+#[derive(Debug, Serialize)]
+pub struct PayoutEstimate {
+    pub item_id: String,
+    pub estimated_hammer: f64,
+    pub commission_rate: f64,
+    /// Rate × hammer plus flat fee, never below the minimum; 0 when exempt
+    pub commission: f64,
+    pub commission_exempt: bool,
+    /// Hammer less commission
+    pub net_to_consignor: f64,
+    pub cost_price: f64,
+    /// Net to consignor less the item's cost
+    pub net_after_cost: f64,
+    /// The commission rate setting was missing or invalid and the fallback was used
+    pub rate_is_fallback: bool,
+}
+
+/// Totals of a picklist written by `export_picklist`
+#[derive(Debug, Serialize)]
+pub struct PicklistSummary {
+    pub buyers: usize,
+    pub lots: usize,
+    /// Lots with no location set, which staff will have to search for
+    pub missing_location: usize,
+}
+
+/// Purchasing signal for one brand/category pair
+#[derive(Debug, Serialize)]
+pub struct ReorderSuggestion {
+    pub brand: String,
+    pub category: String,
+    /// Units in historical_sales
+    pub units_sold: i64,
+    /// Units still in stock, listed, unsold or bought back
+    pub units_on_hand: i64,
+    /// units_sold / (units_sold + units_on_hand), in percent
+    pub sell_through_rate: f64,
+    /// Average (sale − cost) / sale over historical sales, in percent
+    pub avg_margin_percent: f64,
+    pub total_revenue: f64,
+    /// Mean of sell-through and margin; higher is a better buy
+    pub score: f64,
+    /// "Buy more", "Hold", "Avoid" or "Not enough data"
+    pub recommendation: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReconciliationVariance {
+    pub auction_id: String,
+    pub sold_count: i32,
+    pub over_floor_count: i32,
+    pub under_floor_count: i32,
+    pub total_min_price: f64,
+    pub total_high_bid: f64,
+    pub total_variance: f64,
+    pub total_projected_profit: f64,
+    pub total_actual_profit: f64,
+    pub total_profit_variance: f64,
+    pub rows: Vec<VarianceRow>,
+}
+
 struct PeriodFilter {
     clause: String,
     custom_range: Option<(String, String)>,
@@ -81,7 +354,7 @@ fn build_period_filter(
     date_from: Option<String>,
     date_to: Option<String>,
     column: &str,
-) -> Result<PeriodFilter, String> {
+) -> Result<PeriodFilter, AppError> {
     let normalized = period.trim().to_lowercase();
     match normalized.as_str() {
         "week" => Ok(PeriodFilter {
@@ -103,11 +376,15 @@ fn build_period_filter(
             let from = date_from
                 .map(|v| v.trim().to_string())
                 .filter(|v| !v.is_empty())
-                .ok_or_else(|| "date_from is required for custom period".to_string())?;
+                .ok_or_else(|| {
+                    AppError::Validation("date_from is required for custom period".into())
+                })?;
             let to = date_to
                 .map(|v| v.trim().to_string())
                 .filter(|v| !v.is_empty())
-                .ok_or_else(|| "date_to is required for custom period".to_string())?;
+                .ok_or_else(|| {
+                    AppError::Validation("date_to is required for custom period".into())
+                })?;
             Ok(PeriodFilter {
                 clause: format!(
                     " AND {column} >= datetime(?1) AND {column} < datetime(?2, '+1 day')"
@@ -116,7 +393,32 @@ fn build_period_filter(
                 label: format!("{from} - {to}"),
             })
         }
-        _ => Err(format!("Unsupported period: {period}")),
+        _ => Err(AppError::Validation(format!(
+            "Unsupported period: {period}"
+        ))),
+    }
+}
+
+fn median(sorted: &[f64]) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        n if n % 2 == 1 => sorted[n / 2],
+        n => (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0,
+    }
+}
+
+fn velocity_bucket(category: String, mut days: Vec<f64>) -> VelocityBucket {
+    days.sort_by(|a, b| a.total_cmp(b));
+    let avg_days = if days.is_empty() {
+        0.0
+    } else {
+        days.iter().sum::<f64>() / days.len() as f64
+    };
+    VelocityBucket {
+        category,
+        sold_count: days.len() as i32,
+        avg_days: round2(avg_days),
+        median_days: round2(median(&days)),
     }
 }
 
@@ -124,6 +426,34 @@ fn round2(value: f64) -> f64 {
     (value * 100.0).round() / 100.0
 }
 
+/// Internal (house) bidder ids from the comma-separated `ron_larsson_bidder_id`
+/// setting; a single id is a one-element list
+fn parse_internal_bidder_ids(raw: &str) -> Vec<&str> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+/// Lot numbers are opaque strings ("A-12", "TOOL-5"): equal when they match
+/// ignoring case and surrounding whitespace
+fn lot_numbers_equal(a: &str, b: &str) -> bool {
+    a.trim().to_uppercase() == b.trim().to_uppercase()
+}
+
+/// Normalize a lot number for fuzzy matching: uppercase and strip any
+/// non-numeric suffix ("42m" -> "42"). Lots without a numeric part are
+/// only uppercased.
+fn normalize_lot_number(lot_number: &str) -> String {
+    let upper = lot_number.trim().to_uppercase();
+    let stripped = upper.trim_end_matches(|c: char| !c.is_ascii_digit());
+    if stripped.is_empty() {
+        upper
+    } else {
+        stripped.to_string()
+    }
+}
+
 fn report_style_difference(high_bid: f64, retail_price: f64, cost_price: f64) -> f64 {
     let cost_coefficient = if retail_price > 0.0 {
         round2(cost_price / retail_price).max(0.0)
@@ -139,7 +469,7 @@ fn report_style_difference(high_bid: f64, retail_price: f64, cost_price: f64) ->
 fn get_unmatched_difference_adjustment(
     db: &Database,
     filter: &PeriodFilter,
-) -> Result<f64, String> {
+) -> Result<f64, AppError> {
     let sql = format!(
         "
         SELECT COALESCE(SUM(CAST(s.value AS REAL)), 0)
@@ -157,18 +487,65 @@ fn get_unmatched_difference_adjustment(
     if let Some((from, to)) = &filter.custom_range {
         db.conn
             .query_row(&sql, params![from, to], |row| row.get(0))
-            .map_err(|e| e.to_string())
+            .map_err(AppError::from)
+    } else {
+        db.conn
+            .query_row(&sql, [], |row| row.get(0))
+            .map_err(AppError::from)
+    }
+}
+
+/// When item `i` last became Scrap, from `item_status_history`. Later edits
+/// (scrap value, notes, flags) bump `updated_at` but leave this alone.
+const SCRAPPED_AT_SQL: &str = "(SELECT MAX(h.changed_at) FROM item_status_history h
+     WHERE h.item_id = i.id AND h.to_status = 'Scrap')";
+
+/// Scrapped item count and cost written off (cost − scrap_value) within the
+/// period, dated by the Scrap transition (filter column `SCRAPPED_AT_SQL`).
+/// Split and merge originals are skipped: their cost lives on in the new lots.
+fn get_scrap_writeoff(db: &Database, filter: &PeriodFilter) -> Result<(i64, f64), AppError> {
+    let sql = format!(
+        "SELECT COUNT(*), COALESCE(SUM(COALESCE(i.cost_price, 0) - i.scrap_value), 0)
+         FROM inventory_items i
+         WHERE i.current_status = 'Scrap'
+           AND i.merged_into_id IS NULL
+           AND NOT EXISTS (SELECT 1 FROM inventory_items p WHERE p.split_from_id = i.id)
+           {filter_clause}",
+        filter_clause = filter.clause
+    );
+    let mapper = |row: &rusqlite::Row<'_>| Ok((row.get(0)?, row.get(1)?));
+
+    if let Some((from, to)) = &filter.custom_range {
+        db.conn
+            .query_row(&sql, params![from, to], mapper)
+            .map_err(AppError::from)
+    } else {
+        db.conn.query_row(&sql, [], mapper).map_err(AppError::from)
+    }
+}
+
+/// Sum of `adjustments` amounts recorded within the period (filter column `adj.created_at`)
+fn get_item_adjustments_total(db: &Database, filter: &PeriodFilter) -> Result<f64, AppError> {
+    let sql = format!(
+        "SELECT COALESCE(SUM(adj.amount), 0) FROM adjustments adj WHERE 1 = 1 {filter_clause}",
+        filter_clause = filter.clause
+    );
+
+    if let Some((from, to)) = &filter.custom_range {
+        db.conn
+            .query_row(&sql, params![from, to], |row| row.get(0))
+            .map_err(AppError::from)
     } else {
         db.conn
             .query_row(&sql, [], |row| row.get(0))
-            .map_err(|e| e.to_string())
+            .map_err(AppError::from)
     }
 }
 
 fn get_unmatched_difference_adjustment_for_auction(
     db: &Database,
     auction_id: &str,
-) -> Result<f64, String> {
+) -> Result<f64, AppError> {
     let key = format!("auction_unmatched_diff_{}", auction_id);
     db.conn
         .query_row(
@@ -178,30 +555,31 @@ fn get_unmatched_difference_adjustment_for_auction(
         )
         .optional()
         .map(|v| v.unwrap_or(0.0))
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 fn calculate_filtered_report_difference_total(
     db: &Database,
     filter: &PeriodFilter,
-) -> Result<f64, String> {
+) -> Result<f64, AppError> {
     let sql = format!(
         "
         SELECT
             COALESCE(ar.high_bid, 0),
             COALESCE(i.retail_price, 0),
             COALESCE(i.cost_price, 0)
-        FROM auction_results ar
+        FROM {sales} ar
         JOIN inventory_items i ON ar.item_id = i.id
         WHERE 1 = 1
           AND {status_sql} != 'Buyback'
         {filter_clause}
         ",
+        sales = SALE_RESULTS_SQL,
         status_sql = ITEM_STATUS_SQL,
         filter_clause = filter.clause
     );
 
-    let mut stmt = db.conn.prepare(&sql).map_err(|e| e.to_string())?;
+    let mut stmt = db.conn.prepare(&sql)?;
     let mapper = |row: &rusqlite::Row<'_>| -> rusqlite::Result<(f64, f64, f64)> {
         Ok((row.get(0)?, row.get(1)?, row.get(2)?))
     };
@@ -210,12 +588,11 @@ fn calculate_filtered_report_difference_total(
         stmt.query_map(params![from, to], mapper)
     } else {
         stmt.query_map([], mapper)
-    }
-    .map_err(|e| e.to_string())?;
+    }?;
 
     let mut total = 0.0;
     for row in rows {
-        let (high_bid, retail_price, cost_price) = row.map_err(|e| e.to_string())?;
+        let (high_bid, retail_price, cost_price) = row?;
         total += report_style_difference(high_bid, retail_price, cost_price);
     }
     Ok(round2(total))
@@ -224,7 +601,7 @@ fn calculate_filtered_report_difference_total(
 fn calculate_auction_report_difference_total(
     db: &Database,
     auction_id: &str,
-) -> Result<f64, String> {
+) -> Result<f64, AppError> {
     let sql = format!(
         "
             SELECT
@@ -239,26 +616,116 @@ fn calculate_auction_report_difference_total(
         status_sql = ITEM_STATUS_SQL
     );
 
-    let mut stmt = db.conn.prepare(&sql).map_err(|e| e.to_string())?;
+    let mut stmt = db.conn.prepare(&sql)?;
 
-    let rows = stmt
-        .query_map(params![auction_id], |row| {
-            Ok((
-                row.get::<_, f64>(0)?,
-                row.get::<_, f64>(1)?,
-                row.get::<_, f64>(2)?,
-            ))
-        })
-        .map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![auction_id], |row| {
+        Ok((
+            row.get::<_, f64>(0)?,
+            row.get::<_, f64>(1)?,
+            row.get::<_, f64>(2)?,
+        ))
+    })?;
 
     let mut total = 0.0;
     for row in rows {
-        let (high_bid, retail_price, cost_price) = row.map_err(|e| e.to_string())?;
+        let (high_bid, retail_price, cost_price) = row?;
         total += report_style_difference(high_bid, retail_price, cost_price);
     }
     Ok(round2(total))
 }
 
+/// Settings key for the commission rate used when `default_commission_rate` is
+/// missing or invalid
+pub const FALLBACK_COMMISSION_RATE_SETTING: &str = "fallback_commission_rate";
+
+/// Last-resort commission rate when neither rate setting holds a valid value
+pub const FALLBACK_COMMISSION_RATE: f64 = 0.15;
+
+/// Commission charged on a sold lot: `rate` × price plus a `flat` fee per lot,
+/// never less than `min`. Flat and min come from the auction when set, else
+/// settings; the rate comes from the setting of the auction's type (Standard/Cash).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommissionTerms {
+    pub rate: f64,
+    pub flat: f64,
+    pub min: f64,
+    /// Settings key the rate was read from
+    pub rate_setting: &'static str,
+    /// The rate setting was missing or invalid and a fallback rate was used
+    pub rate_is_fallback: bool,
+}
+
+impl CommissionTerms {
+    pub fn load(db: &Database, auction_id: &str) -> Self {
+        let setting = |key: &str, default: f64| {
+            db.get_setting(key)
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|v| v.is_finite() && *v >= 0.0)
+                .unwrap_or(default)
+        };
+        let (auction_flat, auction_min, auction_type): (Option<f64>, Option<f64>, Option<String>) =
+            db.conn
+                .query_row(
+                    "SELECT commission_flat, commission_min, auction_type FROM auctions WHERE id = ?1",
+                    params![auction_id],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                )
+                .unwrap_or((None, None, None));
+
+        let rate_key = AuctionType::from_db(auction_type.as_deref()).commission_rate_setting();
+        let (rate, rate_is_fallback) = Self::load_rate(db, rate_key);
+
+        Self {
+            rate,
+            flat: auction_flat.unwrap_or_else(|| setting("commission_flat", 0.0)),
+            min: auction_min.unwrap_or_else(|| setting("commission_min", 0.0)),
+            rate_setting: rate_key,
+            rate_is_fallback,
+        }
+    }
+
+    /// Rate in [0, 1) from the `rate_key` setting, else the fallback rate
+    /// (logged); the flag is true when the fallback was used
+    pub fn load_rate(db: &Database, rate_key: &str) -> (f64, bool) {
+        let rate_setting = |key: &str| {
+            db.get_setting(key)
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|rate| (0.0..1.0).contains(rate))
+        };
+        match rate_setting(rate_key) {
+            Some(rate) => (rate, false),
+            None => {
+                let rate = rate_setting(FALLBACK_COMMISSION_RATE_SETTING)
+                    .unwrap_or(FALLBACK_COMMISSION_RATE);
+                log::warn!(
+                    "{} is missing or invalid; using fallback commission rate {}",
+                    rate_key,
+                    rate
+                );
+                (rate, true)
+            }
+        }
+    }
+
+    /// Warning for the reconciliation result when the fallback rate was used
+    pub fn fallback_warning(&self) -> Option<String> {
+        self.rate_is_fallback
+            .then(|| fallback_rate_warning(self.rate_setting, self.rate))
+    }
+
+    /// Commission for a lot sold at `price`, rounded to cents
+    pub fn amount(&self, price: f64) -> f64 {
+        round2((price * self.rate + self.flat).max(self.min))
+    }
+}
+
+fn fallback_rate_warning(rate_setting: &str, rate: f64) -> String {
+    format!(
+        "{} setting is missing or invalid; commission was charged at the fallback rate {}",
+        rate_setting, rate
+    )
+}
+
 pub struct ReconciliationManager;
 
 impl ReconciliationManager {
@@ -266,50 +733,219 @@ impl ReconciliationManager {
         db: &Database,
         auction_id: &str,
         file_path: &str,
-    ) -> Result<ReconciliationResult, String> {
-        let results = csv_parser::parse_hibid_results(file_path).map_err(|e| e.to_string())?;
+    ) -> Result<ReconciliationResult, AppError> {
+        let results = csv_parser::parse_hibid_results(file_path)?;
+
+        let tx = db.conn.unchecked_transaction()?;
+        let result = Self::reconcile_rows(db, auction_id, results)?;
+        tx.commit()?;
+        Ok(result)
+    }
+
+    /// Reconcile one combined results file covering several auctions, keyed by
+    /// its `Auction` column (auction id, HiBid id or name). All auctions are
+    /// reconciled in one transaction: an unknown auction, or a value matching
+    /// several auctions, aborts the whole file.
+    pub fn reconcile_multi(
+        db: &Database,
+        file_path: &str,
+    ) -> Result<Vec<AuctionReconciliation>, AppError> {
+        let groups = csv_parser::parse_hibid_results_by_auction(file_path)?;
+
+        let mut resolved = Vec::new();
+        for (auction_key, rows) in groups {
+            if auction_key.is_empty() {
+                return Err(AppError::Validation(format!(
+                    "{} result row(s) have no Auction value",
+                    rows.len()
+                )));
+            }
+            let (auction_id, auction_name) = match Self::find_auctions_by_key(db, &auction_key)?
+                .as_slice()
+            {
+                [] => {
+                    return Err(AppError::NotFound(format!(
+                        "Auction '{}' not found",
+                        auction_key
+                    )))
+                }
+                [only] => (only.auction_id.clone(), only.auction_name.clone()),
+                candidates => {
+                    let listed: Vec<String> = candidates
+                        .iter()
+                        .map(|c| format!("{} ({})", c.auction_name, c.auction_id))
+                        .collect();
+                    return Err(AppError::Validation(format!(
+                        "Auction '{}' matches {} auctions: {}; rename one or use its id in the file",
+                        auction_key,
+                        candidates.len(),
+                        listed.join(", ")
+                    )));
+                }
+            };
+            resolved.push((auction_id, auction_name, rows));
+        }
+
+        let tx = db.conn.unchecked_transaction()?;
+        let mut results = Vec::new();
+        for (auction_id, auction_name, rows) in resolved {
+            let result = Self::reconcile_rows(db, &auction_id, rows)?;
+            results.push(AuctionReconciliation {
+                auction_id,
+                auction_name,
+                result,
+            });
+        }
+        tx.commit()?;
+        Ok(results)
+    }
+
+    /// Auctions whose id, HiBid id or name (case-insensitive) equals `key`,
+    /// best match first
+    fn find_auctions_by_key(db: &Database, key: &str) -> Result<Vec<AuctionCandidate>, AppError> {
+        let mut stmt = db.conn.prepare(
+            "SELECT id, name, hibid_auction_id, status,
+                    CASE WHEN id = ?1 THEN 'id'
+                         WHEN hibid_auction_id = ?1 THEN 'hibid_auction_id'
+                         ELSE 'name' END AS matched_on
+             FROM auctions
+             WHERE id = ?1 OR hibid_auction_id = ?1 OR LOWER(TRIM(name)) = LOWER(?1)
+             ORDER BY CASE WHEN id = ?1 THEN 0 WHEN hibid_auction_id = ?1 THEN 1 ELSE 2 END,
+                      created_at DESC",
+        )?;
+        let candidates = stmt
+            .query_map(params![key], |row| {
+                Ok(AuctionCandidate {
+                    file_value: key.to_string(),
+                    auction_id: row.get(0)?,
+                    auction_name: row.get(1)?,
+                    hibid_auction_id: row.get(2)?,
+                    status: row.get(3)?,
+                    matched_on: row.get(4)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(candidates)
+    }
+
+    /// Auctions named by the results file's `Auction` column, one entry per
+    /// matching auction. Files without that column yield no candidates.
+    pub fn detect_auction(
+        db: &Database,
+        file_path: &str,
+    ) -> Result<Vec<AuctionCandidate>, AppError> {
+        let mut keys: Vec<String> = Vec::new();
+        for row in csv_parser::parse_hibid_results(file_path)? {
+            let key = row.auction.as_deref().unwrap_or("").trim().to_string();
+            if !key.is_empty() && !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+
+        let mut candidates = Vec::new();
+        for key in keys {
+            candidates.extend(Self::find_auctions_by_key(db, &key)?);
+        }
+        Ok(candidates)
+    }
+
+    /// Auction a single-auction results file belongs to, for auto-detect.
+    /// Fails unless the file names exactly one auction that matches exactly one record.
+    pub fn resolve_detected_auction(db: &Database, file_path: &str) -> Result<String, AppError> {
+        let candidates = Self::detect_auction(db, file_path)?;
+        let mut file_values: Vec<&str> = candidates.iter().map(|c| c.file_value.as_str()).collect();
+        file_values.dedup();
+        match candidates.as_slice() {
+            [] => Err(AppError::Validation(
+                "Could not detect the auction: the results file has no matching Auction value"
+                    .to_string(),
+            )),
+            [only] => Ok(only.auction_id.clone()),
+            _ if file_values.len() > 1 => Err(AppError::Validation(format!(
+                "Results file covers {} auctions ({}); use multi-auction reconciliation",
+                file_values.len(),
+                file_values.join(", ")
+            ))),
+            _ => Err(AppError::Validation(format!(
+                "Auction '{}' matches {} auctions; pick one manually",
+                file_values[0],
+                candidates.len()
+            ))),
+        }
+    }
 
+    /// Apply parsed result rows to one auction. Callers own the transaction.
+    fn reconcile_rows(
+        db: &Database,
+        auction_id: &str,
+        results: Vec<csv_parser::HiBidResultRow>,
+    ) -> Result<ReconciliationResult, AppError> {
         let mut sold_count = 0;
         let mut buyback_count = 0;
         let mut total_revenue = 0.0;
         let mut total_profit = 0.0;
+        let mut total_buyers_premium = 0.0;
         let mut errors = Vec::new();
 
-        let tx = db.conn.unchecked_transaction().map_err(|e| e.to_string())?;
-
         let mut bb_stmt = db
             .conn
-            .prepare("SELECT name FROM buybackers WHERE is_active = 1")
-            .map_err(|e| e.to_string())?;
+            .prepare("SELECT name FROM buybackers WHERE is_active = 1")?;
         let buyback_names: Vec<String> = bb_stmt
-            .query_map([], |row| row.get::<_, String>(0))
-            .map_err(|e| e.to_string())?
+            .query_map([], |row| row.get::<_, String>(0))?
             .filter_map(|r| r.ok())
             .map(|n| n.trim().to_lowercase())
             .collect();
 
-        let legacy_id: String = db
+        let internal_bidder_setting = db
+            .get_setting("ron_larsson_bidder_id")
+            .unwrap_or_else(|| "5046".to_string());
+        let internal_bidder_ids = parse_internal_bidder_ids(&internal_bidder_setting);
+
+        let commission_terms = CommissionTerms::load(db, auction_id);
+        let warnings: Vec<String> = commission_terms.fallback_warning().into_iter().collect();
+        let buyers_premium_rate = load_buyers_premium_rate(db);
+        let pricing_engine = PricingEngine::new(&db.conn)?;
+        let created_by = db.current_user();
+        let lot_prefix: Option<String> = db
             .conn
             .query_row(
-                "SELECT value FROM settings WHERE key = 'ron_larsson_bidder_id'",
-                [],
+                "SELECT lot_prefix FROM auctions WHERE id = ?1",
+                params![auction_id],
                 |row| row.get(0),
             )
-            .unwrap_or_else(|_| "5046".to_string());
+            .optional()?
+            .flatten();
 
-        let commission_rate: f64 = db
+        // A winning bidder with a zero/blank high bid usually means a malformed results file
+        let allow_zero_high_bid: bool = db
             .conn
             .query_row(
-                "SELECT value FROM settings WHERE key = 'default_commission_rate'",
+                "SELECT value FROM settings WHERE key = 'reconcile_allow_zero_high_bid'",
                 [],
-                |row| {
-                    let val: String = row.get(0)?;
-                    Ok(val.parse::<f64>().unwrap_or(0.15))
-                },
+                |row| row.get::<_, String>(0),
             )
-            .unwrap_or(0.15);
+            .map(|value| value == "1")
+            .unwrap_or(false);
+
+        // Listed items of this auction, matched by lot number below
+        let mut listed_stmt = db.conn.prepare(
+            "SELECT id, COALESCE(lot_number, ''), raw_title, cost_price, min_price
+                 FROM inventory_items
+                 WHERE auction_id = ?1
+                   AND current_status = 'Listed'",
+        )?;
+        let listed_items: Vec<(String, String, String, f64, f64)> = listed_stmt
+            .query_map(params![auction_id], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let mut details = Vec::new();
+        let mut matched_item_ids: HashSet<String> = HashSet::new();
+        let seasons = db.load_season_map();
 
         for row in results {
+            // Exported lots may carry the auction prefix ("W34-042"); stored lots don't
+            let lot_number = hibid::strip_lot_prefix(lot_prefix.as_deref(), &row.lot_number);
             let high_bid = csv_parser::clean_hibid_cents_price(&row.high_bid);
             let max_bid = row
                 .max_bid
@@ -317,29 +953,57 @@ impl ReconciliationManager {
                 .map(csv_parser::clean_hibid_cents_price)
                 .unwrap_or(0.0);
 
-            let has_valid_bidder = !row.winning_bidder.trim().is_empty()
-                && !row.winning_bidder.trim().eq_ignore_ascii_case("floor")
-                && high_bid > 0.0;
+            let has_bidder = !row.winning_bidder.trim().is_empty()
+                && !row.winning_bidder.trim().eq_ignore_ascii_case("floor");
+            if has_bidder && high_bid <= 0.0 && !allow_zero_high_bid {
+                errors.push(format!(
+                    "Lot {}: Winning bidder {} has no positive high bid ({:?})",
+                    row.lot_number,
+                    row.winning_bidder.trim(),
+                    row.high_bid
+                ));
+                continue;
+            }
+            let has_valid_bidder = has_bidder && high_bid > 0.0;
 
             let winner_lower = row.winning_bidder.to_lowercase();
             let is_buyback_detected = buyback_names
                 .iter()
                 .any(|bb_name| winner_lower.contains(bb_name))
-                || row.bidder_id == legacy_id;
+                || internal_bidder_ids.contains(&row.bidder_id.trim());
 
-            let item_data: rusqlite::Result<(String, f64, f64)> = tx.query_row(
-                "SELECT id, cost_price, min_price
-                 FROM inventory_items
-                 WHERE lot_number = ?1
-                   AND auction_id = ?2
-                   AND current_status = 'Listed'",
-                params![row.lot_number, auction_id],
-                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
-            );
+            // Exact (case-insensitive) match first; numeric normalization is only a fallback
+            let item_data = listed_items
+                .iter()
+                .find(|(id, lot, _, _, _)| {
+                    !matched_item_ids.contains(id) && lot_numbers_equal(lot, &lot_number)
+                })
+                .map(|found| {
+                    (
+                        (found.0.clone(), found.2.clone(), found.3, found.4),
+                        MatchStrategy::Exact,
+                    )
+                })
+                .or_else(|| {
+                    let normalized = normalize_lot_number(&lot_number);
+                    let found = listed_items.iter().find(|(id, lot, _, _, _)| {
+                        !matched_item_ids.contains(id) && normalize_lot_number(lot) == normalized
+                    })?;
+                    log::info!(
+                        "Lot {} matched item lot {} via normalized lot number {}",
+                        row.lot_number,
+                        found.1,
+                        normalized
+                    );
+                    Some((
+                        (found.0.clone(), found.2.clone(), found.3, found.4),
+                        MatchStrategy::Normalized,
+                    ))
+                });
 
-            let (item_id, cost, min_price_snapshot) = match item_data {
-                Ok(data) => data,
-                Err(_) => {
+            let ((item_id, title, cost, min_price_snapshot), match_strategy) = match item_data {
+                Some(data) => data,
+                None => {
                     errors.push(format!(
                         "Lot {}: Item not found or not listed in this auction",
                         row.lot_number
@@ -347,6 +1011,7 @@ impl ReconciliationManager {
                     continue;
                 }
             };
+            matched_item_ids.insert(item_id.clone());
 
             let is_buyback = has_valid_bidder && is_buyback_detected;
             let status = if is_buyback {
@@ -357,8 +1022,16 @@ impl ReconciliationManager {
                 "Unsold"
             };
 
-            let commission = if status == "Sold" {
-                high_bid * commission_rate
+            let commission_exempt = status == "Sold" && {
+                let source: String = db.conn.query_row(
+                    "SELECT COALESCE(source, '') FROM inventory_items WHERE id = ?1",
+                    params![item_id],
+                    |r| r.get(0),
+                )?;
+                pricing_engine.is_commission_exempt(&source)
+            };
+            let commission = if status == "Sold" && !commission_exempt {
+                commission_terms.amount(high_bid)
             } else {
                 0.0
             };
@@ -367,23 +1040,34 @@ impl ReconciliationManager {
             } else {
                 0.0
             };
+            // Informational only: the buyer pays it on top of the hammer price
+            let buyers_premium = if status == "Sold" {
+                round2(high_bid * buyers_premium_rate)
+            } else {
+                0.0
+            };
 
-            tx.execute(
+            db.conn.execute(
                 "UPDATE inventory_items
                  SET current_status = ?1,
                      sold_at = CASE WHEN ?1 = 'Sold' THEN CURRENT_TIMESTAMP ELSE NULL END
                  WHERE id = ?2",
                 params![status, item_id],
-            )
-            .map_err(|e| e.to_string())?;
+            )?;
 
-            tx.execute(
+            let result_id = uuid::Uuid::new_v4().to_string();
+            db.conn.execute(
                 "INSERT INTO auction_results
                  (id, auction_id, item_id, winning_bidder, bidder_id, high_bid, max_bid,
-                  is_buyback, commission_rate, commission_amount, net_profit, item_status, min_price_snapshot)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                  is_buyback, commission_rate, commission_amount, net_profit, item_status, min_price_snapshot,
+                  days_listed, commission_exempt, created_by, buyers_premium)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13,
+                         CASE WHEN ?12 = 'Sold' THEN (
+                             SELECT julianday(sold_at) - julianday(listed_at)
+                             FROM inventory_items WHERE id = ?3
+                         ) END, ?14, ?15, ?16)",
                 params![
-                    uuid::Uuid::new_v4().to_string(),
+                    result_id,
                     auction_id,
                     item_id,
                     row.winning_bidder,
@@ -391,20 +1075,35 @@ impl ReconciliationManager {
                     high_bid,
                     max_bid,
                     is_buyback,
-                    commission_rate,
+                    commission_terms.rate,
                     commission,
                     net_profit,
                     status,
-                    min_price_snapshot
+                    min_price_snapshot,
+                    commission_exempt,
+                    created_by,
+                    buyers_premium
                 ],
-            )
-            .map_err(|e| e.to_string())?;
+            )?;
+            db.record_historical_sale(&result_id, &seasons)?;
+
+            details.push(LotOutcome {
+                lot_number: row.lot_number.clone(),
+                item_id: item_id.clone(),
+                title,
+                status: status.to_string(),
+                high_bid,
+                commission,
+                net_profit,
+                match_strategy,
+            });
 
             match status {
                 "Sold" => {
                     sold_count += 1;
                     total_revenue += high_bid;
                     total_profit += net_profit;
+                    total_buyers_premium += buyers_premium;
                 }
                 "Buyback" => {
                     buyback_count += 1;
@@ -413,25 +1112,287 @@ impl ReconciliationManager {
             }
         }
 
-        tx.commit().map_err(|e| e.to_string())?;
+        let unsold = Self::settle_unreported_lots(db, auction_id)?;
 
-        db.conn
-            .execute(
-                "UPDATE auctions SET status = 'Completed' WHERE id = ?1",
-                params![auction_id],
-            )
-            .map_err(|e| e.to_string())?;
+        db.conn.execute(
+            "UPDATE auctions SET status = 'Completed' WHERE id = ?1",
+            params![auction_id],
+        )?;
 
         Ok(ReconciliationResult {
             sold_count,
             buyback_count,
             total_revenue,
             total_profit,
+            total_buyers_premium: round2(total_buyers_premium),
             errors,
+            warnings,
+            details,
+            unsold,
+        })
+    }
+
+    /// Undo the results of specific lots (e.g. a buyer backed out): their
+    /// auction_results and historical_sales rows are deleted and the items go
+    /// back to Listed in the auction. Lot numbers match ignoring case and
+    /// surrounding whitespace. Fails without changing anything if a lot has no
+    /// result in the auction or its result is already paid.
+    pub fn revert_lots(
+        db: &Database,
+        auction_id: &str,
+        lot_numbers: &[String],
+    ) -> Result<usize, AppError> {
+        let mut lots: Vec<&str> = lot_numbers
+            .iter()
+            .map(|lot| lot.trim())
+            .filter(|lot| !lot.is_empty())
+            .collect();
+        lots.sort_by_key(|lot| lot.to_uppercase());
+        lots.dedup_by(|a, b| lot_numbers_equal(a, b));
+        if lots.is_empty() {
+            return Err(AppError::Validation(
+                "At least one lot number is required".to_string(),
+            ));
+        }
+
+        let tx = db.conn.unchecked_transaction()?;
+        let mut results = Vec::new();
+        let mut missing = Vec::new();
+        {
+            let mut stmt = tx.prepare(
+                "SELECT ar.id, ar.item_id, COALESCE(ar.is_paid, 0)
+                 FROM auction_results ar
+                 JOIN inventory_items i ON i.id = ar.item_id
+                 WHERE ar.auction_id = ?1
+                   AND UPPER(TRIM(i.lot_number)) = UPPER(?2)",
+            )?;
+            for lot in &lots {
+                let rows = stmt
+                    .query_map(params![auction_id, lot], |row| {
+                        Ok((
+                            row.get::<_, String>(0)?,
+                            row.get::<_, String>(1)?,
+                            row.get::<_, bool>(2)?,
+                        ))
+                    })?
+                    .collect::<Result<Vec<_>>>()?;
+                if rows.is_empty() {
+                    missing.push(lot.to_string());
+                }
+                if rows.iter().any(|(_, _, is_paid)| *is_paid) {
+                    return Err(AppError::Validation(format!(
+                        "Lot {} is already paid and cannot be reverted",
+                        lot
+                    )));
+                }
+                results.extend(rows);
+            }
+        }
+        if !missing.is_empty() {
+            return Err(AppError::NotFound(format!(
+                "No results for lot(s) {} in auction {}",
+                missing.join(", "),
+                auction_id
+            )));
+        }
+
+        let mut reverted_items = HashSet::new();
+        for (result_id, item_id, _) in &results {
+            tx.execute(
+                "DELETE FROM historical_sales WHERE id = ?1",
+                params![result_id],
+            )?;
+            tx.execute(
+                "DELETE FROM auction_results WHERE id = ?1",
+                params![result_id],
+            )?;
+            tx.execute(
+                "UPDATE inventory_items
+                 SET current_status = 'Listed',
+                     auction_id = ?1,
+                     sold_at = NULL,
+                     listed_at = COALESCE(listed_at, CURRENT_TIMESTAMP)
+                 WHERE id = ?2",
+                params![auction_id, item_id],
+            )?;
+            reverted_items.insert(item_id.as_str());
+        }
+        tx.commit()?;
+
+        log::info!(
+            "Reverted {} lot(s) of auction {}: {}",
+            reverted_items.len(),
+            auction_id,
+            lots.join(", ")
+        );
+        Ok(reverted_items.len())
+    }
+
+    /// Items still Listed in the auction once the results are applied. With
+    /// `reconcile_unreported_status` set to InStock they roll over (auction cleared);
+    /// with Buyback they are treated as bought in. Anything else leaves them Listed.
+    fn settle_unreported_lots(
+        db: &Database,
+        auction_id: &str,
+    ) -> Result<Vec<UnreportedLot>, AppError> {
+        let target = match db
+            .get_setting(UNREPORTED_STATUS_SETTING)
+            .as_deref()
+            .map(str::trim)
+        {
+            Some("InStock") => "InStock",
+            Some("Buyback") => "Buyback",
+            _ => "Listed",
+        };
+
+        let mut stmt = db.conn.prepare(
+            "SELECT id, COALESCE(lot_number, ''), raw_title
+             FROM inventory_items
+             WHERE auction_id = ?1
+               AND current_status = 'Listed'",
+        )?;
+        let mut lots = stmt
+            .query_map(params![auction_id], |row| {
+                Ok(UnreportedLot {
+                    item_id: row.get(0)?,
+                    lot_number: row.get(1)?,
+                    title: row.get(2)?,
+                    status: target.to_string(),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        lots.sort_by(|a, b| crate::auctions::natural_lot_cmp(&a.lot_number, &b.lot_number));
+
+        if target != "Listed" {
+            db.conn.execute(
+                "UPDATE inventory_items
+                 SET current_status = ?1,
+                     auction_id = CASE WHEN ?1 = 'InStock' THEN NULL ELSE auction_id END
+                 WHERE auction_id = ?2
+                   AND current_status = 'Listed'",
+                params![target, auction_id],
+            )?;
+        }
+        if !lots.is_empty() {
+            log::info!(
+                "Auction {}: {} listed item(s) missing from results, now {}",
+                auction_id,
+                lots.len(),
+                target
+            );
+        }
+        Ok(lots)
+    }
+
+    /// Record a profit adjustment for an item. Positive amounts are credits
+    /// (vendor credit, return refund); negative amounts are extra charges.
+    pub fn add_adjustment(
+        db: &Database,
+        item_id: &str,
+        amount: f64,
+        reason: Option<&str>,
+    ) -> Result<String, AppError> {
+        if amount == 0.0 || !amount.is_finite() {
+            return Err(AppError::Validation(
+                "Adjustment amount must be a non-zero number".into(),
+            ));
+        }
+        let item_exists: bool = db.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM inventory_items WHERE id = ?1)",
+            params![item_id],
+            |row| row.get(0),
+        )?;
+        if !item_exists {
+            return Err(AppError::NotFound(format!("Item {} not found", item_id)));
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        db.conn.execute(
+            "INSERT INTO adjustments (id, item_id, amount, reason) VALUES (?1, ?2, ?3, ?4)",
+            params![id, item_id, round2(amount), reason.map(str::trim)],
+        )?;
+        Ok(id)
+    }
+
+    /// Record a cash (non-HiBid) sale: marks the item Sold, charges the
+    /// `cash_sale_commission_rate` and feeds historical_sales. The sale counts
+    /// in the revenue reports through `SALE_RESULTS_SQL`.
+    pub fn record_cash_sale(
+        db: &Database,
+        item_id: &str,
+        sale_price: f64,
+        buyer_name: Option<&str>,
+    ) -> Result<CashSaleResult, AppError> {
+        if sale_price <= 0.0 || !sale_price.is_finite() {
+            return Err(AppError::Validation(
+                "Sale price must be a positive number".into(),
+            ));
+        }
+        let item: Option<(String, f64, Option<String>)> = db
+            .conn
+            .query_row(
+                "SELECT current_status, cost_price, auction_id FROM inventory_items WHERE id = ?1",
+                params![item_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+        let (status, cost, auction_id) =
+            item.ok_or_else(|| AppError::NotFound(format!("Item {} not found", item_id)))?;
+        if status == "Sold" || status == "Scrap" {
+            return Err(AppError::Validation(format!(
+                "Item {} is {} and cannot be sold",
+                item_id, status
+            )));
+        }
+
+        let rate_key = AuctionType::Cash.commission_rate_setting();
+        let (commission_rate, rate_is_fallback) = CommissionTerms::load_rate(db, rate_key);
+        let commission = round2(sale_price * commission_rate);
+        let net_profit = round2(sale_price - cost - commission);
+        let seasons = db.load_season_map();
+
+        let tx = db.conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE inventory_items
+             SET current_status = 'Sold', sold_at = CURRENT_TIMESTAMP, auction_id = NULL
+             WHERE id = ?1",
+            params![item_id],
+        )?;
+        if let Some(auction_id) = &auction_id {
+            tx.execute(
+                "UPDATE auctions SET total_lots = (SELECT COUNT(*) FROM inventory_items WHERE auction_id = ?1) WHERE id = ?1",
+                params![auction_id],
+            )?;
+        }
+
+        let sale_id = uuid::Uuid::new_v4().to_string();
+        tx.execute(
+            "INSERT INTO cash_sales
+             (id, item_id, buyer_name, sale_price, commission_rate, commission_amount, net_profit)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                sale_id,
+                item_id,
+                buyer_name.map(str::trim).filter(|n| !n.is_empty()),
+                sale_price,
+                commission_rate,
+                commission,
+                net_profit
+            ],
+        )?;
+        db.record_cash_historical_sale(&sale_id, &seasons)?;
+        tx.commit()?;
+
+        Ok(CashSaleResult {
+            sale_id,
+            commission_rate,
+            commission_amount: commission,
+            net_profit,
+            warning: rate_is_fallback.then(|| fallback_rate_warning(rate_key, commission_rate)),
         })
     }
 
-    pub fn generate_pl_report(db: &Database) -> Result<ProfitLossReport, String> {
+    pub fn generate_pl_report(db: &Database) -> Result<ProfitLossReport, AppError> {
         Self::generate_pl_report_filtered(db, "all".to_string(), None, None)
     }
 
@@ -440,15 +1401,23 @@ impl ReconciliationManager {
         period: String,
         date_from: Option<String>,
         date_to: Option<String>,
-    ) -> Result<ProfitLossReport, String> {
+    ) -> Result<ProfitLossReport, AppError> {
+        let adjustment_filter = build_period_filter(
+            &period,
+            date_from.clone(),
+            date_to.clone(),
+            "adj.created_at",
+        )?;
+        let scrap_filter =
+            build_period_filter(&period, date_from.clone(), date_to.clone(), SCRAPPED_AT_SQL)?;
         let filter = build_period_filter(&period, date_from, date_to, "ar.created_at")?;
         let sql = format!(
             "
             SELECT
                 COUNT(*) as total_lots,
-                SUM(CASE WHEN {status_sql} = 'Sold' THEN 1 ELSE 0 END) as sold_items,
-                SUM(CASE WHEN {status_sql} = 'Buyback' THEN 1 ELSE 0 END) as buyback_count,
-                SUM(CASE WHEN {status_sql} = 'Unsold' THEN 1 ELSE 0 END) as unsold_count,
+                COALESCE(SUM(CASE WHEN {status_sql} = 'Sold' THEN 1 ELSE 0 END), 0) as sold_items,
+                COALESCE(SUM(CASE WHEN {status_sql} = 'Buyback' THEN 1 ELSE 0 END), 0) as buyback_count,
+                COALESCE(SUM(CASE WHEN {status_sql} = 'Unsold' THEN 1 ELSE 0 END), 0) as unsold_count,
                 COALESCE(SUM(CASE WHEN {status_sql} = 'Sold' THEN ar.high_bid ELSE 0 END), 0) as revenue,
                 COALESCE(SUM(CASE WHEN {status_sql} = 'Sold' THEN i.cost_price ELSE 0 END), 0) as cogs,
                 COALESCE(SUM(CASE WHEN {status_sql} = 'Sold' THEN ar.commission_amount ELSE 0 END), 0) as expenses,
@@ -460,17 +1429,22 @@ impl ReconciliationManager {
                         END
                     ),
                     0
-                ) as net_profit
-            FROM auction_results ar
+                ) as net_profit,
+                COALESCE(
+                    SUM(CASE WHEN {status_sql} = 'Sold' AND ar.commission_exempt = 1 THEN ar.high_bid ELSE 0 END),
+                    0
+                ) as exempt_revenue
+            FROM {sales} ar
             JOIN inventory_items i ON ar.item_id = i.id
             WHERE 1 = 1
             {filter_clause}
             ",
+            sales = SALE_RESULTS_SQL,
             status_sql = ITEM_STATUS_SQL,
             filter_clause = filter.clause
         );
 
-        let mapper = |row: &rusqlite::Row<'_>| -> rusqlite::Result<(i64, i64, i64, i64, f64, f64, f64, f64)> {
+        let mapper = |row: &rusqlite::Row<'_>| -> rusqlite::Result<(i64, i64, i64, i64, f64, f64, f64, f64, f64)> {
             Ok((
                 row.get(0)?,
                 row.get(1)?,
@@ -480,6 +1454,7 @@ impl ReconciliationManager {
                 row.get(5)?,
                 row.get(6)?,
                 row.get(7)?,
+                row.get(8)?,
             ))
         };
 
@@ -492,18 +1467,20 @@ impl ReconciliationManager {
             cogs,
             expenses,
             _net_profit_sql,
+            exempt_revenue,
         ) = if let Some((from, to)) = &filter.custom_range {
-            db.conn
-                .query_row(&sql, params![from, to], mapper)
-                .map_err(|e| e.to_string())?
+            db.conn.query_row(&sql, params![from, to], mapper)?
         } else {
-            db.conn
-                .query_row(&sql, [], mapper)
-                .map_err(|e| e.to_string())?
+            db.conn.query_row(&sql, [], mapper)?
         };
         let report_difference_total = calculate_filtered_report_difference_total(db, &filter)?;
         let unmatched_adjustment = get_unmatched_difference_adjustment(db, &filter)?;
-        let adjusted_net_profit = round2(report_difference_total + unmatched_adjustment);
+        let total_adjustments = round2(get_item_adjustments_total(db, &adjustment_filter)?);
+        let (scrapped_count, scrap_writeoff) = get_scrap_writeoff(db, &scrap_filter)?;
+        let scrap_writeoff = round2(scrap_writeoff);
+        let adjusted_net_profit = round2(
+            report_difference_total + unmatched_adjustment + total_adjustments - scrap_writeoff,
+        );
 
         let gross_profit = revenue - cogs;
         let margin_percent = if revenue > 0.0 {
@@ -536,6 +1513,10 @@ impl ReconciliationManager {
             unsold_count: unsold_count as i32,
             sell_through_rate,
             avg_sale_price,
+            total_adjustments,
+            exempt_revenue,
+            scrap_writeoff,
+            scrapped_count: scrapped_count as i32,
             period_label: filter.label,
         })
     }
@@ -545,7 +1526,7 @@ impl ReconciliationManager {
         period: String,
         date_from: Option<String>,
         date_to: Option<String>,
-    ) -> Result<Vec<AuctionSummary>, String> {
+    ) -> Result<Vec<AuctionSummary>, AppError> {
         let filter = build_period_filter(&period, date_from, date_to, "ar.created_at")?;
         let sql = format!(
             "
@@ -582,7 +1563,7 @@ impl ReconciliationManager {
             filter_clause = filter.clause
         );
 
-        let mut stmt = db.conn.prepare(&sql).map_err(|e| e.to_string())?;
+        let mut stmt = db.conn.prepare(&sql)?;
         let mapper = |row: &rusqlite::Row<'_>| -> rusqlite::Result<AuctionSummary> {
             let total_revenue: f64 = row.get(7)?;
             let net_profit: f64 = row.get(10)?;
@@ -613,12 +1594,9 @@ impl ReconciliationManager {
             stmt.query_map(params![from, to], mapper)
         } else {
             stmt.query_map([], mapper)
-        }
-        .map_err(|e| e.to_string())?;
+        }?;
 
-        let mut summaries = rows
-            .collect::<rusqlite::Result<Vec<_>>>()
-            .map_err(|e| e.to_string())?;
+        let mut summaries = rows.collect::<rusqlite::Result<Vec<_>>>()?;
 
         for summary in &mut summaries {
             let report_total = calculate_auction_report_difference_total(db, &summary.auction_id)?;
@@ -646,7 +1624,7 @@ impl ReconciliationManager {
         period: String,
         date_from: Option<String>,
         date_to: Option<String>,
-    ) -> Result<Vec<VendorBreakdown>, String> {
+    ) -> Result<Vec<VendorBreakdown>, AppError> {
         let filter = build_period_filter(&period, date_from, date_to, "ar.created_at")?;
         let sql = format!(
             "
@@ -658,18 +1636,19 @@ impl ReconciliationManager {
                 COALESCE(SUM(ar.high_bid), 0) as revenue,
                 COALESCE(SUM(ar.high_bid * 1.15), 0) as revenue_with_comm,
                 COALESCE(SUM(COALESCE(ar.high_bid, 0) - COALESCE(i.cost_price, 0)), 0) as profit_loss
-            FROM auction_results ar
+            FROM {sales} ar
             JOIN inventory_items i ON ar.item_id = i.id
             WHERE {status_sql} = 'Sold'
             {filter_clause}
             GROUP BY source
             ORDER BY revenue DESC
             ",
+            sales = SALE_RESULTS_SQL,
             status_sql = ITEM_STATUS_SQL,
             filter_clause = filter.clause
         );
 
-        let mut stmt = db.conn.prepare(&sql).map_err(|e| e.to_string())?;
+        let mut stmt = db.conn.prepare(&sql)?;
         let mapper = |row: &rusqlite::Row<'_>| -> rusqlite::Result<VendorBreakdown> {
             let total_retail: f64 = row.get(2)?;
             let total_cost: f64 = row.get(3)?;
@@ -702,27 +1681,718 @@ impl ReconciliationManager {
             stmt.query_map(params![from, to], mapper)
         } else {
             stmt.query_map([], mapper)
-        }
-        .map_err(|e| e.to_string())?;
+        }?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(AppError::from)
+    }
+    pub fn get_auction_commission(
+        db: &Database,
+        auction_id: &str,
+    ) -> Result<AuctionCommissionSummary, AppError> {
+        let sql = format!(
+            "
+            SELECT
+                COUNT(*),
+                COALESCE(SUM(ar.high_bid), 0),
+                COALESCE(SUM(ar.commission_amount), 0)
+            FROM auction_results ar
+            WHERE ar.auction_id = ?1
+              AND {status_sql} = 'Sold'
+            ",
+            status_sql = ITEM_STATUS_SQL
+        );
+
+        let (sold_count, total_revenue, total_commission): (i64, f64, f64) =
+            db.conn.query_row(&sql, params![auction_id], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?;
+
+        let average_commission_rate = if total_revenue > 0.0 {
+            total_commission / total_revenue
+        } else {
+            0.0
+        };
+
+        Ok(AuctionCommissionSummary {
+            auction_id: auction_id.to_string(),
+            sold_count: sold_count as i32,
+            total_revenue: round2(total_revenue),
+            total_commission: round2(total_commission),
+            average_commission_rate,
+        })
+    }
+
+    pub fn get_bid_spread(db: &Database, auction_id: &str) -> Result<Vec<BidSpreadRow>, AppError> {
+        let sql = format!(
+            "
+            SELECT
+                i.id,
+                i.lot_number,
+                i.raw_title,
+                {status_sql},
+                COALESCE(ar.high_bid, 0),
+                COALESCE(ar.max_bid, 0)
+            FROM auction_results ar
+            JOIN inventory_items i ON ar.item_id = i.id
+            WHERE ar.auction_id = ?1
+              AND COALESCE(ar.high_bid, 0) > 0
+            ORDER BY i.lot_number
+            ",
+            status_sql = ITEM_STATUS_SQL
+        );
+
+        let mut stmt = db.conn.prepare(&sql)?;
+        let rows = stmt.query_map(params![auction_id], |row| {
+            let high_bid: f64 = row.get(4)?;
+            let raw_max_bid: f64 = row.get(5)?;
+            // Results without a recorded max bid closed at the high bid
+            let max_bid = if raw_max_bid > 0.0 {
+                raw_max_bid
+            } else {
+                high_bid
+            };
+            let spread = round2((max_bid - high_bid).max(0.0));
+            let spread_pct = if high_bid > 0.0 {
+                (spread / high_bid) * 100.0
+            } else {
+                0.0
+            };
+            Ok(BidSpreadRow {
+                item_id: row.get(0)?,
+                lot_number: row.get(1)?,
+                title: row.get(2)?,
+                item_status: row.get(3)?,
+                high_bid,
+                max_bid,
+                spread,
+                spread_pct,
+                left_on_table: spread > high_bid * BID_SPREAD_FLAG_RATIO,
+                closed_at_max: spread < 0.01,
+            })
+        })?;
 
         rows.collect::<rusqlite::Result<Vec<_>>>()
-            .map_err(|e| e.to_string())
+            .map_err(AppError::from)
+    }
+
+    /// Expected (min price floor) vs actual (high bid) for each sold lot of an auction.
+    /// The floor is the min price snapshot taken at reconciliation when present.
+    pub fn get_reconciliation_variance(
+        db: &Database,
+        auction_id: &str,
+    ) -> Result<ReconciliationVariance, AppError> {
+        let sql = format!(
+            "
+            SELECT
+                i.id,
+                i.lot_number,
+                i.raw_title,
+                COALESCE(ar.min_price_snapshot, i.min_price, 0),
+                COALESCE(ar.high_bid, 0),
+                COALESCE(i.cost_price, 0)
+            FROM auction_results ar
+            JOIN inventory_items i ON ar.item_id = i.id
+            WHERE ar.auction_id = ?1
+              AND {status_sql} = 'Sold'
+            ORDER BY i.lot_number
+            ",
+            status_sql = ITEM_STATUS_SQL
+        );
+
+        let mut stmt = db.conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map(params![auction_id], |row| {
+                let min_price: f64 = row.get(3)?;
+                let high_bid: f64 = row.get(4)?;
+                let cost: f64 = row.get(5)?;
+                let variance = round2(high_bid - min_price);
+                let projected_profit = round2(min_price - cost);
+                let actual_profit = round2(high_bid - cost);
+                Ok(VarianceRow {
+                    item_id: row.get(0)?,
+                    lot_number: row.get(1)?,
+                    title: row.get(2)?,
+                    min_price,
+                    high_bid,
+                    variance,
+                    variance_pct: if min_price > 0.0 {
+                        (variance / min_price) * 100.0
+                    } else {
+                        0.0
+                    },
+                    projected_profit,
+                    actual_profit,
+                    profit_variance: round2(actual_profit - projected_profit),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let total_min_price: f64 = rows.iter().map(|r| r.min_price).sum();
+        let total_high_bid: f64 = rows.iter().map(|r| r.high_bid).sum();
+        let total_projected_profit: f64 = rows.iter().map(|r| r.projected_profit).sum();
+        let total_actual_profit: f64 = rows.iter().map(|r| r.actual_profit).sum();
+
+        Ok(ReconciliationVariance {
+            auction_id: auction_id.to_string(),
+            sold_count: rows.len() as i32,
+            over_floor_count: rows.iter().filter(|r| r.variance >= 0.0).count() as i32,
+            under_floor_count: rows.iter().filter(|r| r.variance < 0.0).count() as i32,
+            total_min_price: round2(total_min_price),
+            total_high_bid: round2(total_high_bid),
+            total_variance: round2(total_high_bid - total_min_price),
+            total_projected_profit: round2(total_projected_profit),
+            total_actual_profit: round2(total_actual_profit),
+            total_profit_variance: round2(total_actual_profit - total_projected_profit),
+            rows,
+        })
+    }
+
+    /// Average and median days-to-sell (listing to sale) overall and per category
+    pub fn get_velocity_stats(db: &Database) -> Result<VelocityStats, AppError> {
+        let mut stmt = db.conn.prepare(&format!(
+            "SELECT COALESCE(NULLIF(TRIM(i.category), ''), 'Uncategorized'), ar.days_listed
+             FROM {} ar
+             JOIN inventory_items i ON i.id = ar.item_id
+             WHERE ar.item_status = 'Sold'
+               AND ar.days_listed IS NOT NULL
+               AND ar.days_listed >= 0",
+            SALE_RESULTS_SQL
+        ))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut per_category: std::collections::BTreeMap<String, Vec<f64>> =
+            std::collections::BTreeMap::new();
+        for (category, days) in &rows {
+            per_category
+                .entry(category.clone())
+                .or_default()
+                .push(*days);
+        }
+
+        Ok(VelocityStats {
+            overall: velocity_bucket(
+                "All".to_string(),
+                rows.iter().map(|(_, days)| *days).collect(),
+            ),
+            by_category: per_category
+                .into_iter()
+                .map(|(category, days)| velocity_bucket(category, days))
+                .collect(),
+        })
+    }
+
+    /// Average and median days items spend in a status before moving to the
+    /// next, per transition seen in `item_status_history` (e.g. InStock→Listed,
+    /// Listed→Unsold, Unsold→Listed, Listed→Sold). Every pass counts, so a
+    /// relisted item contributes each listing. A transition is only measured
+    /// when the item's entry into the earlier status was recorded too; items
+    /// from before the history table skip their first change. Dates
+    /// (YYYY-MM-DD, inclusive) filter on when the transition happened.
+    pub fn get_status_durations(
+        db: &Database,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+    ) -> Result<Vec<StatusDuration>, AppError> {
+        let mut stmt = db.conn.prepare(
+            "SELECT from_status, to_status, julianday(changed_at) - julianday(entered_at)
+             FROM (
+                 SELECT from_status, to_status, changed_at,
+                        LAG(changed_at) OVER (PARTITION BY item_id ORDER BY id) AS entered_at,
+                        LAG(to_status) OVER (PARTITION BY item_id ORDER BY id) AS entered_status
+                 FROM item_status_history
+             )
+             WHERE from_status IS NOT NULL
+               AND entered_status = from_status
+               AND (?1 IS NULL OR datetime(changed_at) >= datetime(?1))
+               AND (?2 IS NULL OR datetime(changed_at) < datetime(?2, '+1 day'))",
+        )?;
+        let rows = stmt
+            .query_map(params![start_date, end_date], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, f64>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut transitions: HashMap<(String, String), Vec<f64>> = HashMap::new();
+        for (from_status, to_status, days) in rows {
+            transitions
+                .entry((from_status, to_status))
+                .or_default()
+                .push(days.max(0.0));
+        }
+        let mut durations: Vec<StatusDuration> = transitions
+            .into_iter()
+            .map(|((from_status, to_status), days)| {
+                let bucket = velocity_bucket(String::new(), days);
+                StatusDuration {
+                    from_status,
+                    to_status,
+                    item_count: bucket.sold_count,
+                    avg_days: bucket.avg_days,
+                    median_days: bucket.median_days,
+                }
+            })
+            .collect();
+        durations
+            .sort_by(|a, b| (&a.from_status, &a.to_status).cmp(&(&b.from_status, &b.to_status)));
+        Ok(durations)
+    }
+
+    /// Sold lots grouped by extracted brand, most profitable first. Dates filter
+    /// on the sale date (YYYY-MM-DD, inclusive); items without a brand are
+    /// grouped under "Unknown".
+    pub fn get_brand_performance(
+        db: &Database,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+    ) -> Result<Vec<BrandPerformance>, AppError> {
+        let sql = format!(
+            "SELECT COALESCE(NULLIF(TRIM(i.extracted_brand), ''), 'Unknown') AS brand,
+                    COUNT(*),
+                    COALESCE(SUM(ar.high_bid), 0),
+                    COALESCE(SUM(ar.net_profit), 0)
+             FROM {} ar
+             JOIN inventory_items i ON i.id = ar.item_id
+             WHERE {} = 'Sold'
+               AND (?1 IS NULL OR datetime(COALESCE(i.sold_at, ar.created_at)) >= datetime(?1))
+               AND (?2 IS NULL OR datetime(COALESCE(i.sold_at, ar.created_at)) < datetime(?2, '+1 day'))
+             GROUP BY brand
+             ORDER BY 4 DESC",
+            SALE_RESULTS_SQL, ITEM_STATUS_SQL
+        );
+        let mut stmt = db.conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map(params![start_date, end_date], |row| {
+                let total_revenue: f64 = row.get(2)?;
+                let total_net_profit: f64 = row.get(3)?;
+                Ok(BrandPerformance {
+                    brand: row.get(0)?,
+                    units_sold: row.get(1)?,
+                    total_revenue: round2(total_revenue),
+                    total_net_profit: round2(total_net_profit),
+                    avg_margin_percent: if total_revenue > 0.0 {
+                        round2(total_net_profit / total_revenue * 100.0)
+                    } else {
+                        0.0
+                    },
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    /// Write a CSV statement of a vendor's sold lots between `start_date` and
+    /// `end_date` (YYYY-MM-DD, inclusive, by sale date): hammer price, commission
+    /// retained and net due per lot, followed by a totals row. Lots belong to the
+    /// vendor their source prices through (name or alias match).
+    pub fn export_consignor_statement(
+        db: &Database,
+        vendor_id: &str,
+        start_date: &str,
+        end_date: &str,
+        file_path: &str,
+    ) -> Result<ConsignorStatement, AppError> {
+        let parse_date = |date: &str| {
+            chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| {
+                AppError::Validation(format!("Invalid date '{}', expected YYYY-MM-DD", date))
+            })
+        };
+        if parse_date(start_date)? > parse_date(end_date)? {
+            return Err(AppError::Validation(format!(
+                "start_date {} is after end_date {}",
+                start_date, end_date
+            )));
+        }
+
+        let engine = PricingEngine::new(&db.conn)?;
+        let vendor = engine
+            .vendor(vendor_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("Active vendor {} not found", vendor_id)))?;
+
+        let sql = format!(
+            "SELECT i.lot_number, i.raw_title, COALESCE(i.source, ''),
+                    COALESCE(a.name, ar.platform),
+                    DATE(COALESCE(i.sold_at, ar.created_at)),
+                    COALESCE(ar.high_bid, 0), COALESCE(ar.commission_amount, 0)
+             FROM {} ar
+             JOIN inventory_items i ON i.id = ar.item_id
+             LEFT JOIN auctions a ON a.id = ar.auction_id
+             WHERE {} = 'Sold'
+               AND datetime(COALESCE(i.sold_at, ar.created_at)) >= datetime(?1)
+               AND datetime(COALESCE(i.sold_at, ar.created_at)) < datetime(?2, '+1 day')
+             ORDER BY 5, 4, i.lot_number",
+            SALE_RESULTS_SQL, ITEM_STATUS_SQL
+        );
+        let mut stmt = db.conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map(params![start_date.trim(), end_date.trim()], |row| {
+                Ok((
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, f64>(5)?,
+                    row.get::<_, f64>(6)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut wtr = csv::Writer::from_path(file_path)?;
+        wtr.write_record([
+            "Lot#",
+            "Title",
+            "Auction",
+            "Sale Date",
+            "Hammer",
+            "Commission",
+            "Net Due",
+        ])?;
+
+        let mut statement = ConsignorStatement {
+            vendor_name: vendor.name.clone(),
+            items_sold: 0,
+            hammer_total: 0.0,
+            commission_total: 0.0,
+            net_due: 0.0,
+        };
+        for (lot, title, source, auction, sale_date, hammer, commission) in rows {
+            if engine.match_vendor(&source).map(|v| v.id.as_str()) != Some(vendor.id.as_str()) {
+                continue;
+            }
+            wtr.write_record([
+                lot.as_deref().unwrap_or(""),
+                &title,
+                auction.as_deref().unwrap_or(""),
+                sale_date.as_deref().unwrap_or(""),
+                &format!("{:.2}", hammer),
+                &format!("{:.2}", commission),
+                &format!("{:.2}", hammer - commission),
+            ])?;
+            statement.items_sold += 1;
+            statement.hammer_total += hammer;
+            statement.commission_total += commission;
+        }
+        statement.hammer_total = round2(statement.hammer_total);
+        statement.commission_total = round2(statement.commission_total);
+        statement.net_due = round2(statement.hammer_total - statement.commission_total);
+
+        wtr.write_record([
+            "TOTAL",
+            &format!(
+                "{} ({} - {})",
+                vendor.name,
+                start_date.trim(),
+                end_date.trim()
+            ),
+            "",
+            "",
+            &format!("{:.2}", statement.hammer_total),
+            &format!("{:.2}", statement.commission_total),
+            &format!("{:.2}", statement.net_due),
+        ])?;
+        wtr.flush()?;
+
+        Ok(statement)
+    }
+
+    /// Quote commission and net for an item at `estimated_hammer`, using the
+    /// same terms reconciliation would apply (its auction's flat/min fees when
+    /// assigned, else settings; vendor exemption). Nothing is written.
+    pub fn estimate_payout(
+        db: &Database,
+        item_id: &str,
+        estimated_hammer: f64,
+    ) -> Result<PayoutEstimate, AppError> {
+        if !(estimated_hammer.is_finite() && estimated_hammer >= 0.0) {
+            return Err(AppError::Validation(
+                "Estimated hammer price must be a non-negative number".to_string(),
+            ));
+        }
+        let (source, cost_price, auction_id): (String, f64, Option<String>) = db
+            .conn
+            .query_row(
+                "SELECT COALESCE(source, ''), cost_price, auction_id
+                 FROM inventory_items WHERE id = ?1",
+                params![item_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => {
+                    AppError::NotFound(format!("Item {} not found", item_id))
+                }
+                other => other.into(),
+            })?;
+
+        let terms = CommissionTerms::load(db, auction_id.as_deref().unwrap_or(""));
+        let commission_exempt = PricingEngine::new(&db.conn)?.is_commission_exempt(&source);
+        let commission = if commission_exempt {
+            0.0
+        } else {
+            terms.amount(estimated_hammer)
+        };
+        let net_to_consignor = round2(estimated_hammer - commission);
+
+        Ok(PayoutEstimate {
+            item_id: item_id.to_string(),
+            estimated_hammer,
+            commission_rate: terms.rate,
+            commission,
+            commission_exempt,
+            net_to_consignor,
+            cost_price,
+            net_after_cost: round2(net_to_consignor - cost_price),
+            rate_is_fallback: terms.rate_is_fallback,
+        })
+    }
+
+    /// Write a fulfillment picklist CSV for an auction: sold lots (buybacks
+    /// excluded) grouped by buyer, in lot order within each buyer, with title
+    /// and warehouse location.
+    pub fn export_picklist(
+        db: &Database,
+        auction_id: &str,
+        file_path: &str,
+    ) -> Result<PicklistSummary, AppError> {
+        db.conn
+            .query_row(
+                "SELECT id FROM auctions WHERE id = ?1",
+                params![auction_id],
+                |row| row.get::<_, String>(0),
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => {
+                    AppError::NotFound(format!("Auction {} not found", auction_id))
+                }
+                other => other.into(),
+            })?;
+
+        let sql = format!(
+            "SELECT COALESCE(ar.winning_bidder, ''), COALESCE(ar.bidder_id, ''),
+                    COALESCE(i.lot_number, ''), i.raw_title, i.location
+             FROM auction_results ar
+             JOIN inventory_items i ON i.id = ar.item_id
+             WHERE ar.auction_id = ?1
+               AND {} = 'Sold'",
+            ITEM_STATUS_SQL
+        );
+        let mut stmt = db.conn.prepare(&sql)?;
+        let mut rows = stmt
+            .query_map(params![auction_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        rows.sort_by(|a, b| {
+            (a.0.to_lowercase(), &a.1)
+                .cmp(&(b.0.to_lowercase(), &b.1))
+                .then_with(|| crate::auctions::natural_lot_cmp(&a.2, &b.2))
+        });
+
+        let mut wtr = csv::Writer::from_path(file_path)?;
+        wtr.write_record(["Buyer", "Bidder ID", "Lot#", "Title", "Location"])?;
+        let mut buyers = HashSet::new();
+        let mut missing_location = 0;
+        for (buyer, bidder_id, lot, title, location) in &rows {
+            buyers.insert((buyer.to_lowercase(), bidder_id.as_str()));
+            if location.is_none() {
+                missing_location += 1;
+            }
+            wtr.write_record([
+                buyer.as_str(),
+                bidder_id,
+                lot,
+                title,
+                location.as_deref().unwrap_or(""),
+            ])?;
+        }
+        wtr.flush()?;
+
+        Ok(PicklistSummary {
+            buyers: buyers.len(),
+            lots: rows.len(),
+            missing_location,
+        })
+    }
+
+    /// Score each brand/category by sell-through (historical sales against
+    /// units still on hand) and average margin, best buys first. Groups with
+    /// too little data are listed last.
+    pub fn get_reorder_suggestions(db: &Database) -> Result<Vec<ReorderSuggestion>, AppError> {
+        let mut stmt = db.conn.prepare(
+            "WITH sold AS (
+                SELECT COALESCE(NULLIF(TRIM(extracted_brand), ''), 'Unknown') AS brand,
+                       COALESCE(NULLIF(TRIM(category), ''), 'Uncategorized') AS category,
+                       COUNT(*) AS units,
+                       AVG(CASE WHEN sale_price > 0
+                                THEN (sale_price - COALESCE(cost_price, 0)) * 100.0 / sale_price
+                           END) AS margin,
+                       COALESCE(SUM(sale_price), 0) AS revenue
+                FROM historical_sales
+                GROUP BY 1, 2
+             ),
+             on_hand AS (
+                SELECT COALESCE(NULLIF(TRIM(extracted_brand), ''), 'Unknown') AS brand,
+                       COALESCE(NULLIF(TRIM(category), ''), 'Uncategorized') AS category,
+                       COALESCE(SUM(quantity), 0) AS units
+                FROM inventory_items
+                WHERE current_status IN ('InStock', 'Listed', 'Unsold', 'Buyback')
+                GROUP BY 1, 2
+             ),
+             groups AS (
+                SELECT brand, category FROM sold
+                UNION
+                SELECT brand, category FROM on_hand
+             )
+             SELECT g.brand, g.category,
+                    COALESCE(s.units, 0), COALESCE(o.units, 0),
+                    COALESCE(s.margin, 0), COALESCE(s.revenue, 0)
+             FROM groups g
+             LEFT JOIN sold s ON s.brand = g.brand AND s.category = g.category
+             LEFT JOIN on_hand o ON o.brand = g.brand AND o.category = g.category",
+        )?;
+        let mut suggestions = stmt
+            .query_map([], |row| {
+                let units_sold: i64 = row.get(2)?;
+                let units_on_hand: i64 = row.get(3)?;
+                let avg_margin: f64 = row.get(4)?;
+                let total = units_sold + units_on_hand;
+                let sell_through = if total > 0 {
+                    units_sold as f64 * 100.0 / total as f64
+                } else {
+                    0.0
+                };
+                let recommendation = if total < REORDER_MIN_SAMPLE {
+                    "Not enough data"
+                } else if sell_through >= REORDER_BUY_SELL_THROUGH
+                    && avg_margin >= REORDER_BUY_MARGIN
+                {
+                    "Buy more"
+                } else if sell_through < REORDER_AVOID_SELL_THROUGH || avg_margin < 0.0 {
+                    "Avoid"
+                } else {
+                    "Hold"
+                };
+                Ok(ReorderSuggestion {
+                    brand: row.get(0)?,
+                    category: row.get(1)?,
+                    units_sold,
+                    units_on_hand,
+                    sell_through_rate: round2(sell_through),
+                    avg_margin_percent: round2(avg_margin),
+                    total_revenue: round2(row.get(5)?),
+                    score: round2((sell_through + avg_margin.clamp(-100.0, 100.0)) / 2.0),
+                    recommendation: recommendation.to_string(),
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        suggestions.sort_by(|a, b| {
+            let scored =
+                |s: &ReorderSuggestion| s.units_sold + s.units_on_hand >= REORDER_MIN_SAMPLE;
+            scored(b)
+                .cmp(&scored(a))
+                .then(b.score.total_cmp(&a.score))
+                .then_with(|| a.brand.cmp(&b.brand))
+                .then_with(|| a.category.cmp(&b.category))
+        });
+        Ok(suggestions)
     }
 }
 
 #[tauri::command]
 pub fn reconcile_auction(
-    auction_id: String,
+    auction_id: Option<String>,
     file_path: String,
+    auto_detect: Option<bool>,
     state: State<crate::AppState>,
-) -> Result<ReconciliationResult, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+) -> Result<ReconciliationResult, AppError> {
+    let db = state.db.lock()?;
+    let auction_id = if auto_detect.unwrap_or(false) {
+        let detected = ReconciliationManager::resolve_detected_auction(&db, &file_path)?;
+        // A picked auction that disagrees with the file is the wrong-auction mistake auto-detect guards against
+        if let Some(picked) = auction_id.filter(|id| !id.is_empty() && *id != detected) {
+            return Err(AppError::Validation(format!(
+                "Results file belongs to auction {}, not the selected auction {}",
+                detected, picked
+            )));
+        }
+        detected
+    } else {
+        auction_id
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| AppError::Validation("auction_id is required".to_string()))?
+    };
     ReconciliationManager::reconcile_hibid_results(&db, &auction_id, &file_path)
 }
 
 #[tauri::command]
-pub fn get_pl_report(state: State<crate::AppState>) -> Result<ProfitLossReport, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+pub fn revert_lots(
+    auction_id: String,
+    lot_numbers: Vec<String>,
+    state: State<crate::AppState>,
+) -> Result<usize, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::revert_lots(&db, &auction_id, &lot_numbers)
+}
+
+#[tauri::command]
+pub fn detect_auction_from_results(
+    file_path: String,
+    state: State<crate::AppState>,
+) -> Result<Vec<AuctionCandidate>, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::detect_auction(&db, &file_path)
+}
+
+#[tauri::command]
+pub fn reconcile_multi(
+    file_path: String,
+    state: State<crate::AppState>,
+) -> Result<Vec<AuctionReconciliation>, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::reconcile_multi(&db, &file_path)
+}
+
+#[tauri::command]
+pub fn add_adjustment(
+    item_id: String,
+    amount: f64,
+    reason: Option<String>,
+    state: State<crate::AppState>,
+) -> Result<String, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::add_adjustment(&db, &item_id, amount, reason.as_deref())
+}
+
+#[tauri::command]
+pub fn record_cash_sale(
+    item_id: String,
+    sale_price: f64,
+    buyer_name: Option<String>,
+    state: State<crate::AppState>,
+) -> Result<CashSaleResult, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::record_cash_sale(&db, &item_id, sale_price, buyer_name.as_deref())
+}
+
+#[tauri::command]
+pub fn get_pl_report(state: State<crate::AppState>) -> Result<ProfitLossReport, AppError> {
+    let db = state.db.lock()?;
     ReconciliationManager::generate_pl_report(&db)
 }
 
@@ -732,8 +2402,8 @@ pub fn get_pl_report_filtered(
     date_from: Option<String>,
     date_to: Option<String>,
     state: State<crate::AppState>,
-) -> Result<ProfitLossReport, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+) -> Result<ProfitLossReport, AppError> {
+    let db = state.db.lock()?;
     ReconciliationManager::generate_pl_report_filtered(&db, period, date_from, date_to)
 }
 
@@ -743,8 +2413,8 @@ pub fn get_auction_summaries(
     date_from: Option<String>,
     date_to: Option<String>,
     state: State<crate::AppState>,
-) -> Result<Vec<AuctionSummary>, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+) -> Result<Vec<AuctionSummary>, AppError> {
+    let db = state.db.lock()?;
     ReconciliationManager::get_auction_summaries(&db, period, date_from, date_to)
 }
 
@@ -754,7 +2424,1372 @@ pub fn get_vendor_breakdown(
     date_from: Option<String>,
     date_to: Option<String>,
     state: State<crate::AppState>,
-) -> Result<Vec<VendorBreakdown>, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+) -> Result<Vec<VendorBreakdown>, AppError> {
+    let db = state.db.lock()?;
     ReconciliationManager::get_vendor_breakdown(&db, period, date_from, date_to)
 }
+
+#[tauri::command]
+pub fn get_auction_commission(
+    auction_id: String,
+    state: State<crate::AppState>,
+) -> Result<AuctionCommissionSummary, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::get_auction_commission(&db, &auction_id)
+}
+
+#[tauri::command]
+pub fn get_bid_spread(
+    auction_id: String,
+    state: State<crate::AppState>,
+) -> Result<Vec<BidSpreadRow>, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::get_bid_spread(&db, &auction_id)
+}
+
+#[tauri::command]
+pub fn get_reconciliation_variance(
+    auction_id: String,
+    state: State<crate::AppState>,
+) -> Result<ReconciliationVariance, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::get_reconciliation_variance(&db, &auction_id)
+}
+
+#[tauri::command]
+pub fn get_velocity_stats(state: State<crate::AppState>) -> Result<VelocityStats, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::get_velocity_stats(&db)
+}
+
+/// Days between status changes, from the recorded item status history
+#[tauri::command]
+pub fn get_status_durations(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    state: State<crate::AppState>,
+) -> Result<Vec<StatusDuration>, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::get_status_durations(&db, start_date.as_deref(), end_date.as_deref())
+}
+
+#[tauri::command]
+pub fn get_brand_performance(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    state: State<crate::AppState>,
+) -> Result<Vec<BrandPerformance>, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::get_brand_performance(&db, start_date.as_deref(), end_date.as_deref())
+}
+
+#[tauri::command]
+pub fn export_consignor_statement(
+    vendor_id: String,
+    start_date: String,
+    end_date: String,
+    file_path: String,
+    state: State<crate::AppState>,
+) -> Result<ConsignorStatement, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::export_consignor_statement(
+        &db,
+        &vendor_id,
+        &start_date,
+        &end_date,
+        &file_path,
+    )
+}
+
+#[tauri::command]
+pub fn estimate_payout(
+    item_id: String,
+    estimated_hammer: f64,
+    state: State<crate::AppState>,
+) -> Result<PayoutEstimate, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::estimate_payout(&db, &item_id, estimated_hammer)
+}
+
+#[tauri::command]
+pub fn export_picklist(
+    auction_id: String,
+    file_path: String,
+    state: State<crate::AppState>,
+) -> Result<PicklistSummary, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::export_picklist(&db, &auction_id, &file_path)
+}
+
+#[tauri::command]
+pub fn get_reorder_suggestions(
+    state: State<crate::AppState>,
+) -> Result<Vec<ReorderSuggestion>, AppError> {
+    let db = state.db.lock()?;
+    ReconciliationManager::get_reorder_suggestions(&db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+    use uuid::Uuid;
+
+    /// Per-test directory, removed when dropped. Bind it before the database so
+    /// the connection closes first.
+    struct TestDir(PathBuf);
+
+    impl std::ops::Deref for TestDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn setup_db(label: &str) -> (TestDir, Database) {
+        let base_dir: PathBuf =
+            std::env::temp_dir().join(format!("sugarland_reconcile_{}_{}", label, Uuid::new_v4()));
+        fs::create_dir_all(&base_dir).expect("Failed to create temp dir");
+        let db_path = base_dir.join("reconcile.db");
+        let db = Database::new(db_path.to_str().expect("Path should be valid UTF-8"))
+            .expect("Failed to create test db");
+        (TestDir(base_dir), db)
+    }
+
+    /// Reconcile a HiBid results CSV against auction-1. The file is written next
+    /// to the test database, so it goes away with the test's directory.
+    fn reconcile_csv(db: &Database, body: &str) -> ReconciliationResult {
+        let db_path = db.conn.path().expect("Test database should be on disk");
+        let csv_path = Path::new(db_path).with_file_name("results.csv");
+        fs::write(&csv_path, body).expect("Failed to write csv");
+        ReconciliationManager::reconcile_hibid_results(
+            db,
+            "auction-1",
+            csv_path.to_str().expect("Path should be valid UTF-8"),
+        )
+        .expect("reconcile failed")
+    }
+
+    fn insert_listed_item(db: &Database, auction_id: &str, item_id: &str, lot_number: &str) {
+        db.conn
+            .execute(
+                "INSERT OR IGNORE INTO manifests (id, source_filename) VALUES ('m-test', 'manifest.csv')",
+                [],
+            )
+            .expect("Failed to insert manifest");
+        db.conn
+            .execute(
+                "INSERT OR IGNORE INTO auctions (id, name, status) VALUES (?1, 'Sugarland 42', 'Active')",
+                params![auction_id],
+            )
+            .expect("Failed to insert auction");
+        db.conn
+            .execute(
+                "INSERT INTO inventory_items (
+                    id, manifest_id, lot_number, raw_title, normalized_title, source,
+                    retail_price, cost_price, min_price, current_status, auction_id
+                 ) VALUES (?1, 'm-test', ?2, 'Test Item', 'test item', 'Best Buy', 1000.0, 140.0, 240.0, 'Listed', ?3)",
+                params![item_id, lot_number, auction_id],
+            )
+            .expect("Failed to insert inventory item");
+    }
+
+    #[test]
+    fn normalize_lot_number_strips_suffix_and_uppercases() {
+        assert_eq!(normalize_lot_number("42m"), "42");
+        assert_eq!(normalize_lot_number(" 42 "), "42");
+        assert_eq!(normalize_lot_number("a12b"), "A12");
+        assert_eq!(normalize_lot_number("abc"), "ABC");
+    }
+
+    #[test]
+    fn reconcile_matches_alphanumeric_lot_ignoring_case() {
+        let (_base_dir, db) = setup_db("alphanumeric_lot");
+        insert_listed_item(&db, "auction-1", "item-1", "A-12");
+        insert_listed_item(&db, "auction-1", "item-2", "A-12B");
+
+        let result = reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             \"a-12 \",Test Item,1001,Test Buyer,30500,,,\n\
+             a-12b,Test Item,1002,Other Buyer,1000,,,\n",
+        );
+
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(result.sold_count, 2);
+        assert_eq!(result.details[0].item_id, "item-1");
+        assert_eq!(result.details[0].match_strategy, MatchStrategy::Exact);
+        assert_eq!(result.details[1].item_id, "item-2");
+        assert_eq!(result.details[1].match_strategy, MatchStrategy::Exact);
+    }
+
+    #[test]
+    fn reconcile_matches_suffixed_lot_via_normalization() {
+        let (_base_dir, db) = setup_db("normalized_lot");
+        insert_listed_item(&db, "auction-1", "item-1", "42m");
+
+        let result = reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n42,Test Item,1001,Test Buyer,30500,,,\n",
+        );
+
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(result.sold_count, 1);
+        assert_eq!(result.total_revenue, 305.0);
+        assert_eq!(result.details.len(), 1);
+        let outcome = &result.details[0];
+        assert_eq!(outcome.lot_number, "42");
+        assert_eq!(outcome.item_id, "item-1");
+        assert_eq!(outcome.status, "Sold");
+        assert_eq!(outcome.high_bid, 305.0);
+        assert_eq!(outcome.match_strategy, MatchStrategy::Normalized);
+
+        let status: String = db
+            .conn
+            .query_row(
+                "SELECT current_status FROM inventory_items WHERE id = 'item-1'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("Failed to read item status");
+        assert_eq!(status, "Sold");
+    }
+
+    #[test]
+    fn bid_spread_flags_money_left_on_table_and_closed_at_max() {
+        let (_base_dir, db) = setup_db("bid_spread");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        insert_listed_item(&db, "auction-1", "item-2", "2");
+
+        reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             1,Test Item,1001,Buyer One,10000,25000,,\n\
+             2,Test Item,1002,Buyer Two,5000,5000,,\n",
+        );
+
+        let rows = ReconciliationManager::get_bid_spread(&db, "auction-1").expect("bid spread");
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].spread, 150.0);
+        assert!(rows[0].left_on_table);
+        assert!(!rows[0].closed_at_max);
+        assert_eq!(rows[1].spread, 0.0);
+        assert!(!rows[1].left_on_table);
+        assert!(rows[1].closed_at_max);
+    }
+
+    #[test]
+    fn reconcile_records_historical_sale_with_configured_season() {
+        let (_base_dir, db) = setup_db("season_map");
+        insert_listed_item(&db, "auction-1", "item-1", "7");
+        let season_map = (1..=12)
+            .map(|month| format!("\"{}\": \"Holiday\"", month))
+            .collect::<Vec<_>>()
+            .join(", ");
+        db.conn
+            .execute(
+                "UPDATE settings SET value = ?1 WHERE key = 'season_map'",
+                params![format!("{{{}}}", season_map)],
+            )
+            .expect("Failed to update season map");
+
+        reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n7,Test Item,1001,Test Buyer,20000,,,\n",
+        );
+
+        let (season, sale_price): (String, f64) = db
+            .conn
+            .query_row(
+                "SELECT season, sale_price FROM historical_sales",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("Historical sale should be recorded");
+        assert_eq!(season, "Holiday");
+        assert_eq!(sale_price, 200.0);
+    }
+
+    #[test]
+    fn recompute_seasons_relabels_existing_sales() {
+        let (_base_dir, db) = setup_db("recompute_seasons");
+        db.conn
+            .execute_batch(
+                "INSERT INTO historical_sales (id, normalized_title, sale_price, sale_date, season) VALUES
+                    ('hs-1', 'lamp', 10, '2025-01-15', 'Q1'),
+                    ('hs-2', 'desk', 20, '2025-12-02', 'Q4'),
+                    ('hs-3', 'sofa', 30, '2025-07-20', 'Q3');
+                 UPDATE settings SET value = '{\"12\": \"Holiday\", \"1\": \"Holiday\"}'
+                 WHERE key = 'season_map';",
+            )
+            .expect("Failed to seed historical sales");
+
+        assert_eq!(db.recompute_seasons().expect("recompute failed"), 2);
+        assert_eq!(db.recompute_seasons().expect("recompute failed"), 0);
+
+        let seasons = db
+            .conn
+            .prepare("SELECT season FROM historical_sales ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get::<_, String>(0))
+            .unwrap()
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(seasons, vec!["Holiday", "Holiday", "Q3"]);
+    }
+
+    #[test]
+    fn reconcile_rejects_zero_high_bid_for_winning_bidder() {
+        let (_base_dir, db) = setup_db("zero_high_bid");
+        insert_listed_item(&db, "auction-1", "item-1", "5");
+        insert_listed_item(&db, "auction-1", "item-2", "6");
+
+        let result = reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             5,Test Item,1001,Test Buyer,$0.00,,,\n\
+             6,Test Item,,,,,,\n",
+        );
+
+        assert_eq!(result.errors.len(), 1, "errors: {:?}", result.errors);
+        assert!(result.errors[0].starts_with("Lot 5:"));
+        assert_eq!(result.sold_count, 0);
+
+        let results_count: i64 = db
+            .conn
+            .query_row(
+                "SELECT COUNT(*) FROM auction_results WHERE item_id = 'item-1'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("Failed to count results");
+        assert_eq!(results_count, 0);
+
+        let unsold_status: String = db
+            .conn
+            .query_row(
+                "SELECT current_status FROM inventory_items WHERE id = 'item-2'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("Failed to read item status");
+        assert_eq!(unsold_status, "Unsold");
+    }
+
+    #[test]
+    fn variance_compares_min_price_floor_to_high_bid() {
+        let (_base_dir, db) = setup_db("variance");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        insert_listed_item(&db, "auction-1", "item-2", "2");
+        insert_listed_item(&db, "auction-1", "item-3", "3");
+
+        reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             1,Test Item,1001,Buyer One,30000,,,\n\
+             2,Test Item,1002,Buyer Two,20000,,,\n\
+             3,Test Item,,,,,,\n",
+        );
+
+        let report =
+            ReconciliationManager::get_reconciliation_variance(&db, "auction-1").expect("variance");
+
+        assert_eq!(report.sold_count, 2);
+        assert_eq!(report.over_floor_count, 1);
+        assert_eq!(report.under_floor_count, 1);
+        assert_eq!(report.rows[0].variance, 60.0);
+        assert_eq!(report.rows[0].projected_profit, 100.0);
+        assert_eq!(report.rows[0].actual_profit, 160.0);
+        assert_eq!(report.rows[1].variance, -40.0);
+        assert_eq!(report.total_variance, 20.0);
+        assert_eq!(report.total_profit_variance, 20.0);
+    }
+
+    #[test]
+    fn pl_report_includes_item_adjustments() {
+        let (_base_dir, db) = setup_db("adjustments");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+
+        reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n1,Test Item,1001,Buyer One,30000,,,\n",
+        );
+
+        let before = ReconciliationManager::generate_pl_report(&db).expect("pl report");
+        assert_eq!(before.total_adjustments, 0.0);
+
+        ReconciliationManager::add_adjustment(&db, "item-1", 25.0, Some("Vendor credit"))
+            .expect("add adjustment");
+        assert!(ReconciliationManager::add_adjustment(&db, "item-1", 0.0, None).is_err());
+        let missing = ReconciliationManager::add_adjustment(&db, "missing", 5.0, None).unwrap_err();
+        assert_eq!(missing.code(), "NOT_FOUND");
+
+        let after = ReconciliationManager::generate_pl_report(&db).expect("pl report");
+        assert_eq!(after.total_adjustments, 25.0);
+        assert_eq!(round2(after.net_profit - before.net_profit), 25.0);
+        assert_eq!(after.total_cogs, before.total_cogs);
+    }
+
+    #[test]
+    fn pl_report_writes_off_scrapped_items() {
+        let (_base_dir, db) = setup_db("scrap_writeoff");
+        for (item_id, lot) in [
+            ("item-1", "1"),
+            ("item-2", "2"),
+            ("item-3", "3"),
+            ("item-4", "4"),
+        ] {
+            insert_listed_item(&db, "auction-1", item_id, lot);
+        }
+        let before = ReconciliationManager::generate_pl_report(&db).expect("pl report");
+        assert_eq!(before.scrapped_count, 0);
+
+        // item-1 scrapped with $40 salvage; item-2 split into item-4; item-3 merged
+        db.conn
+            .execute_batch(
+                "UPDATE inventory_items SET current_status = 'Scrap', scrap_value = 40 WHERE id = 'item-1';
+                 UPDATE inventory_items SET current_status = 'Scrap' WHERE id IN ('item-2', 'item-3');
+                 UPDATE inventory_items SET split_from_id = 'item-2' WHERE id = 'item-4';
+                 UPDATE inventory_items SET merged_into_id = 'item-4' WHERE id = 'item-3';",
+            )
+            .expect("Failed to scrap items");
+
+        let after = ReconciliationManager::generate_pl_report(&db).expect("pl report");
+        assert_eq!(after.scrapped_count, 1);
+        assert_eq!(after.scrap_writeoff, 100.0);
+        assert_eq!(round2(before.net_profit - after.net_profit), 100.0);
+    }
+
+    #[test]
+    fn scrap_writeoff_stays_in_the_period_it_was_scrapped() {
+        let (_base_dir, db) = setup_db("scrap_period");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        db.conn
+            .execute_batch(
+                "UPDATE inventory_items SET current_status = 'Scrap' WHERE id = 'item-1';
+                 UPDATE item_status_history SET changed_at = datetime('now', '-3 months')
+                 WHERE item_id = 'item-1' AND to_status = 'Scrap';",
+            )
+            .expect("Failed to scrap item");
+        // Edits long after scrapping bump updated_at but not the write-off date
+        db.conn
+            .execute(
+                "UPDATE inventory_items SET scrap_value = 40, notes = 'salvaged' WHERE id = 'item-1'",
+                [],
+            )
+            .expect("Failed to edit item");
+
+        let month =
+            ReconciliationManager::generate_pl_report_filtered(&db, "month".into(), None, None)
+                .expect("pl report");
+        assert_eq!(month.scrapped_count, 0);
+        let all = ReconciliationManager::generate_pl_report(&db).expect("pl report");
+        assert_eq!(all.scrapped_count, 1);
+        assert_eq!(all.scrap_writeoff, 100.0);
+    }
+
+    #[test]
+    fn cash_sale_marks_item_sold_and_records_history() {
+        let (_base_dir, db) = setup_db("cash_sale");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+
+        let sale = ReconciliationManager::record_cash_sale(&db, "item-1", 200.0, Some(" Walk-in "))
+            .expect("cash sale");
+        assert!(sale.warning.is_none());
+        let sale_id = sale.sale_id;
+
+        let (status, auction_id): (String, Option<String>) = db
+            .conn
+            .query_row(
+                "SELECT current_status, auction_id FROM inventory_items WHERE id = 'item-1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("item row");
+        assert_eq!(status, "Sold");
+        assert!(auction_id.is_none());
+
+        let (commission, net_profit, buyer): (f64, f64, String) = db
+            .conn
+            .query_row(
+                "SELECT commission_amount, net_profit, buyer_name FROM cash_sales WHERE id = ?1",
+                params![sale_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .expect("cash sale row");
+        assert_eq!(commission, 20.0);
+        assert_eq!(net_profit, 40.0);
+        assert_eq!(buyer, "Walk-in");
+
+        let report = ReconciliationManager::generate_pl_report(&db).expect("pl report");
+        assert_eq!(report.sold_items, 1);
+        assert_eq!(report.total_revenue, 200.0);
+        assert_eq!(report.total_expenses, 20.0);
+
+        let platform: String = db
+            .conn
+            .query_row(
+                "SELECT platform FROM historical_sales WHERE id = ?1",
+                params![sale_id],
+                |row| row.get(0),
+            )
+            .expect("historical sale");
+        assert_eq!(platform, "Cash");
+
+        let again = ReconciliationManager::record_cash_sale(&db, "item-1", 200.0, None);
+        assert_eq!(again.unwrap_err().code(), "VALIDATION");
+    }
+
+    #[test]
+    fn cash_sale_reports_fallback_commission_rate() {
+        let (_base_dir, db) = setup_db("cash_sale_fallback");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        db.conn
+            .execute(
+                "UPDATE settings SET value = '1.5' WHERE key = 'cash_sale_commission_rate'",
+                [],
+            )
+            .expect("Failed to update setting");
+
+        let sale =
+            ReconciliationManager::record_cash_sale(&db, "item-1", 200.0, None).expect("cash sale");
+        assert_eq!(sale.commission_rate, FALLBACK_COMMISSION_RATE);
+        assert_eq!(sale.commission_amount, 30.0);
+        let warning = sale.warning.expect("fallback warning");
+        assert!(warning.contains("cash_sale_commission_rate"), "{}", warning);
+    }
+
+    #[test]
+    fn velocity_stats_use_days_between_listing_and_sale() {
+        let (_base_dir, db) = setup_db("velocity");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        insert_listed_item(&db, "auction-1", "item-2", "2");
+        db.conn
+            .execute(
+                "UPDATE inventory_items SET listed_at = datetime('now', '-4 days'), category = 'Tools'",
+                [],
+            )
+            .expect("Failed to backdate listing");
+
+        reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n1,Test Item,1001,Buyer One,30000,,,\n",
+        );
+
+        let stats = ReconciliationManager::get_velocity_stats(&db).expect("velocity stats");
+        assert_eq!(stats.overall.sold_count, 1);
+        assert_eq!(stats.overall.median_days.round(), 4.0);
+        assert_eq!(stats.by_category.len(), 1);
+        assert_eq!(stats.by_category[0].category, "Tools");
+    }
+
+    #[test]
+    fn status_durations_follow_recorded_history_including_relists() {
+        let (_base_dir, db) = setup_db("status_durations");
+        // item-1 is created Listed (never InStock), goes unsold and is relisted
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        db.conn
+            .execute_batch(
+                "UPDATE inventory_items SET current_status = 'Unsold' WHERE id = 'item-1';
+                 UPDATE inventory_items SET current_status = 'Listed' WHERE id = 'item-1';
+                 INSERT INTO inventory_items (id, manifest_id, lot_number, raw_title, retail_price,
+                     cost_price, min_price, current_status)
+                 VALUES ('item-2', 'm-test', '2', 'Test Item', 100.0, 14.0, 24.0, 'InStock');
+                 UPDATE inventory_items SET current_status = 'Listed', auction_id = 'auction-1'
+                 WHERE id = 'item-2';",
+            )
+            .expect("Failed to move items");
+
+        reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n1,Test Item,1001,Buyer One,30000,,,\n",
+        );
+
+        // Backdate the history: item-1 listed -10d, unsold -7d, relisted -5d, sold -1d;
+        // item-2 in stock -6d, listed -4d
+        db.conn
+            .execute_batch(
+                "UPDATE item_status_history SET changed_at = datetime('now', CASE
+                     WHEN item_id = 'item-1' AND from_status IS NULL THEN '-10 days'
+                     WHEN item_id = 'item-1' AND to_status = 'Unsold' THEN '-7 days'
+                     WHEN item_id = 'item-1' AND from_status = 'Unsold' THEN '-5 days'
+                     WHEN item_id = 'item-1' AND to_status = 'Sold' THEN '-1 days'
+                     WHEN item_id = 'item-2' AND from_status IS NULL THEN '-6 days'
+                     ELSE '-4 days' END);",
+            )
+            .expect("Failed to backdate history");
+
+        let durations =
+            ReconciliationManager::get_status_durations(&db, None, None).expect("durations");
+        let transition = |from: &str, to: &str| {
+            durations
+                .iter()
+                .find(|d| d.from_status == from && d.to_status == to)
+                .unwrap_or_else(|| panic!("missing {} -> {}", from, to))
+        };
+        // Only item-2 actually passed through InStock
+        assert_eq!(transition("InStock", "Listed").item_count, 1);
+        assert_eq!(transition("InStock", "Listed").avg_days.round(), 2.0);
+        assert_eq!(transition("Listed", "Unsold").avg_days.round(), 3.0);
+        assert_eq!(transition("Unsold", "Listed").avg_days.round(), 2.0);
+        // The sale is measured from the relisting, not the first listing
+        assert_eq!(transition("Listed", "Sold").item_count, 1);
+        assert_eq!(transition("Listed", "Sold").median_days.round(), 4.0);
+        assert_eq!(durations.len(), 4);
+
+        let durations = ReconciliationManager::get_status_durations(
+            &db,
+            Some("2000-01-01"),
+            Some("2000-12-31"),
+        )
+        .expect("durations");
+        assert!(durations.is_empty());
+    }
+
+    #[test]
+    fn median_handles_even_and_odd_counts() {
+        assert_eq!(median(&[]), 0.0);
+        assert_eq!(median(&[1.0, 3.0, 8.0]), 3.0);
+        assert_eq!(median(&[1.0, 3.0, 5.0, 9.0]), 4.0);
+    }
+
+    #[test]
+    fn commission_terms_apply_flat_fee_and_minimum() {
+        let pct_only = CommissionTerms {
+            rate: 0.15,
+            flat: 0.0,
+            min: 0.0,
+            rate_setting: "default_commission_rate",
+            rate_is_fallback: false,
+        };
+        assert_eq!(pct_only.amount(50.0), 7.5);
+        // 15% of 12.34 is 1.851; charged to the cent
+        assert_eq!(pct_only.amount(12.34), 1.85);
+
+        let flat_plus = CommissionTerms {
+            rate: 0.10,
+            flat: 1.0,
+            min: 0.0,
+            rate_setting: "default_commission_rate",
+            rate_is_fallback: false,
+        };
+        assert_eq!(flat_plus.amount(50.0), 6.0);
+
+        let with_floor = CommissionTerms {
+            rate: 0.15,
+            flat: 0.0,
+            min: 2.0,
+            rate_setting: "default_commission_rate",
+            rate_is_fallback: false,
+        };
+        assert_eq!(with_floor.amount(5.0), 2.0);
+        assert_eq!(with_floor.amount(100.0), 15.0);
+    }
+
+    #[test]
+    fn reconcile_applies_commission_minimum_on_cheap_lot() {
+        let (_base_dir, db) = setup_db("commission_min");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        db.conn
+            .execute(
+                "UPDATE settings SET value = '2' WHERE key = 'commission_min'",
+                [],
+            )
+            .expect("Failed to set commission_min");
+
+        reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n1,Test Item,1001,Buyer One,500,,,\n",
+        );
+
+        let commission: f64 = db
+            .conn
+            .query_row(
+                "SELECT commission_amount FROM auction_results WHERE item_id = 'item-1'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("auction result");
+        assert_eq!(commission, 2.0);
+
+        // An auction-level floor overrides the setting
+        db.conn
+            .execute(
+                "UPDATE auctions SET commission_min = 3 WHERE id = 'auction-1'",
+                [],
+            )
+            .expect("Failed to set auction commission_min");
+        assert_eq!(CommissionTerms::load(&db, "auction-1").amount(5.0), 3.0);
+    }
+
+    #[test]
+    fn cash_auction_uses_cash_sale_commission_rate() {
+        let (_base_dir, db) = setup_db("cash_auction");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        db.conn
+            .execute(
+                "UPDATE settings SET value = '0.15' WHERE key = 'default_commission_rate'",
+                [],
+            )
+            .expect("Failed to set commission rate");
+        assert_eq!(CommissionTerms::load(&db, "auction-1").rate, 0.15);
+
+        db.conn
+            .execute(
+                "UPDATE auctions SET auction_type = 'Cash' WHERE id = 'auction-1'",
+                [],
+            )
+            .expect("Failed to set auction type");
+        let terms = CommissionTerms::load(&db, "auction-1");
+        assert_eq!(terms.rate, 0.10);
+        assert_eq!(terms.rate_setting, "cash_sale_commission_rate");
+        assert!(!terms.rate_is_fallback);
+    }
+
+    #[test]
+    fn estimate_payout_uses_auction_commission_terms() {
+        let (_base_dir, db) = setup_db("estimate_payout");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        db.conn
+            .execute(
+                "UPDATE settings SET value = '0.1' WHERE key = 'default_commission_rate'",
+                [],
+            )
+            .expect("Failed to set commission rate");
+        db.conn
+            .execute(
+                "UPDATE auctions SET commission_flat = 2, commission_min = 5 WHERE id = 'auction-1'",
+                [],
+            )
+            .expect("Failed to set auction fees");
+
+        let quote = ReconciliationManager::estimate_payout(&db, "item-1", 200.0).expect("quote");
+        assert_eq!(quote.commission, 22.0);
+        assert_eq!(quote.net_to_consignor, 178.0);
+        assert_eq!(quote.net_after_cost, 38.0);
+        assert!(!quote.commission_exempt);
+
+        // The minimum applies to cheap lots
+        let cheap = ReconciliationManager::estimate_payout(&db, "item-1", 10.0).expect("quote");
+        assert_eq!(cheap.commission, 5.0);
+
+        let result_count: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM auction_results", [], |row| row.get(0))
+            .expect("count");
+        assert_eq!(result_count, 0);
+        assert!(matches!(
+            ReconciliationManager::estimate_payout(&db, "missing", 10.0),
+            Err(AppError::NotFound(_))
+        ));
+        assert!(matches!(
+            ReconciliationManager::estimate_payout(&db, "item-1", -1.0),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn picklist_groups_sold_lots_by_buyer() {
+        let (base_dir, db) = setup_db("picklist");
+        for (item_id, lot) in [("item-1", "10"), ("item-2", "2"), ("item-3", "3")] {
+            insert_listed_item(&db, "auction-1", item_id, lot);
+        }
+        db.conn
+            .execute(
+                "UPDATE inventory_items SET location = 'B-12' WHERE id = 'item-1'",
+                [],
+            )
+            .expect("Failed to set location");
+        reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             10,Test Item,1001,Zed Buyer,20000,,,\n\
+             2,Test Item,1002,Amy Buyer,9000,,,\n\
+             3,Test Item,1001,Zed Buyer,5000,,,\n",
+        );
+
+        let out_path = base_dir.join("picklist.csv");
+        let summary = ReconciliationManager::export_picklist(
+            &db,
+            "auction-1",
+            out_path.to_str().expect("Path should be valid UTF-8"),
+        )
+        .expect("picklist");
+        assert_eq!(summary.buyers, 2);
+        assert_eq!(summary.lots, 3);
+        assert_eq!(summary.missing_location, 2);
+
+        let written = fs::read_to_string(&out_path).expect("picklist file");
+        let lots: Vec<&str> = written
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').nth(2).unwrap_or(""))
+            .collect();
+        assert_eq!(lots, ["2", "3", "10"]);
+        assert!(written.contains(",10,Test Item,B-12"));
+    }
+
+    #[test]
+    fn revert_lots_resets_only_the_given_lots() {
+        let (_base_dir, db) = setup_db("revert_lots");
+        insert_listed_item(&db, "auction-1", "item-1", "A-1");
+        insert_listed_item(&db, "auction-1", "item-2", "A-2");
+        reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             A-1,Test Item,1001,Buyer One,20000,,,\n\
+             A-2,Test Item,1002,Buyer Two,9000,,,\n",
+        );
+        let count =
+            |sql: &str| -> i64 { db.conn.query_row(sql, [], |row| row.get(0)).expect("count") };
+        let sales_before = count("SELECT COUNT(*) FROM historical_sales");
+
+        let err = ReconciliationManager::revert_lots(
+            &db,
+            "auction-1",
+            &["a-1".to_string(), "A-9".to_string()],
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), "NOT_FOUND");
+        assert_eq!(count("SELECT COUNT(*) FROM auction_results"), 2);
+
+        let reverted = ReconciliationManager::revert_lots(
+            &db,
+            "auction-1",
+            &[" a-1 ".to_string(), "A-1".to_string()],
+        )
+        .expect("revert");
+        assert_eq!(reverted, 1);
+        assert_eq!(count("SELECT COUNT(*) FROM auction_results"), 1);
+        assert_eq!(
+            count("SELECT COUNT(*) FROM historical_sales"),
+            sales_before - 1
+        );
+        let (status, sold_at): (String, Option<String>) = db
+            .conn
+            .query_row(
+                "SELECT current_status, sold_at FROM inventory_items WHERE id = 'item-1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("item-1");
+        assert_eq!(status, "Listed");
+        assert!(sold_at.is_none());
+        assert_eq!(
+            count("SELECT COUNT(*) FROM inventory_items WHERE id = 'item-2' AND current_status = 'Sold'"),
+            1
+        );
+    }
+
+    #[test]
+    fn reconcile_warns_when_commission_rate_falls_back() {
+        let (_base_dir, db) = setup_db("fallback_commission");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        db.conn
+            .execute(
+                "DELETE FROM settings WHERE key = 'default_commission_rate'",
+                [],
+            )
+            .expect("Failed to delete rate");
+        db.conn
+            .execute(
+                "UPDATE settings SET value = '0.2' WHERE key = ?1",
+                params![FALLBACK_COMMISSION_RATE_SETTING],
+            )
+            .expect("Failed to set fallback rate");
+
+        let result = reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n1,Test Item,1001,Buyer One,10000,,,\n",
+        );
+
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("fallback rate 0.2"));
+        let commission: f64 = db
+            .conn
+            .query_row(
+                "SELECT commission_amount FROM auction_results WHERE item_id = 'item-1'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("auction result");
+        assert_eq!(commission, 20.0);
+    }
+
+    #[test]
+    fn reconcile_strips_auction_lot_prefix() {
+        let (_base_dir, db) = setup_db("lot_prefix");
+        insert_listed_item(&db, "auction-1", "item-1", "042");
+        db.conn
+            .execute(
+                "UPDATE auctions SET lot_prefix = 'W34' WHERE id = 'auction-1'",
+                [],
+            )
+            .expect("Failed to set lot_prefix");
+
+        let result = reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\nW34-042,Test Item,1001,Buyer One,5000,,,\n",
+        );
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.sold_count, 1);
+        assert_eq!(result.details[0].match_strategy, MatchStrategy::Exact);
+        let lot: String = db
+            .conn
+            .query_row(
+                "SELECT lot_number FROM inventory_items WHERE id = 'item-1'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("item");
+        assert_eq!(lot, "042");
+    }
+
+    #[test]
+    fn exempt_vendor_sales_carry_no_commission() {
+        let (_base_dir, db) = setup_db("commission_exempt");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        db.conn
+            .execute(
+                "UPDATE vendors SET commission_exempt = 1 WHERE name = 'Best Buy'",
+                [],
+            )
+            .expect("Failed to mark vendor exempt");
+
+        let result = reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n1,Test Item,1001,Buyer One,50000,,,\n",
+        );
+
+        assert_eq!(result.details[0].commission, 0.0);
+        assert!((result.details[0].net_profit - 360.0).abs() < 0.001);
+
+        let report = ReconciliationManager::generate_pl_report(&db).expect("P&L report");
+        assert!((report.exempt_revenue - 500.0).abs() < 0.001);
+        assert_eq!(report.total_expenses, 0.0);
+    }
+
+    #[test]
+    fn buyers_premium_is_stored_without_touching_profit() {
+        let (_base_dir, db) = setup_db("buyers_premium");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        db.conn
+            .execute(
+                "UPDATE settings SET value = '0.18' WHERE key = 'buyers_premium_rate'",
+                [],
+            )
+            .expect("Failed to update setting");
+
+        let result = reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n1,Test Item,1001,Buyer One,50000,,,\n",
+        );
+
+        assert_eq!(result.total_buyers_premium, 90.0);
+        assert_eq!(result.total_revenue, 500.0);
+        assert_eq!(result.total_profit, 360.0);
+        let premium: f64 = db
+            .conn
+            .query_row(
+                "SELECT buyers_premium FROM auction_results WHERE item_id = 'item-1'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("result");
+        assert_eq!(premium, 90.0);
+    }
+
+    #[test]
+    fn unreported_listed_items_are_reported_and_rolled_over() {
+        let (_base_dir, db) = setup_db("unreported");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        insert_listed_item(&db, "auction-1", "item-2", "2");
+        db.conn
+            .execute(
+                "UPDATE settings SET value = 'InStock' WHERE key = 'reconcile_unreported_status'",
+                [],
+            )
+            .expect("Failed to update setting");
+        let result = reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n1,Test Item,1001,Buyer One,50000,,,\n",
+        );
+
+        assert_eq!(result.unsold.len(), 1);
+        assert_eq!(result.unsold[0].item_id, "item-2");
+        assert_eq!(result.unsold[0].status, "InStock");
+        let (status, auction_id): (String, Option<String>) = db
+            .conn
+            .query_row(
+                "SELECT current_status, auction_id FROM inventory_items WHERE id = 'item-2'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("item");
+        assert_eq!(status, "InStock");
+        assert_eq!(auction_id, None);
+    }
+
+    #[test]
+    fn any_internal_bidder_id_marks_buyback() {
+        assert_eq!(parse_internal_bidder_ids("5046"), ["5046"]);
+        assert_eq!(
+            parse_internal_bidder_ids(" 5046, 7001 ,,"),
+            ["5046", "7001"]
+        );
+
+        let (_base_dir, db) = setup_db("internal_bidders");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        insert_listed_item(&db, "auction-1", "item-2", "2");
+        insert_listed_item(&db, "auction-1", "item-3", "3");
+        db.conn
+            .execute(
+                "UPDATE settings SET value = '5046, 7001' WHERE key = 'ron_larsson_bidder_id'",
+                [],
+            )
+            .expect("Failed to update setting");
+
+        let result = reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             1,Test Item,5046,House One,30000,,,\n\
+             2,Test Item,7001,House Two,20000,,,\n\
+             3,Test Item,1001,Buyer One,10000,,,\n",
+        );
+
+        assert_eq!(result.buyback_count, 2);
+        assert_eq!(result.sold_count, 1);
+        assert_eq!(result.details[1].status, "Buyback");
+    }
+
+    #[test]
+    fn brand_performance_excludes_buybacks() {
+        let (_base_dir, db) = setup_db("brand_performance");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        insert_listed_item(&db, "auction-1", "item-2", "2");
+        insert_listed_item(&db, "auction-1", "item-3", "3");
+        db.conn
+            .execute_batch(
+                "UPDATE inventory_items SET extracted_brand = 'Samsung' WHERE id IN ('item-1', 'item-2');
+                 UPDATE inventory_items SET extracted_brand = 'LG' WHERE id = 'item-3';",
+            )
+            .expect("Failed to set brands");
+
+        reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             1,Test Item,1001,Buyer One,50000,,,\n\
+             2,Test Item,1002,Buyer Two,20000,,,\n\
+             3,Test Item,5046,House,90000,,,\n",
+        );
+
+        let brands =
+            ReconciliationManager::get_brand_performance(&db, None, None).expect("brand stats");
+        assert_eq!(brands.len(), 1);
+        assert_eq!(brands[0].brand, "Samsung");
+        assert_eq!(brands[0].units_sold, 2);
+        assert_eq!(brands[0].total_revenue, 700.0);
+        assert_eq!(brands[0].total_net_profit, 420.0);
+        assert_eq!(brands[0].avg_margin_percent, 60.0);
+
+        let future = ReconciliationManager::get_brand_performance(&db, Some("2999-01-01"), None)
+            .expect("brand stats");
+        assert!(future.is_empty());
+    }
+
+    #[test]
+    fn consignor_statement_lists_vendor_sales_with_net_due() {
+        let (base_dir, db) = setup_db("consignor");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        insert_listed_item(&db, "auction-1", "item-2", "2");
+        db.conn
+            .execute(
+                "UPDATE inventory_items SET source = 'Wayfair' WHERE id = 'item-2'",
+                [],
+            )
+            .expect("Failed to set source");
+        reconcile_csv(
+            &db,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             1,Test Item,1001,Buyer One,20000,,,\n\
+             2,Test Item,1002,Buyer Two,9000,,,\n",
+        );
+
+        let vendor_id: String = db
+            .conn
+            .query_row(
+                "SELECT id FROM vendors WHERE name = 'Best Buy'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("Best Buy vendor");
+        let commission: f64 = db
+            .conn
+            .query_row(
+                "SELECT commission_amount FROM auction_results WHERE item_id = 'item-1'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("commission");
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let out_path = base_dir.join("statement.csv");
+        let statement = ReconciliationManager::export_consignor_statement(
+            &db,
+            &vendor_id,
+            "2000-01-01",
+            &today,
+            out_path.to_str().expect("Path should be valid UTF-8"),
+        )
+        .expect("statement");
+
+        assert_eq!(statement.items_sold, 1);
+        assert_eq!(statement.hammer_total, 200.0);
+        assert_eq!(statement.commission_total, round2(commission));
+        assert_eq!(statement.net_due, round2(200.0 - commission));
+        let written = fs::read_to_string(&out_path).expect("statement file");
+        assert_eq!(written.lines().count(), 3);
+        assert!(written.lines().last().unwrap().starts_with("TOTAL,"));
+
+        let err = ReconciliationManager::export_consignor_statement(
+            &db,
+            &vendor_id,
+            &today,
+            "2000-01-01",
+            out_path.to_str().expect("Path should be valid UTF-8"),
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), "VALIDATION");
+    }
+
+    #[test]
+    fn reorder_suggestions_rank_by_sell_through_and_margin() {
+        let (_base_dir, db) = setup_db("reorder");
+        for (item_id, lot) in [("item-1", "1"), ("item-2", "2"), ("item-3", "3")] {
+            insert_listed_item(&db, "auction-1", item_id, lot);
+        }
+        db.conn
+            .execute_batch(
+                "UPDATE inventory_items SET extracted_brand = 'LG', category = 'TVs';
+                 INSERT INTO historical_sales
+                    (id, normalized_title, extracted_brand, category, cost_price, sale_price, sale_date)
+                 VALUES
+                    ('hs-1', 'tv', 'Samsung', 'TVs', 30, 100, '2025-01-01'),
+                    ('hs-2', 'tv', 'Samsung', 'TVs', 40, 100, '2025-01-02'),
+                    ('hs-3', 'tv', 'Samsung', 'TVs', 50, 100, '2025-01-03'),
+                    ('hs-4', 'tv', 'LG', 'TVs', 120, 100, '2025-01-04'),
+                    ('hs-5', 'lamp', 'Ikea', NULL, 5, 10, '2025-01-05');",
+            )
+            .expect("Failed to seed sales");
+
+        let suggestions =
+            ReconciliationManager::get_reorder_suggestions(&db).expect("reorder suggestions");
+        let summary: Vec<_> = suggestions
+            .iter()
+            .map(|s| {
+                (
+                    s.brand.as_str(),
+                    s.category.as_str(),
+                    s.recommendation.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Samsung", "TVs", "Buy more"),
+                ("LG", "TVs", "Avoid"),
+                ("Ikea", "Uncategorized", "Not enough data"),
+            ]
+        );
+        assert_eq!(suggestions[0].sell_through_rate, 100.0);
+        assert_eq!(suggestions[0].avg_margin_percent, 60.0);
+        assert_eq!(suggestions[1].units_on_hand, 3);
+        assert_eq!(suggestions[1].sell_through_rate, 25.0);
+        assert_eq!(suggestions[1].avg_margin_percent, -20.0);
+    }
+
+    #[test]
+    fn detect_auction_matches_hibid_id_and_name() {
+        let (base_dir, db) = setup_db("detect");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        db.conn
+            .execute(
+                "UPDATE auctions SET hibid_auction_id = 'HB-77' WHERE id = 'auction-1'",
+                [],
+            )
+            .expect("Failed to set hibid id");
+
+        let csv_path = base_dir.join("results.csv");
+        fs::write(
+            &csv_path,
+            "Auction,Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             HB-77,1,Test Item,1001,Buyer One,30000,,,\n",
+        )
+        .expect("Failed to write csv");
+        let path = csv_path.to_str().expect("Path should be valid UTF-8");
+
+        let candidates = ReconciliationManager::detect_auction(&db, path).expect("detect");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].auction_id, "auction-1");
+        assert_eq!(candidates[0].matched_on, "hibid_auction_id");
+        assert_eq!(
+            ReconciliationManager::resolve_detected_auction(&db, path).expect("resolve"),
+            "auction-1"
+        );
+
+        let by_name = base_dir.join("by_name.csv");
+        fs::write(
+            &by_name,
+            "Auction,Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             sugarland 42,1,Test Item,1001,Buyer One,30000,,,\n",
+        )
+        .expect("Failed to write csv");
+        let candidates = ReconciliationManager::detect_auction(
+            &db,
+            by_name.to_str().expect("Path should be valid UTF-8"),
+        )
+        .expect("detect");
+        assert_eq!(candidates[0].matched_on, "name");
+
+        let no_column = base_dir.join("single.csv");
+        fs::write(
+            &no_column,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n1,Test Item,1001,Buyer One,30000,,,\n",
+        )
+        .expect("Failed to write csv");
+        let no_column = no_column.to_str().expect("Path should be valid UTF-8");
+        assert!(ReconciliationManager::detect_auction(&db, no_column)
+            .expect("detect")
+            .is_empty());
+        assert!(ReconciliationManager::resolve_detected_auction(&db, no_column).is_err());
+    }
+
+    #[test]
+    fn reconcile_multi_groups_rows_by_auction_column() {
+        let (base_dir, db) = setup_db("multi");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        db.conn
+            .execute(
+                "INSERT INTO auctions (id, name, status) VALUES ('auction-2', 'Sugarland 43', 'Active')",
+                [],
+            )
+            .expect("Failed to insert auction");
+        insert_listed_item(&db, "auction-2", "item-2", "1");
+
+        let csv_path = base_dir.join("combined.csv");
+        fs::write(
+            &csv_path,
+            "Auction,Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             auction-1,1,Test Item,1001,Buyer One,30000,,,\n\
+             Sugarland 43,1,Test Item,1002,Buyer Two,20000,,,\n",
+        )
+        .expect("Failed to write csv");
+        let path = csv_path.to_str().expect("Path should be valid UTF-8");
+
+        let results = ReconciliationManager::reconcile_multi(&db, path).expect("reconcile multi");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].auction_id, "auction-1");
+        assert_eq!(results[0].result.total_revenue, 300.0);
+        assert_eq!(results[1].auction_id, "auction-2");
+        assert_eq!(results[1].result.total_revenue, 200.0);
+
+        let single_path = base_dir.join("single.csv");
+        fs::write(
+            &single_path,
+            "Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n1,Test Item,1001,Buyer One,30000,,,\n",
+        )
+        .expect("Failed to write csv");
+        let missing_column = ReconciliationManager::reconcile_multi(
+            &db,
+            single_path.to_str().expect("Path should be valid UTF-8"),
+        );
+        assert!(missing_column.is_err());
+    }
+
+    #[test]
+    fn reconcile_multi_rejects_unknown_auction_without_changes() {
+        let (base_dir, db) = setup_db("multi_unknown");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+
+        let csv_path = base_dir.join("combined.csv");
+        fs::write(
+            &csv_path,
+            "Auction,Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             auction-1,1,Test Item,1001,Buyer One,30000,,,\n\
+             Nope,2,Other Item,1002,Buyer Two,20000,,,\n",
+        )
+        .expect("Failed to write csv");
+
+        let err = ReconciliationManager::reconcile_multi(
+            &db,
+            csv_path.to_str().expect("Path should be valid UTF-8"),
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), "NOT_FOUND");
+
+        let status: String = db
+            .conn
+            .query_row(
+                "SELECT current_status FROM inventory_items WHERE id = 'item-1'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("item status");
+        assert_eq!(status, "Listed");
+    }
+
+    #[test]
+    fn reconcile_multi_rejects_ambiguous_auction_name() {
+        let (base_dir, db) = setup_db("multi_ambiguous");
+        insert_listed_item(&db, "auction-1", "item-1", "1");
+        db.conn
+            .execute(
+                "INSERT INTO auctions (id, name, status) VALUES ('auction-2', 'sugarland 42', 'Active')",
+                [],
+            )
+            .expect("Failed to insert auction");
+
+        let csv_path = base_dir.join("combined.csv");
+        fs::write(
+            &csv_path,
+            "Auction,Lot,Title,Winning Bidder,Name,High Bid,Max Bid,Email,Phone\n\
+             Sugarland 42,1,Test Item,1001,Buyer One,30000,,,\n",
+        )
+        .expect("Failed to write csv");
+
+        let err = ReconciliationManager::reconcile_multi(
+            &db,
+            csv_path.to_str().expect("Path should be valid UTF-8"),
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), "VALIDATION");
+        let message = err.to_string();
+        assert!(
+            message.contains("auction-1") && message.contains("auction-2"),
+            "{}",
+            message
+        );
+
+        let status: String = db
+            .conn
+            .query_row(
+                "SELECT current_status FROM inventory_items WHERE id = 'item-1'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("item status");
+        assert_eq!(status, "Listed");
+    }
+}
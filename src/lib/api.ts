@@ -1,9 +1,12 @@
 import { invoke } from '@tauri-apps/api/core';
 import { open } from '@tauri-apps/plugin-dialog';
 import type {
+    Manifest,
     ManifestSummary,
     InventoryItem,
     Auction,
+    AuctionPage,
+    AuctionType,
     ReconciliationSummary,
     DashboardStats,
     Vendor,
@@ -12,7 +15,9 @@ import type {
     VendorBreakdown,
     AuctionPnlRow,
     ItemStatus,
+    NumberLocale,
     ValidationResult,
+    CsvProbe,
     FinishAuctionResult,
     AuctionReport,
     PalletManifestExportResult,
@@ -22,6 +27,18 @@ import type {
     PricingRule,
     Buybacker,
     ItemHistoryEntry,
+    ImageCheckSummary,
+    AuctionCandidate,
+    SalesAnalyticsRow,
+    ReorderSuggestion,
+    ConsignorStatement,
+    PicklistSummary,
+    PayoutEstimate,
+    DuplicateGroup,
+    StatusDuration,
+    RepriceSummary,
+    MarginRepriceSummary,
+    AssignItemsResult,
 } from '@/types';
 
 // Wrap invoke to handle mock mode if backend is not available (for dev without Rust)
@@ -75,8 +92,20 @@ export const api = {
     importManifest: (file_path: string, auction_id?: string) =>
         invokeCommand<ManifestSummary>('import_manifest', { filePath: file_path, auctionId: auction_id }),
 
-    getInventoryItems: (status?: string) =>
-        invokeCommand<InventoryItem[]>('get_inventory_items', { status }),
+    getManifests: () =>
+        invokeCommand<Manifest[]>('get_manifests'),
+
+    setManifestNotes: (manifestId: string, notes: string) =>
+        invokeCommand<void>('set_manifest_notes', { manifestId, notes }),
+
+    setManifestTags: (manifestId: string, tags: string[]) =>
+        invokeCommand<string[]>('set_manifest_tags', { manifestId, tags }),
+
+    getInventoryItems: (status?: string, manifestId?: string, source?: string) =>
+        invokeCommand<InventoryItem[]>('get_inventory_items', { status, manifestId, source }),
+
+    getInventoryByMargin: (minPct?: number, maxPct?: number) =>
+        invokeCommand<InventoryItem[]>('get_inventory_by_margin', { minPct, maxPct }),
 
     getRelistableInventoryItems: (auctionId: string) =>
         invokeCommand<InventoryItem[]>('get_relistable_inventory_items', {
@@ -84,12 +113,14 @@ export const api = {
             auction_id: auctionId,
         }),
 
-    assignItemsToAuction: (auctionId: string, itemIds: string[]) =>
-        invokeCommand<number>('assign_items_to_auction', {
+    assignItemsToAuction: (auctionId: string, itemIds: string[], strictVendor = false) =>
+        invokeCommand<AssignItemsResult>('assign_items_to_auction', {
             auctionId,
             itemIds,
+            strictVendor,
             auction_id: auctionId,
             item_ids: itemIds,
+            strict_vendor: strictVendor,
         }),
 
     getDashboardStats: () =>
@@ -98,26 +129,52 @@ export const api = {
     getVendors: () =>
         invokeCommand<Vendor[]>('get_vendors'),
 
+    cloneVendor: (sourceVendorId: string, newName: string) =>
+        invokeCommand<Vendor>('clone_vendor', { sourceVendorId, newName }),
+
     // Auctions
-    createAuction: (req: { name: string; vendor_id?: string; start_date?: string; end_date?: string }) =>
+    createAuction: (req: { name: string; vendor_id?: string; start_date?: string; end_date?: string; max_lots?: number; auction_type?: AuctionType }) =>
         invokeCommand<string>('create_auction', { req }),
 
     getAuctions: () =>
         invokeCommand<Auction[]>('get_auctions'),
 
+    getAuctionsFiltered: (
+        status?: string,
+        startDate?: string,
+        endDate?: string,
+        limit?: number,
+        offset?: number,
+    ) =>
+        invokeCommand<AuctionPage>('get_auctions_filtered', { status, startDate, endDate, limit, offset }),
+
     getAuctionById: (auctionId: string) =>
         invokeCommand<Auction>('get_auction_by_id', { auctionId }),
 
     updateAuctionStatus: (auctionId: string, status: string) =>
         invokeCommand<void>('update_auction_status', { auctionId, status }),
 
-    updateVendor: (vendorId: string, data: { cost_coefficient: number; min_price_margin: number }) =>
+    updateVendor: (vendorId: string, data: { cost_coefficient: number; min_price_margin: number; commission_exempt?: boolean }) =>
         invokeCommand<void>('update_vendor', { vendorId, data }),
 
 
     // Reconciliation
-    reconcileAuction: (auctionId: string, filePath: string) =>
-        invokeCommand<ReconciliationSummary>('reconcile_auction', { auctionId, filePath }),
+    reconcileAuction: (auctionId: string | null, filePath: string, autoDetect = false) =>
+        invokeCommand<ReconciliationSummary>('reconcile_auction', { auctionId, filePath, autoDetect }),
+
+    detectAuctionFromResults: (filePath: string) =>
+        invokeCommand<AuctionCandidate[]>('detect_auction_from_results', { filePath }),
+
+    // Resolves to the number of items put back to Listed
+    revertLots: (auctionId: string, lotNumbers: string[]) =>
+        invokeCommand<number>('revert_lots', { auctionId, lotNumbers }),
+
+    // Quote only; nothing is written
+    estimatePayout: (itemId: string, estimatedHammer: number) =>
+        invokeCommand<PayoutEstimate>('estimate_payout', { itemId, estimatedHammer }),
+
+    exportPicklist: (auctionId: string, filePath: string) =>
+        invokeCommand<PicklistSummary>('export_picklist', { auctionId, filePath }),
 
     getPlReport: () =>
         invokeCommand<ProfitLossReport>('get_pl_report'),
@@ -154,21 +211,57 @@ export const api = {
     saveSetting: (key: string, value: string) =>
         invokeCommand<void>('save_setting', { key, value }),
 
+    setCurrentUser: (name: string) =>
+        invokeCommand<string>('set_current_user', { name }),
+
     updateItemStatus: (itemId: string, status: ItemStatus) =>
         invokeCommand<void>('update_item_status', { itemId, status }),
 
-    exportInventoryCsv: (filePath: string, status?: string) =>
-        invokeCommand<number>('export_inventory_csv', { filePath, status }),
+    exportInventoryCsv: (
+        filePath: string,
+        status?: string,
+        numberLocale?: NumberLocale,
+        manifestId?: string,
+        source?: string,
+    ) =>
+        invokeCommand<number>('export_inventory_csv', { filePath, status, numberLocale, manifestId, source }),
 
     getAuctionPnlList: () =>
         invokeCommand<AuctionPnlRow[]>('get_auction_pnl_list'),
 
+    getAuctionPnl: (auctionId: string, includeBuybackCost?: boolean) =>
+        invokeCommand<AuctionPnlRow>('get_auction_pnl', { auctionId, includeBuybackCost }),
+
+    getSalesAnalytics: (brand?: string, category?: string) =>
+        invokeCommand<SalesAnalyticsRow[]>('get_sales_analytics', { brand, category }),
+
+    exportSalesAnalyticsCsv: (filePath: string, brand?: string, category?: string) =>
+        invokeCommand<number>('export_sales_analytics_csv', { filePath, brand, category }),
+
+    getReorderSuggestions: () =>
+        invokeCommand<ReorderSuggestion[]>('get_reorder_suggestions'),
+
+    getStatusDurations: (startDate?: string, endDate?: string) =>
+        invokeCommand<StatusDuration[]>('get_status_durations', { startDate, endDate }),
+
+    exportConsignorStatement: (vendorId: string, startDate: string, endDate: string, filePath: string) =>
+        invokeCommand<ConsignorStatement>('export_consignor_statement', { vendorId, startDate, endDate, filePath }),
+
+    recomputeSeasons: () =>
+        invokeCommand<number>('recompute_seasons'),
+
     unassignItem: (itemId: string) =>
         invokeCommand<void>('unassign_item', { itemId }),
 
     validateCsv: (filePath: string) =>
         invokeCommand<ValidationResult>('validate_csv', { filePath }),
 
+    validateHibidResults: (filePath: string) =>
+        invokeCommand<ValidationResult>('validate_hibid_results', { filePath }),
+
+    probeCsv: (filePath: string) =>
+        invokeCommand<CsvProbe>('probe_csv', { filePath }),
+
     // Finish Auction & Reports
     finishAuction: (auctionId: string, resultsCsvPath: string) =>
         invokeCommand<FinishAuctionResult>('finish_auction', { auctionId, resultsCsvPath }),
@@ -218,6 +311,38 @@ export const api = {
     updateItemBuybacker: (itemId: string, buybackerId: string) =>
         invokeCommand<void>('update_item_buybacker', { itemId, buybackerId }),
 
+    updateItemNotes: (itemId: string, notes: string) =>
+        invokeCommand<void>('update_item_notes', { itemId, notes }),
+
+    updateItemPrices: (itemId: string, costPrice?: number, minPrice?: number, allowBelowCost?: boolean) =>
+        invokeCommand<void>('update_item_prices', { itemId, costPrice, minPrice, allowBelowCost }),
+
+    setItemLocation: (itemId: string, location: string) =>
+        invokeCommand<void>('set_item_location', { itemId, location }),
+
+    setScrapValue: (itemId: string, scrapValue: number) =>
+        invokeCommand<void>('set_scrap_value', { itemId, scrapValue }),
+
+    flagItem: (itemId: string, reason: string) =>
+        invokeCommand<void>('flag_item', { itemId, reason }),
+
+    unflagItem: (itemId: string) =>
+        invokeCommand<void>('unflag_item', { itemId }),
+
+    getFlaggedItems: () =>
+        invokeCommand<InventoryItem[]>('get_flagged_items'),
+
+    // Candidate double imports among InStock items (threshold 0-1, default 0.8)
+    findDuplicateItems: (threshold?: number) =>
+        invokeCommand<DuplicateGroup[]>('find_duplicate_items', { threshold }),
+
+    getItemsNeedingTitle: () =>
+        invokeCommand<InventoryItem[]>('get_items_needing_title'),
+
+    // Resolves to whether the item still needs a better title
+    updateItemTitle: (itemId: string, title: string) =>
+        invokeCommand<boolean>('update_item_title', { itemId, title }),
+
     // Pricing Rules
     getPricingRules: () =>
         invokeCommand<PricingRule[]>('get_pricing_rules'),
@@ -230,14 +355,22 @@ export const api = {
         vendorCosts: Record<string, number>,
         conditionMarginsBySupplier: Record<string, Record<string, number>>,
         conditionMargins?: Record<string, number>,
+        allowBelowCost?: boolean,
     ) =>
         invokeCommand<number>('recalculate_prices', {
             auctionId,
             vendorCosts,
             conditionMarginsBySupplier,
             conditionMargins,
+            allowBelowCost,
         }),
 
+    repriceInventory: (status?: ItemStatus, source?: string, allowBelowCost?: boolean) =>
+        invokeCommand<RepriceSummary>('reprice_inventory', { status, source, allowBelowCost }),
+
+    repriceToMargin: (targetMarginPct: number, status?: ItemStatus, source?: string) =>
+        invokeCommand<MarginRepriceSummary>('reprice_to_margin', { targetMarginPct, status, source }),
+
     // Buy-backers
     getBuybackers: () =>
         invokeCommand<Buybacker[]>('get_buybackers'),
@@ -273,6 +406,18 @@ export const api = {
     renameAuction: (auctionId: string, name: string) =>
         invokeCommand<void>('rename_auction', { auctionId, name }),
 
+    setAuctionLotPrefix: (auctionId: string, lotPrefix: string | null) =>
+        invokeCommand<void>('set_auction_lot_prefix', { auctionId, lotPrefix }),
+
+    setAuctionMaxLots: (auctionId: string, maxLots: number | null) =>
+        invokeCommand<void>('set_auction_max_lots', { auctionId, maxLots }),
+
+    setAuctionType: (auctionId: string, auctionType: AuctionType) =>
+        invokeCommand<void>('set_auction_type', { auctionId, auctionType }),
+
+    checkAuctionImages: (auctionId: string, imageDir: string) =>
+        invokeCommand<ImageCheckSummary>('check_auction_images', { auctionId, imageDir }),
+
     deleteAuction: (auctionId: string) =>
         invokeCommand<void>('delete_auction', { auctionId }),
         
@@ -332,7 +477,7 @@ function mockResponse(cmd: string): any {
                 created_at: new Date().toISOString(),
             }));
         case 'assign_items_to_auction':
-            return 3;
+            return { assigned_count: 3, vendor_mismatches: [] };
         case 'get_auctions':
             return [
                 { id: '1', name: 'Weekly Auction #45', status: 'Active', total_lots: 0, created_at: new Date().toISOString() },
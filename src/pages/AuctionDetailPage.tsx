@@ -191,8 +191,8 @@ export function AuctionDetailPage() {
     const assignSelectedItemsToAuction = async () => {
         if (!auction || selectedRelistIds.length === 0) return;
         try {
-            const assigned = await api.assignItemsToAuction(auction.id, selectedRelistIds);
-            toast.success(`Added ${assigned} item(s) to auction`);
+            const result = await api.assignItemsToAuction(auction.id, selectedRelistIds);
+            toast.success(`Added ${result.assigned_count} item(s) to auction`);
             setShowRelistDialog(false);
             await loadAuctionData(auction.id);
         } catch (err) {
@@ -9,6 +9,7 @@ export interface Vendor {
     cost_coefficient: number;
     min_price_margin: number;
     is_active: boolean;
+    commission_exempt?: boolean;
     created_at: string;
 }
 
@@ -21,7 +22,9 @@ export interface Manifest {
     total_cost: number;
     items_count: number;
     status: ManifestStatus;
-    notes?: string;
+    notes?: string | null;
+    tags: string[];
+    created_by: string;
 }
 
 export type ManifestStatus = 'Imported' | 'Listed' | 'Closed';
@@ -64,10 +67,25 @@ export interface InventoryItem {
     sale_order?: number;
     buybacker_id?: string;
 
+    // Staff note, appended to the HiBid description
+    notes?: string | null;
+    // (min_price - cost) / min_price in percent
+    gross_margin_pct?: number | null;
+    // Manager review queue
+    is_flagged?: boolean;
+    flag_reason?: string | null;
+    // Title was missing or unusable on import
+    needs_title?: boolean;
+    // Warehouse bin/shelf, printed on picklists
+    location?: string | null;
+
     created_at: string;
     updated_at: string;
 }
 
+// Decimal separator for numbers in CSV exports
+export type NumberLocale = 'Dot' | 'Comma';
+
 export type ItemStatus = 'InStock' | 'Listed' | 'Sold' | 'Unsold' | 'FloorSale' | 'Buyback' | 'Scrap';
 
 // --- Auction ---
@@ -81,10 +99,37 @@ export interface Auction {
     status: AuctionStatus;
     total_lots: number;
     created_at: string;
+    lot_prefix?: string | null;
+    created_by?: string;
+    max_lots?: number | null;
+    // Picks default_commission_rate or cash_sale_commission_rate
+    auction_type?: AuctionType;
 }
 
 export type AuctionStatus = 'Active' | 'Completed';
 
+export type AuctionType = 'Standard' | 'Cash';
+
+export interface AuctionPage {
+    auctions: Auction[];
+    // Matching auctions across all pages
+    total_count: number;
+}
+
+export interface VendorMismatch {
+    item_id: string;
+    lot_number?: string;
+    source?: string;
+    matched_vendor?: string;
+}
+
+export interface AssignItemsResult {
+    assigned_count: number;
+    vendor_mismatches: VendorMismatch[];
+    // Eligible items left out because the auction reached max_lots
+    capacity_rejected?: number;
+}
+
 // --- Auction Result ---
 export interface AuctionResult {
     id: string;
@@ -205,6 +250,10 @@ export interface ManifestSummary {
     items_count: number;
     total_retail: number;
     total_cost: number;
+    auto_scrapped: number;
+    needs_title_count?: number;
+    // Rows whose cost/retail looks wrong for the vendor or brand
+    cost_warnings?: string[];
 }
 
 // --- Reconciliation ---
@@ -213,7 +262,20 @@ export interface ReconciliationSummary {
     buyback_count: number;
     total_revenue: number;
     total_profit: number;
+    // Paid by buyers on top of the hammer price; not part of revenue or profit
+    total_buyers_premium?: number;
     errors: string[];
+    // Non-fatal problems, e.g. the commission rate fell back because its setting is broken
+    warnings?: string[];
+    // Listed items the results file never mentioned
+    unsold?: UnreportedLot[];
+}
+
+export interface UnreportedLot {
+    item_id: string;
+    lot_number: string;
+    title: string;
+    status: ItemStatus;
 }
 
 // --- Dashboard Stats ---
@@ -242,6 +304,9 @@ export interface ProfitLossReport {
     unsold_count: number;
     sell_through_rate: number;
     avg_sale_price: number;
+    // Cost of scrapped items less salvage, already subtracted from net_profit
+    scrap_writeoff: number;
+    scrapped_count: number;
     period_label: string;
 }
 
@@ -287,6 +352,18 @@ export interface ValidationResult {
     warnings: string[];
 }
 
+export type CsvEncoding = 'UTF-8' | 'UTF-8 (BOM)' | 'UTF-16LE' | 'UTF-16BE' | 'Latin-1';
+
+export interface CsvProbe {
+    delimiter: string;
+    delimiter_name: 'comma' | 'tab' | 'semicolon' | 'pipe';
+    encoding: CsvEncoding;
+    headers: string[];
+    row_count: number;
+    row_count_exact: boolean;
+    file_size: number;
+}
+
 // --- Auction Reports ---
 export interface AuctionReport {
     id: string;
@@ -347,3 +424,108 @@ export interface ItemHistoryEntry {
     bidder_name: string;
     is_buyback: boolean;
 }
+
+// --- Pre-export Image Check ---
+export interface MissingImageItem {
+    item_id: string;
+    lot_number: string;
+    title: string;
+}
+
+export interface ImageCheckSummary {
+    listed_count: number;
+    with_images: number;
+    missing_count: number;
+    missing: MissingImageItem[];
+}
+
+// --- Results File Auction Detection ---
+export interface AuctionCandidate {
+    file_value: string;
+    auction_id: string;
+    auction_name: string;
+    hibid_auction_id?: string | null;
+    status: string;
+    matched_on: 'id' | 'hibid_auction_id' | 'name';
+}
+
+// --- Sales Analytics (v_sales_analytics) ---
+export interface SalesAnalyticsRow {
+    brand?: string | null;
+    category?: string | null;
+    season?: string | null;
+    sales_count: number;
+    avg_sale_price: number;
+    avg_retail_price: number;
+    first_sale?: string | null;
+    last_sale?: string | null;
+}
+
+export interface RepriceSummary {
+    matched: number;
+    changed: number;
+    cost_delta: number;
+    min_price_delta: number;
+}
+
+export interface MarginRepriceSummary {
+    matched: number;
+    changed: number;
+    min_price_low: number | null;
+    min_price_high: number | null;
+}
+
+export type ReorderRecommendation = 'Buy more' | 'Hold' | 'Avoid' | 'Not enough data';
+
+export interface StatusDuration {
+    from_status: ItemStatus;
+    to_status: ItemStatus;
+    item_count: number;
+    avg_days: number;
+    median_days: number;
+}
+
+export interface ConsignorStatement {
+    vendor_name: string;
+    items_sold: number;
+    hammer_total: number;
+    commission_total: number;
+    net_due: number;
+}
+
+export interface DuplicateGroup {
+    source: string;
+    // Lowest title similarity among the pairs that joined the group
+    similarity: number;
+    items: InventoryItem[];
+}
+
+export interface PayoutEstimate {
+    item_id: string;
+    estimated_hammer: number;
+    commission_rate: number;
+    commission: number;
+    commission_exempt: boolean;
+    net_to_consignor: number;
+    cost_price: number;
+    net_after_cost: number;
+    rate_is_fallback: boolean;
+}
+
+export interface PicklistSummary {
+    buyers: number;
+    lots: number;
+    missing_location: number;
+}
+
+export interface ReorderSuggestion {
+    brand: string;
+    category: string;
+    units_sold: number;
+    units_on_hand: number;
+    sell_through_rate: number;
+    avg_margin_percent: number;
+    total_revenue: number;
+    score: number;
+    recommendation: ReorderRecommendation;
+}